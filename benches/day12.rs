@@ -0,0 +1,40 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use aoc22::year2022::day12::{self, Cell, Endpoints, Topology};
+
+// Rolling hills, so heights change gently and every height band forms large plateaus.
+fn terrain(size: usize) -> Topology {
+    let levels = (0..size * size)
+        .map(|index| {
+            let (x, y) = ((index % size) as f64, (index / size) as f64);
+            (127.5 + 64.0 * (x / 310.0).sin() + 63.0 * (y / 170.0).cos()) as u8
+        })
+        .collect::<Vec<_>>();
+    Topology::from_gray(size, size, &levels, Endpoints::Extremes).unwrap()
+}
+
+fn is_start(cell: &Cell) -> bool {
+    matches!(cell, Cell::Start(_))
+}
+
+fn is_end(cell: &Cell) -> bool {
+    matches!(cell, Cell::End(_))
+}
+
+fn contraction_benchmark(c: &mut Criterion) {
+    let topology = terrain(4000);
+    assert!(day12::walk_contracted(topology.clone(), is_start, day12::climbable, is_end).is_ok());
+
+    let mut group = c.benchmark_group("day12");
+    group.sample_size(10);
+    group.bench_function("walk/4000", |b| {
+        b.iter(|| day12::walk(black_box(topology.clone()), is_start, day12::climbable, is_end))
+    });
+    group.bench_function("walk_contracted/4000", |b| {
+        b.iter(|| day12::walk_contracted(black_box(topology.clone()), is_start, day12::climbable, is_end))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, contraction_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::{collections::VecDeque, hint::black_box};
+use aoc22::year2022::day05::{self, CraneAction, CraneModel, PersistentStack, StackStore, Stacks};
+
+fn replay<S: StackStore>(stacks: &Stacks<S>, actions: &[CraneAction]) -> String {
+    let mut stacks = stacks.clone();
+    for action in actions {
+        stacks.apply(action, CraneModel::CrateMover9001).unwrap();
+    }
+    stacks.top()
+}
+
+fn store_benchmark(c: &mut Criterion) {
+    let (stacks, actions) = day05::read_input(include_str!("../src/year2022/data/day5_challenge.txt")).unwrap();
+    let deque = stacks.with_store::<VecDeque<char>>();
+    let persistent = stacks.with_store::<PersistentStack>();
+
+    let mut group = c.benchmark_group("day5");
+    group.bench_function("store/vec", |b| b.iter(|| replay(black_box(&stacks), &actions)));
+    group.bench_function("store/vec_deque", |b| b.iter(|| replay(black_box(&deque), &actions)));
+    group.bench_function("store/persistent", |b| b.iter(|| replay(black_box(&persistent), &actions)));
+    group.finish();
+}
+
+criterion_group!(benches, store_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use aoc22::year2022::day06;
+
+fn marker_benchmark(c: &mut Criterion) {
+    let input = include_str!("../src/year2022/data/day6_challenge.txt").trim().repeat(1000);
+    let bytes = input.as_bytes();
+
+    // Markers of 4 and 14 show up early, no 27 distinct lowercase letters exist so that one scans everything.
+    let mut group = c.benchmark_group("day6");
+    group.sample_size(10);
+    for window in [4, 14, 27] {
+        group.bench_function(format!("find_distinct_window/{window}"), |b| {
+            b.iter(|| day06::find_distinct_window(black_box(bytes), black_box(window)))
+        });
+        group.bench_function(format!("find_marker_in/{window}"), |b| {
+            b.iter(|| day06::find_marker_in(black_box(bytes), black_box(window)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, marker_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use aoc22::year2022::day07::{self, NodeHandle};
+
+// One directory per level, each holding a single file.
+fn deep_session(depth: usize) -> String {
+    let mut session = String::from("$ cd /\n");
+    for level in 1..=depth {
+        session.push_str(&format!("$ ls\ndir d\n{level} f\n$ cd d\n"));
+    }
+    session
+}
+
+fn sum_total_sizes(dirs: &[NodeHandle]) -> u64 {
+    dirs.iter().map(|dir| dir.borrow().total_size()).sum()
+}
+
+fn total_size_benchmark(c: &mut Criterion) {
+    let root = day07::read_input(&deep_session(1_000)).unwrap();
+    let dirs = day07::all_dirs(root.clone()).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("day7");
+    group.sample_size(10);
+    group.bench_function("read_input/deep", |b| b.iter(|| day07::read_input(black_box(&deep_session(1_000)))));
+
+    // Dropping the deepest cache drops every cache above it, so each directory walks its whole subtree.
+    day07::invalidate_total_size(dirs.last().unwrap());
+    let uncached = sum_total_sizes(&dirs);
+    group.bench_function("total_sizes/uncached", |b| b.iter(|| sum_total_sizes(black_box(&dirs))));
+
+    day07::cache_total_sizes(&root);
+    assert_eq!(sum_total_sizes(&dirs), uncached);
+    group.bench_function("total_sizes/cached", |b| b.iter(|| sum_total_sizes(black_box(&dirs))));
+    group.finish();
+}
+
+criterion_group!(benches, total_size_benchmark);
+criterion_main!(benches);
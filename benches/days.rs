@@ -0,0 +1,54 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use aoc22::{
+    solution,
+    year2022::{day01, day02, day03, day04, day05, day07, day08, day09, day10, day11, day12},
+};
+
+fn read_data(day: u8, kind: &str) -> String {
+    let path = format!("{}/src/year2022/data/day{}_{}.txt", env!("CARGO_MANIFEST_DIR"), day, kind);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
+
+// Day 6 has nothing to parse, its input is the datastream itself.
+type Parser = fn(&str) -> bool;
+
+const PARSERS: &[(u8, Parser)] = &[
+    (1, |content| day01::read_input(content).is_ok()),
+    (2, |content| day02::read_rounds(content).is_ok()),
+    (3, |content| day03::read_input(content).is_ok()),
+    (4, |content| day04::read_input(content).is_ok()),
+    (5, |content| day05::read_input(content).is_ok()),
+    (7, |content| day07::read_input(content).is_ok()),
+    (8, |content| day08::read_input(content).is_ok()),
+    (9, |content| day09::read_input(content).is_ok()),
+    (10, |content| day10::read_input(content).is_ok()),
+    (11, |content| day11::read_input(content).is_ok()),
+    (12, |content| day12::Topology::parse(content).is_ok()),
+];
+
+// Parsing alone, then each part end to end: the gap between the two is the solving time.
+fn days_benchmark(c: &mut Criterion) {
+    for (day, solution) in solution::days(2022) {
+        let mut group = c.benchmark_group(format!("day{}", day));
+        group.sample_size(10);
+
+        for kind in ["example", "challenge"] {
+            let content = read_data(day, kind);
+
+            if let Some((_, parse)) = PARSERS.iter().find(|(number, _)| *number == day) {
+                assert!(parse(&content), "day {} {} input does not parse", day, kind);
+                group.bench_function(format!("parse/{}", kind), |b| b.iter(|| parse(black_box(&content))));
+            }
+            for part in [1, 2] {
+                group.bench_function(format!("part{}/{}", part, kind), |b| {
+                    b.iter(|| solution.part(part, black_box(&content)))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,193 @@
+use camino::Utf8Path;
+use std::{collections::BTreeMap, fmt, fs};
+use thiserror::Error;
+use crate::runner::PartRun;
+
+// Accepted answers by year, day and part, as kept in answers.toml:
+//
+//     [2022.day1]
+//     part1 = "74394"
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KnownAnswers(BTreeMap<(u16, u8, u8), String>);
+
+impl KnownAnswers {
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let mut answers = BTreeMap::new();
+
+        for (year_key, days) in content.parse::<toml::Table>()? {
+            let (Ok(year), Some(days)) = (year_key.parse::<u16>(), days.as_table()) else {
+                return Err(Error::Entry(year_key));
+            };
+
+            for (day_key, parts) in days {
+                let day = day_key.strip_prefix("day").and_then(|day| day.parse::<u8>().ok());
+                let (Some(day), Some(parts)) = (day, parts.as_table()) else {
+                    return Err(Error::Entry(format!("{}.{}", year_key, day_key)));
+                };
+
+                for (part_key, answer) in parts {
+                    let part = match part_key.as_str() {
+                        "part1" => Some(1),
+                        "part2" => Some(2),
+                        _ => None,
+                    };
+                    let (Some(part), Some(answer)) = (part, answer.as_str()) else {
+                        return Err(Error::Entry(format!("{}.{}.{}", year_key, day_key, part_key)));
+                    };
+                    answers.insert((year, day, part), answer.to_string());
+                }
+            }
+        }
+
+        Ok(KnownAnswers(answers))
+    }
+
+    pub fn load(path: &Utf8Path) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+// A part whose answer is no longer the accepted one, or that does not find one at all.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Result<String, String>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} part {}: ", self.year, self.day, self.part)?;
+        match &self.actual {
+            Err(e) => write!(f, "expected {}, failed with {}", self.expected, e),
+            Ok(actual) if !actual.contains('\n') && !self.expected.contains('\n') => {
+                write!(f, "expected {}, got {}", self.expected, actual)
+            }
+            Ok(actual) => write!(f, "expected\n{}\ngot\n{}", self.expected, actual),
+        }
+    }
+}
+
+// Rows of lit pixels, without trailing dark pixels nor surrounding blank lines, so that rendered
+// letters compare the same whatever characters draw them.
+pub fn normalize_crt(rendered: &str) -> Vec<Vec<bool>> {
+    let lines = rendered.lines().collect::<Vec<_>>();
+    let first = lines.iter().position(|line| !line.trim().is_empty()).unwrap_or(lines.len());
+    let last = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(first, |last| last + 1);
+
+    lines[first..last]
+        .iter()
+        .map(|line| {
+            let mut pixels = line.chars().map(|c| c == '#' || c == '█').collect::<Vec<_>>();
+            while pixels.last() == Some(&false) {
+                pixels.pop();
+            }
+            pixels
+        })
+        .collect()
+}
+
+// Rendered letters compare by their lit pixels, any other answer as it is.
+pub fn same_answer(expected: &str, actual: &str) -> bool {
+    let rendered = |answer: &str| answer.contains('\n') && answer.chars().all(|c| matches!(c, '#' | '█' | '.' | ' ' | '\n'));
+    expected == actual || rendered(expected) && rendered(actual) && normalize_crt(expected) == normalize_crt(actual)
+}
+
+// Parts without an accepted answer are not checked.
+pub fn verify(known: &KnownAnswers, runs: &[PartRun]) -> Vec<Change> {
+    runs.iter()
+        .filter_map(|run| {
+            let expected = known.get(run.year, run.day, run.part)?;
+            let actual = match &run.answer {
+                Ok(answer) if same_answer(expected, &answer.to_string()) => return None,
+                Ok(answer) => Ok(answer.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            Some(Change { year: run.year, day: run.day, part: run.part, expected: expected.to_string(), actual })
+        })
+        .collect()
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid answer entry {0}, expected YEAR.dayN.part1 or YEAR.dayN.part2 with a string answer")]
+    Entry(String),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => crate::Error::Io(e),
+            e => crate::Error::parse(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::*;
+    use crate::{input::IntoInput, runner::{self, DayInput}};
+
+    #[test]
+    fn answer_changes() -> Result<(), Error> {
+        let known = KnownAnswers::parse("[2022.day1]\npart1 = \"7\"\npart2 = \"8\"\n\n[2022.day10]\npart2 = '''\n#.\n.#'''\n")?;
+        assert_eq!((known.len(), known.get(2022, 10, 2), known.get(2023, 10, 2)), (3, Some("#.\n.#"), None));
+        assert!(matches!(KnownAnswers::parse("[2022.day1]\npart3 = \"7\"\n"), Err(Error::Entry(e)) if e == "2022.day1.part3"));
+        assert!(matches!(KnownAnswers::parse("[2022.day1]\npart1 = 7\n"), Err(Error::Entry(_))));
+        assert!(matches!(KnownAnswers::parse("[2022.dayx]\n"), Err(Error::Entry(_))));
+        assert!(matches!(KnownAnswers::parse("[day1]\n"), Err(Error::Entry(_))));
+
+        let run = |day, part, answer: Result<&str, crate::Error>| PartRun {
+            year: 2022,
+            day,
+            part,
+            answer: answer.map(Into::into),
+            elapsed: Default::default(),
+            source: None,
+            peak_memory: None,
+        };
+        let runs = [
+            run(1, 1, Ok("7")),
+            run(1, 2, Ok("9")),
+            run(2, 1, Ok("1")),
+            run(10, 2, Err(crate::Error::solve("no letters"))),
+        ];
+        let changes = verify(&known, &runs);
+        assert_eq!(changes.iter().map(|c| (c.day, c.part)).collect::<Vec<_>>(), vec![(1, 2), (10, 2)]);
+        assert!(verify(&known, &[run(10, 2, Ok("\n█  \n █\n"))]).is_empty());
+        assert_eq!(verify(&known, &[run(10, 2, Ok("#.\n##"))]).len(), 1);
+        assert_eq!(changes[0].to_string(), "2022 day 1 part 2: expected 8, got 9");
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_answers_unchanged() -> Result<(), crate::Error> {
+        let known = KnownAnswers::load("answers.toml".into())?;
+        let runs = runner::run_all(2022, None, |day| {
+            let path = format!("src/year2022/data/day{}_challenge.txt", day);
+            Ok(DayInput { content: Utf8Path::new(&path).into_input()?, source: path })
+        });
+
+        let changes = verify(&known, &runs);
+        assert!(changes.is_empty(), "{}", changes.iter().map(Change::to_string).collect::<Vec<_>>().join("\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    sync::{Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+// adventofcode.com asks automated tools to say who they are.
+pub const USER_AGENT: &str = concat!("github.com/abestel/aoc22/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Policy {
+    // Between the start of two requests, across every client and thread of the process.
+    pub min_interval: Duration,
+    pub retries: u32,
+    // Wait before the first retry, doubled after each one.
+    pub backoff: Duration,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy { min_interval: Duration::from_secs(1), retries: 3, backoff: Duration::from_secs(2) }
+    }
+}
+
+#[derive(Debug)]
+struct Throttle {
+    last: Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    const fn new() -> Self {
+        Throttle { last: Mutex::new(None) }
+    }
+
+    // How long to wait before sending, the slot being taken right away so that concurrent callers
+    // queue up behind each other.
+    fn reserve(&self, interval: Duration, now: Instant) -> Duration {
+        let mut last = self.last.lock().unwrap_or_else(PoisonError::into_inner);
+        let at = last.map_or(now, |previous| (previous + interval).max(now));
+        *last = Some(at);
+        at - now
+    }
+}
+
+static THROTTLE: Throttle = Throttle::new();
+
+// A request that may have reached the site, like a submission that timed out, is only retried when
+// sending it twice is harmless.
+fn transient(error: &ureq::Error, idempotent: bool) -> bool {
+    match error {
+        ureq::Error::ConnectionFailed | ureq::Error::StatusCode(429 | 503) => true,
+        ureq::Error::StatusCode(500..=599) | ureq::Error::Io(_) | ureq::Error::Timeout(_) => idempotent,
+        _ => false,
+    }
+}
+
+fn with_retries<T>(
+    policy: &Policy,
+    idempotent: bool,
+    mut sleep: impl FnMut(Duration),
+    mut attempt: impl FnMut() -> Result<T, ureq::Error>,
+) -> Result<T, ureq::Error> {
+    let mut backoff = policy.backoff;
+    for _ in 0..policy.retries {
+        match attempt() {
+            Err(e) if transient(&e, idempotent) => {
+                tracing::warn!("{}, retrying in {:?}", e, backoff);
+                sleep(backoff);
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+    attempt()
+}
+
+// Every request to adventofcode.com goes through here: throttled, retried, and authenticated with
+// the session cookie unless it is empty.
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    session: String,
+    policy: Policy,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self::with_policy(session, Policy::default())
+    }
+
+    pub fn with_policy(session: impl Into<String>, policy: Policy) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Client { agent, session: session.into(), policy }
+    }
+
+    fn send(&self, idempotent: bool, mut request: impl FnMut() -> Result<String, ureq::Error>) -> Result<String, ureq::Error> {
+        with_retries(&self.policy, idempotent, thread::sleep, || {
+            thread::sleep(THROTTLE.reserve(self.policy.min_interval, Instant::now()));
+            request()
+        })
+    }
+
+    fn authenticated<B>(&self, request: ureq::RequestBuilder<B>) -> ureq::RequestBuilder<B> {
+        match self.session.as_str() {
+            "" => request,
+            session => request.header("Cookie", &format!("session={}", session)),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> Result<String, ureq::Error> {
+        self.send(true, || {
+            let mut response = self.authenticated(self.agent.get(url)).call()?;
+            response.body_mut().read_to_string()
+        })
+    }
+
+    pub fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String, ureq::Error> {
+        self.send(false, || {
+            let mut response = self.authenticated(self.agent.post(url)).send_form(form.iter().copied())?;
+            response.body_mut().read_to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::*;
+
+    #[test]
+    fn throttled_requests() {
+        let throttle = Throttle::new();
+        let now = Instant::now();
+        let second = Duration::from_secs(1);
+
+        assert_eq!(throttle.reserve(second, now), Duration::ZERO);
+        assert_eq!(throttle.reserve(second, now), second);
+        assert_eq!(throttle.reserve(second, now + Duration::from_millis(500)), Duration::from_millis(1500));
+        assert_eq!(throttle.reserve(second, now + Duration::from_secs(10)), Duration::ZERO);
+    }
+
+    #[test]
+    fn retried_failures() {
+        let policy = Policy { min_interval: Duration::ZERO, retries: 3, backoff: Duration::from_millis(10) };
+        let mut sleeps = Vec::new();
+        let mut failures = vec![ureq::Error::StatusCode(502), ureq::Error::ConnectionFailed];
+        let result = with_retries(&policy, true, |wait| sleeps.push(wait), || failures.pop().map_or(Ok("ok"), Err));
+        assert_eq!(result.ok(), Some("ok"));
+        assert_eq!(sleeps, vec![Duration::from_millis(10), Duration::from_millis(20)]);
+
+        let mut attempts = 0;
+        let result = with_retries(&policy, false, |_| (), || {
+            attempts += 1;
+            Err::<(), _>(ureq::Error::StatusCode(502))
+        });
+        assert!(matches!(result, Err(ureq::Error::StatusCode(502))));
+        assert_eq!(attempts, 1);
+
+        let mut attempts = 0;
+        let result = with_retries(&policy, false, |_| (), || {
+            attempts += 1;
+            Err::<(), _>(ureq::Error::StatusCode(429))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 4);
+        assert!(!transient(&ureq::Error::StatusCode(404), true));
+    }
+}
@@ -0,0 +1,86 @@
+use thiserror::Error;
+use crate::{
+    client::Client,
+    input::{self, InputCache},
+    render::{html, style::ColorSupport},
+};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] input::Error),
+    #[error("No puzzle description in the page of day {1} of {0}, try again with --refresh")]
+    Missing(u16, u8),
+}
+
+pub fn url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}", year, day)
+}
+
+// Without a session only the first part is served, the second one shows up once the first is solved.
+pub fn download(year: u16, day: u8, session: &str) -> Result<String, input::Error> {
+    let url = url(year, day);
+    Client::new(session).get(&url).map_err(|e| input::Error::Http(url, Box::new(e)))
+}
+
+// The page is cached as served, `refresh` fetches it again, e.g. to get the second part.
+pub fn get(cache: &InputCache, year: u16, day: u8, refresh: bool) -> Result<String, Error> {
+    let path = cache.description_path(year, day);
+    if refresh {
+        InputCache::remove(&path)?;
+    }
+    Ok(cache.get_or_fetch_at(path, year, day, || download(year, day, &input::session().unwrap_or_default()))?)
+}
+
+// One per part, the rest of the page is the site's chrome.
+fn articles(page: &str) -> Vec<&str> {
+    page.split("<article")
+        .skip(1)
+        .filter_map(|article| article.split_once('>'))
+        .map(|(_, article)| article.split_once("</article>").map_or(article, |(article, _)| article))
+        .collect()
+}
+
+fn render(page: &str, colors: ColorSupport, year: u16, day: u8) -> Result<String, Error> {
+    let articles = articles(page);
+    if articles.is_empty() {
+        return Err(Error::Missing(year, day));
+    }
+    Ok(articles.iter().map(|article| html::to_terminal(article, colors)).collect::<Vec<_>>().join("\n"))
+}
+
+// Styled for the terminal when it supports colors, plain text otherwise.
+pub fn to_terminal(page: &str, year: u16, day: u8) -> Result<String, Error> {
+    render(page, ColorSupport::detect(), year, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use crate::description::*;
+
+    #[test]
+    fn cached_description() -> Result<(), Error> {
+        let page = "<html><main>\n<article class=\"day-desc\"><h2>--- Day 14: Regolith Reservoir ---</h2>\
+            <p>The sand is <em>pouring</em> in:</p><pre><code>498,4 -&gt; 498,6\n</code></pre></article>\n\
+            <p>Your puzzle answer was <code>24</code>.</p>\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>There is a floor.</p></article>\
+            </main></html>";
+        assert_eq!(
+            render(page, ColorSupport::None, 2022, 14)?,
+            "--- Day 14: Regolith Reservoir ---\n\nThe sand is pouring in:\n\n    498,4 -> 498,6\n\n\
+            --- Part Two ---\n\nThere is a floor.\n"
+        );
+        assert!(matches!(render("<html></html>", ColorSupport::None, 2022, 14), Err(Error::Missing(2022, 14))));
+        assert_eq!(url(2022, 14), "https://adventofcode.com/2022/day/14");
+
+        let root = Utf8PathBuf::from_path_buf(std::env::temp_dir().join("aoc22_description_cache")).unwrap();
+        let cache = InputCache::new(&root, true);
+        std::fs::create_dir_all(root.join("2022")).map_err(input::Error::Io)?;
+        std::fs::write(root.join("2022/day14.html"), page).map_err(input::Error::Io)?;
+        assert_eq!(get(&cache, 2022, 14, false)?, page);
+        assert!(matches!(get(&cache, 2022, 14, true), Err(Error::Input(input::Error::NotCached(2022, 14, _)))));
+        assert!(!root.join("2022/day14.html").exists());
+        Ok(())
+    }
+}
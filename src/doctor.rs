@@ -0,0 +1,188 @@
+use camino::Utf8Path;
+use std::{fmt, fs, io::IsTerminal};
+use crate::{
+    input::{self, InputCache, SESSION_VARIABLE},
+    render::style::ColorSupport,
+    solution::{self, PUZZLES},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Ok,
+    // Something works in a degraded way, or is not set up but only needed by some commands.
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Status::Ok => "ok",
+            Status::Warning => "warn",
+            Status::Error => "FAIL",
+        })
+    }
+}
+
+// The outcome of a single check, with what to do about it when it is not ok.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn ok(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Check { name: name.into(), status: Status::Ok, detail: detail.into(), hint: None }
+    }
+
+    fn warning(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check { name: name.into(), status: Status::Warning, detail: detail.into(), hint: Some(hint.into()) }
+    }
+
+    fn error(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check { name: name.into(), status: Status::Error, detail: detail.into(), hint: Some(hint.into()) }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:>4}] {}: {}", self.status, self.name, self.detail)?;
+        if let Some(hint) = &self.hint {
+            write!(f, "\n       {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+// Every compiled-in day's example must be there and read as that day's input.
+pub fn embedded_inputs() -> Vec<Check> {
+    PUZZLES
+        .iter()
+        .map(|(year, day, solution)| {
+            let name = format!("{} day {} example", year, day);
+            let input = solution.example().input;
+            if input.trim().is_empty() {
+                return Check::error(name, "empty", format!("restore src/year{}/data/day{}_example.txt", year, day));
+            }
+            match solution.parse(input) {
+                Ok(()) => Check::ok(name, format!("{} lines", input.lines().count())),
+                Err(e) => Check::error(name, e.to_string(), "the parser or the bundled example changed, run the day's tests"),
+            }
+        })
+        .collect()
+}
+
+// The site answers 400 for the input of a session it does not know.
+fn session_check(session: Result<String, input::Error>, validate: Option<impl FnOnce(&str) -> Result<String, input::Error>>) -> Check {
+    const NAME: &str = "session";
+    let session = match session {
+        Ok(session) => session,
+        Err(e) => return Check::warning(NAME, "not configured, only needed to download inputs and submit", e.to_string()),
+    };
+    let Some(validate) = validate else {
+        return Check::ok(NAME, format!("{} is set, not checked offline", SESSION_VARIABLE));
+    };
+
+    match validate(&session) {
+        Ok(_) => Check::ok(NAME, "accepted by adventofcode.com"),
+        Err(input::Error::Http(_, e)) if matches!(*e, ureq::Error::StatusCode(400 | 401 | 403)) => Check::error(
+            NAME,
+            format!("rejected by adventofcode.com: {}", e),
+            format!("the token expired or is mistyped, copy the session cookie again into {}", SESSION_VARIABLE),
+        ),
+        Err(e) => Check::warning(NAME, format!("could not be checked: {}", e), "check the network, or run again with --offline"),
+    }
+}
+
+pub fn session(offline: bool) -> Check {
+    let validate = (!offline).then_some(|session: &str| input::download(solution::DEFAULT_YEAR, 1, session));
+    session_check(input::session(), validate)
+}
+
+// Writes then removes a file, which is all the cache ever needs.
+fn writable_check(root: &Utf8Path) -> Check {
+    const NAME: &str = "cache";
+    let probe = root.join(".doctor");
+    let written = fs::create_dir_all(root).and_then(|()| fs::write(&probe, b"")).and_then(|()| fs::remove_file(&probe));
+    match written {
+        Ok(()) => Check::ok(NAME, format!("{} is writable", root)),
+        Err(e) => Check::error(NAME, format!("{} is not writable: {}", root, e), "fix its permissions, or point XDG_CACHE_HOME somewhere writable"),
+    }
+}
+
+pub fn cache() -> Check {
+    match InputCache::default_location(false) {
+        Ok(cache) => writable_check(cache.root()),
+        Err(e) => Check::error("cache", e.to_string(), "set XDG_CACHE_HOME to a writable directory"),
+    }
+}
+
+fn terminal_check(colors: ColorSupport, terminal: bool) -> Check {
+    const NAME: &str = "terminal";
+    match colors {
+        ColorSupport::TrueColor => Check::ok(NAME, "true color"),
+        ColorSupport::Ansi256 => Check::warning(
+            NAME,
+            "256 colors, visualizations use the closest ones",
+            "set COLORTERM=truecolor if the terminal supports it",
+        ),
+        ColorSupport::None if !terminal => Check::warning(
+            NAME,
+            "not a terminal, visualizations fall back to shades",
+            "run without redirecting the output to see colors",
+        ),
+        ColorSupport::None => Check::warning(
+            NAME,
+            "no colors, visualizations fall back to shades",
+            "unset NO_COLOR and use a TERM other than dumb",
+        ),
+    }
+}
+
+pub fn terminal() -> Check {
+    terminal_check(ColorSupport::detect(), std::io::stdout().is_terminal())
+}
+
+pub fn run(offline: bool) -> Vec<Check> {
+    let mut checks = embedded_inputs();
+    checks.extend([session(offline), cache(), terminal()]);
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use crate::doctor::*;
+
+    #[test]
+    fn environment_checks() {
+        assert!(embedded_inputs().iter().all(|check| check.status == Status::Ok));
+        assert_eq!(embedded_inputs().len(), PUZZLES.len());
+
+        let accepted = session_check(Ok("token".to_string()), Some(|_: &str| Ok(String::new())));
+        assert_eq!(accepted.status, Status::Ok);
+        let rejected = session_check(
+            Ok("token".to_string()),
+            Some(|_: &str| Err(input::Error::Http("url".to_string(), Box::new(ureq::Error::StatusCode(400))))),
+        );
+        assert_eq!(rejected.status, Status::Error);
+        assert!(rejected.to_string().starts_with("[FAIL] session: rejected by adventofcode.com"));
+        let unreachable = session_check(Ok("token".to_string()), Some(|_: &str| Err(input::Error::NoCacheDirectory)));
+        assert_eq!(unreachable.status, Status::Warning);
+        let missing = session_check(Err(input::Error::MissingSession), None::<fn(&str) -> Result<String, input::Error>>);
+        assert_eq!(missing.status, Status::Warning);
+
+        let root = Utf8PathBuf::from_path_buf(std::env::temp_dir().join("aoc22_doctor")).unwrap();
+        assert_eq!(writable_check(&root).status, Status::Ok);
+        assert!(!root.join(".doctor").exists());
+        let file = root.join("file");
+        fs::write(&file, "").unwrap();
+        assert_eq!(writable_check(&file.join("cache")).status, Status::Error);
+
+        assert_eq!(terminal_check(ColorSupport::TrueColor, true).to_string(), "[  ok] terminal: true color");
+        assert_eq!(terminal_check(ColorSupport::None, false).status, Status::Warning);
+    }
+}
@@ -0,0 +1,66 @@
+use thiserror::Error;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+// What every day's own error becomes at the public boundary. The day error is kept as the source,
+// so callers can still downcast to it for the details.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid input: {0}")]
+    Parse(BoxError),
+    #[error("Inconsistent input: {0}")]
+    Validation(BoxError),
+    #[error("No solution: {0}")]
+    Solve(BoxError),
+    #[error("Unable to write output: {0}")]
+    Output(BoxError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error("Unknown part {0}")]
+    UnknownPart(u8),
+    #[error("Part {0} has no variant {1}")]
+    UnknownVariant(u8, String),
+    #[error("Timed out after {0:?}")]
+    TimedOut(std::time::Duration),
+    #[error("Day {0} part {1} panicked")]
+    Panicked(u8, u8),
+}
+
+impl Error {
+    pub fn parse(e: impl Into<BoxError>) -> Self {
+        Error::Parse(e.into())
+    }
+
+    pub fn validation(e: impl Into<BoxError>) -> Self {
+        Error::Validation(e.into())
+    }
+
+    pub fn solve(e: impl Into<BoxError>) -> Self {
+        Error::Solve(e.into())
+    }
+
+    pub fn output(e: impl Into<BoxError>) -> Self {
+        Error::Output(e.into())
+    }
+}
+
+// Goes through days 5 and 12, so it only builds along with both.
+#[cfg(all(test, feature = "day5", feature = "day12"))]
+mod tests {
+    use crate::{Error, solution};
+
+    #[test]
+    fn uniform_failures() {
+        let day5 = solution::puzzle(2022, 5).unwrap();
+        assert!(matches!(day5.part1("[A]\n 1 \n\nmove x from 1 to 2\n"), Err(Error::Parse(_))));
+        assert!(matches!(day5.part1("[A]\n 1 \n\nmove 1 from 1 to 2\n"), Err(Error::Validation(_))));
+        assert!(matches!(day5.part(3, ""), Err(Error::UnknownPart(3))));
+
+        match solution::puzzle(2022, 12).unwrap().part1("SbE") {
+            Err(Error::Solve(source)) => assert!(source.downcast_ref::<crate::year2022::day12::Error>().is_some()),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
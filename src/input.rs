@@ -0,0 +1,279 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use std::{
+    convert::Infallible,
+    fmt,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+use crate::client::Client;
+
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("No session token, set {SESSION_VARIABLE} to the value of your adventofcode.com session cookie")]
+    MissingSession,
+    #[error("Unable to download {0}: {1}")]
+    Http(String, Box<ureq::Error>),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("No cache directory, set XDG_CACHE_HOME or HOME")]
+    NoCacheDirectory,
+    #[error("Day {1} of {0} is not cached at {2} and downloads are disabled")]
+    NotCached(u16, u8, Utf8PathBuf),
+    #[error("Unable to read {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("Input is not valid UTF-8: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+}
+
+// Anything a puzzle input can come from, decoded to the text every solution parses.
+pub trait IntoInput {
+    fn into_input(self) -> Result<String, Error>;
+}
+
+impl IntoInput for &str {
+    fn into_input(self) -> Result<String, Error> {
+        Ok(self.to_string())
+    }
+}
+
+impl IntoInput for String {
+    fn into_input(self) -> Result<String, Error> {
+        Ok(self)
+    }
+}
+
+impl IntoInput for &String {
+    fn into_input(self) -> Result<String, Error> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoInput for &[u8] {
+    fn into_input(self) -> Result<String, Error> {
+        Ok(std::str::from_utf8(self)?.to_string())
+    }
+}
+
+impl IntoInput for &Path {
+    fn into_input(self) -> Result<String, Error> {
+        let bytes = fs::read(self).map_err(|e| Error::Read(self.to_path_buf(), e))?;
+        bytes.as_slice().into_input()
+    }
+}
+
+impl IntoInput for &Utf8Path {
+    fn into_input(self) -> Result<String, Error> {
+        self.as_std_path().into_input()
+    }
+}
+
+// Readers are wrapped, as a blanket impl over Read would overlap with the byte slice one.
+pub struct Reader<R>(pub R);
+
+impl<R: Read> IntoInput for Reader<R> {
+    fn into_input(mut self) -> Result<String, Error> {
+        let mut bytes = Vec::new();
+        self.0.read_to_end(&mut bytes)?;
+        bytes.as_slice().into_input()
+    }
+}
+
+// Where a run reads its input from, as given on the command line: "-" is stdin.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    Stdin,
+    Path(Utf8PathBuf),
+    Text(String),
+}
+
+impl FromStr for Input {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => Input::Stdin,
+            path => Input::Path(path.into()),
+        })
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Stdin => f.write_str("stdin"),
+            Input::Path(path) => write!(f, "{}", path),
+            Input::Text(_) => f.write_str("text"),
+        }
+    }
+}
+
+impl IntoInput for Input {
+    fn into_input(self) -> Result<String, Error> {
+        match self {
+            Input::Stdin => Reader(std::io::stdin().lock()).into_input(),
+            Input::Path(path) => path.as_path().into_input(),
+            Input::Text(text) => Ok(text),
+        }
+    }
+}
+
+pub fn url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}/input", year, day)
+}
+
+fn session_from(value: Option<String>) -> Result<String, Error> {
+    value
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or(Error::MissingSession)
+}
+
+pub fn session() -> Result<String, Error> {
+    session_from(std::env::var(SESSION_VARIABLE).ok())
+}
+
+// The returned text is exactly what the site serves, ready for any run_challenge*.
+pub fn download(year: u16, day: u8, session: &str) -> Result<String, Error> {
+    let url = url(year, day);
+    Client::new(session).get(&url).map_err(|e| Error::Http(url, Box::new(e)))
+}
+
+// Inputs never change once published, so a cached file stays valid until explicitly invalidated.
+// Puzzle descriptions and leaderboards are kept alongside them.
+#[derive(Clone, Debug)]
+pub struct InputCache {
+    root: Utf8PathBuf,
+    offline: bool,
+}
+
+impl InputCache {
+    pub fn new(root: impl Into<Utf8PathBuf>, offline: bool) -> Self {
+        InputCache { root: root.into(), offline }
+    }
+
+    // ~/.cache/aoc22, or its XDG_CACHE_HOME equivalent.
+    pub fn default_location(offline: bool) -> Result<Self, Error> {
+        let cache = std::env::var("XDG_CACHE_HOME")
+            .ok()
+            .filter(|path| !path.is_empty())
+            .map(Utf8PathBuf::from)
+            .or_else(|| std::env::var("HOME").ok().map(|home| Utf8Path::new(&home).join(".cache")))
+            .ok_or(Error::NoCacheDirectory)?;
+        Ok(InputCache::new(cache.join("aoc22"), offline))
+    }
+
+    pub fn root(&self) -> &Utf8Path {
+        &self.root
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> Utf8PathBuf {
+        self.root.join(year.to_string()).join(format!("day{}.txt", day))
+    }
+
+    pub fn description_path(&self, year: u16, day: u8) -> Utf8PathBuf {
+        self.root.join(year.to_string()).join(format!("day{}.html", day))
+    }
+
+    pub fn leaderboard_path(&self, year: u16, id: u64) -> Utf8PathBuf {
+        self.root.join(year.to_string()).join(format!("leaderboard{}.json", id))
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Result<String, Error> {
+        self.get_or_fetch(year, day, || download(year, day, &session()?))
+    }
+
+    fn get_or_fetch(&self, year: u16, day: u8, fetch: impl FnOnce() -> Result<String, Error>) -> Result<String, Error> {
+        self.get_or_fetch_at(self.path(year, day), year, day, fetch)
+    }
+
+    pub(crate) fn get_or_fetch_at(
+        &self,
+        path: Utf8PathBuf,
+        year: u16,
+        day: u8,
+        fetch: impl FnOnce() -> Result<String, Error>,
+    ) -> Result<String, Error> {
+        if path.exists() {
+            return Ok(fs::read_to_string(&path)?);
+        }
+        if self.offline {
+            return Err(Error::NotCached(year, day, path));
+        }
+
+        let content = fetch()?;
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, &content)?;
+        Ok(content)
+    }
+
+    // Whether there was a cached input to drop.
+    pub fn invalidate(&self, year: u16, day: u8) -> Result<bool, Error> {
+        Self::remove(&self.path(year, day))
+    }
+
+    pub(crate) fn remove(path: &Utf8Path) -> Result<bool, Error> {
+        match fs::remove_file(path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::*;
+
+    #[test]
+    fn session_token() {
+        assert_eq!(url(2022, 7), "https://adventofcode.com/2022/day/7/input");
+        assert_eq!(url(2023, 1), "https://adventofcode.com/2023/day/1/input");
+        assert_eq!(session_from(Some(" 53616c74\n".to_string())).unwrap(), "53616c74");
+        assert!(matches!(session_from(Some("  ".to_string())), Err(Error::MissingSession)));
+        assert!(matches!(session_from(None), Err(Error::MissingSession)));
+    }
+
+    #[test]
+    fn cached_inputs() -> Result<(), Error> {
+        let root = crate::utils::unique_temp_path("aoc22_input_cache");
+        let cache = InputCache::new(&root, false);
+
+        assert_eq!(cache.get_or_fetch(2022, 3, || Ok("abc\n".to_string()))?, "abc\n");
+        assert_eq!(fs::read_to_string(root.join("2022/day3.txt"))?, "abc\n");
+        assert_eq!(cache.get_or_fetch(2022, 3, || Err(Error::MissingSession))?, "abc\n");
+        assert_eq!(cache.path(2023, 3), root.join("2023/day3.txt"));
+
+        assert!(cache.invalidate(2022, 3)?);
+        assert!(!cache.invalidate(2022, 3)?);
+        let offline = InputCache::new(&root, true);
+        assert!(matches!(offline.get_or_fetch(2022, 3, || Ok(String::new())), Err(Error::NotCached(2022, 3, _))));
+
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn input_sources() -> Result<(), Error> {
+        let path = crate::utils::unique_temp_path("aoc22_input_sources").with_extension("txt");
+        fs::write(&path, "1\n2\n")?;
+
+        assert_eq!("1\n2\n".into_input()?, "1\n2\n");
+        assert_eq!(b"1\n2\n".as_slice().into_input()?, "1\n2\n");
+        assert_eq!(Reader(fs::File::open(&path)?).into_input()?, "1\n2\n");
+        assert_eq!(path.as_std_path().into_input()?, "1\n2\n");
+        assert_eq!(Input::Path(path.clone()).into_input()?, "1\n2\n");
+        assert_eq!(Input::Text("3\n".to_string()).into_input()?, "3\n");
+        assert_eq!("-".parse::<Input>(), Ok(Input::Stdin));
+        assert_eq!("day1.txt".parse::<Input>(), Ok(Input::Path("day1.txt".into())));
+
+        fs::remove_file(&path)?;
+        assert!(matches!(path.as_std_path().into_input(), Err(Error::Read(_, _))));
+        assert!(matches!([0xff_u8].as_slice().into_input(), Err(Error::Utf8(_))));
+        Ok(())
+    }
+}
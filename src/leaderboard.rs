@@ -0,0 +1,223 @@
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs,
+    time::{Duration, SystemTime},
+};
+use thiserror::Error;
+use crate::{
+    client::Client,
+    input::{self, InputCache},
+};
+
+pub const LEADERBOARD_VARIABLE: &str = "AOC_LEADERBOARD";
+
+// The site asks not to fetch a private leaderboard more often than this.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] input::Error),
+    #[error("Invalid leaderboard: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("No leaderboard, give its id or set {LEADERBOARD_VARIABLE} to it")]
+    MissingId,
+}
+
+// As served by the site, keeping only what the table needs. Days and parts are numbers in strings.
+#[derive(Debug, Deserialize)]
+struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    local_score: u64,
+    stars: u32,
+    completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+// When a member got the stars of a day, as Unix timestamps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Completion {
+    pub first: i64,
+    pub second: Option<i64>,
+}
+
+impl Completion {
+    // How long the second part took once the first was solved.
+    pub fn delta(&self) -> Option<Duration> {
+        self.second.map(|second| Duration::from_secs((second - self.first).max(0) as u64))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub local_score: u64,
+    pub stars: u32,
+    pub days: BTreeMap<u8, Completion>,
+}
+
+pub fn url(year: u16, id: u64) -> String {
+    format!("https://adventofcode.com/{}/leaderboard/private/view/{}.json", year, id)
+}
+
+fn id_from(value: Option<String>) -> Result<u64, Error> {
+    value.and_then(|id| id.trim().parse().ok()).ok_or(Error::MissingId)
+}
+
+pub fn default_id() -> Result<u64, Error> {
+    id_from(std::env::var(LEADERBOARD_VARIABLE).ok())
+}
+
+pub fn download(year: u16, id: u64, session: &str) -> Result<String, input::Error> {
+    let url = url(year, id);
+    Client::new(session).get(&url).map_err(|e| input::Error::Http(url, Box::new(e)))
+}
+
+fn stale(modified: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(modified).map_or(true, |age| age >= REFRESH_INTERVAL)
+}
+
+// Cached alongside the inputs, but fetched again once older than the refresh interval.
+pub fn get(cache: &InputCache, year: u16, id: u64) -> Result<String, Error> {
+    let path = cache.leaderboard_path(year, id);
+    let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+    if modified.is_ok_and(|modified| !stale(modified, SystemTime::now())) {
+        return Ok(fs::read_to_string(&path).map_err(input::Error::Io)?);
+    }
+
+    let json = download(year, id, &input::session()?)?;
+    fs::create_dir_all(path.parent().unwrap()).map_err(input::Error::Io)?;
+    fs::write(&path, &json).map_err(input::Error::Io)?;
+    Ok(json)
+}
+
+// Best local score first, ties by name.
+pub fn standings(json: &str) -> Result<Vec<Standing>, Error> {
+    let leaderboard: Leaderboard = serde_json::from_str(json)?;
+    let mut standings = leaderboard
+        .members
+        .into_values()
+        .map(|member| Standing {
+            name: member.name.unwrap_or_else(|| format!("(anonymous user #{})", member.id)),
+            local_score: member.local_score,
+            stars: member.stars,
+            days: member
+                .completion_day_level
+                .into_iter()
+                .filter_map(|(day, parts)| {
+                    let first = parts.get(&1)?.get_star_ts;
+                    Some((day, Completion { first, second: parts.get(&2).map(|star| star.get_star_ts) }))
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    standings.sort_by(|left, right| right.local_score.cmp(&left.local_score).then(left.name.cmp(&right.name)));
+    Ok(standings)
+}
+
+fn cell(completion: Option<&Completion>) -> String {
+    match completion.map(Completion::delta) {
+        None => ".".to_string(),
+        Some(None) => "*".to_string(),
+        Some(Some(delta)) => {
+            let seconds = delta.as_secs();
+            match seconds {
+                0..60 => format!("{}s", seconds),
+                60..3600 => format!("{}m{:02}s", seconds / 60, seconds % 60),
+                _ => format!("{}h{:02}m", seconds / 3600, seconds % 3600 / 60),
+            }
+        }
+    }
+}
+
+// One row per member, one column per day anyone completed: how long the second part took after
+// the first, `*` when only the first is done, `.` when neither.
+pub fn table(standings: &[Standing]) -> String {
+    let mut days = standings.iter().flat_map(|standing| standing.days.keys().copied()).collect::<Vec<_>>();
+    days.sort_unstable();
+    days.dedup();
+
+    let rows = standings
+        .iter()
+        .map(|standing| days.iter().map(|day| cell(standing.days.get(day))).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let widths = days
+        .iter()
+        .enumerate()
+        .map(|(column, day)| rows.iter().map(|row| row[column].len()).chain([day.to_string().len()]).max().unwrap_or_default())
+        .collect::<Vec<_>>();
+    let name_width = standings.iter().map(|standing| standing.name.chars().count()).max().unwrap_or_default().max(6);
+
+    let mut table = format!("{:>4}  {:<name_width$}  {:>5}  {:>5}", "Rank", "Member", "Score", "Stars");
+    for (day, width) in days.iter().zip(&widths) {
+        write!(table, "  {:>width$}", day).unwrap();
+    }
+    table.push('\n');
+    for (rank, (standing, row)) in standings.iter().zip(rows).enumerate() {
+        write!(table, "{:>4}  {:<name_width$}  {:>5}  {:>5}", rank + 1, standing.name, standing.local_score, standing.stars).unwrap();
+        for (cell, width) in row.iter().zip(&widths) {
+            write!(table, "  {:>width$}", cell).unwrap();
+        }
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::leaderboard::*;
+
+    const LEADERBOARD: &str = r#"{"event":"2022","owner_id":1,"members":{
+        "1":{"id":1,"name":"Alice","stars":3,"local_score":12,"global_score":0,"last_star_ts":1670000400,
+            "completion_day_level":{"1":{"1":{"get_star_ts":1669870000,"star_index":0},"2":{"get_star_ts":1669870042,"star_index":1}},
+                "2":{"1":{"get_star_ts":1669960000,"star_index":2}}}},
+        "2":{"id":2,"name":null,"stars":4,"local_score":12,"global_score":0,"last_star_ts":1670100000,
+            "completion_day_level":{"1":{"1":{"get_star_ts":1669880000,"star_index":3},"2":{"get_star_ts":1669880750,"star_index":4}},
+                "10":{"1":{"get_star_ts":1670660000,"star_index":5},"2":{"get_star_ts":1670667300,"star_index":6}}}},
+        "3":{"id":3,"name":"Bob","stars":0,"local_score":0,"global_score":0,"last_star_ts":0,"completion_day_level":{}}}}"#;
+
+    #[test]
+    fn member_deltas() -> Result<(), Error> {
+        let board = standings(LEADERBOARD)?;
+        assert_eq!(
+            board.iter().map(|standing| standing.name.as_str()).collect::<Vec<_>>(),
+            vec!["(anonymous user #2)", "Alice", "Bob"]
+        );
+        assert_eq!(board[1].days[&1].delta(), Some(Duration::from_secs(42)));
+        assert_eq!(board[1].days[&2], Completion { first: 1669960000, second: None });
+
+        assert_eq!(
+            table(&board),
+            "Rank  Member               Score  Stars       1  2     10\n   \
+                1  (anonymous user #2)     12      4  12m30s  .  2h01m\n   \
+                2  Alice                   12      3     42s  *      .\n   \
+                3  Bob                      0      0       .  .      .\n"
+        );
+        assert!(matches!(standings("{}"), Err(Error::Json(_))));
+        assert_eq!(url(2022, 123), "https://adventofcode.com/2022/leaderboard/private/view/123.json");
+        Ok(())
+    }
+
+    #[test]
+    fn leaderboard_config() {
+        let now = SystemTime::now();
+        assert!(!stale(now - Duration::from_secs(60), now));
+        assert!(stale(now - REFRESH_INTERVAL, now));
+        assert!(stale(now + Duration::from_secs(60), now - Duration::from_secs(60)));
+        assert_eq!(id_from(Some(" 123\n".to_string())).ok(), Some(123));
+        assert!(matches!(id_from(Some("abc".to_string())), Err(Error::MissingId)));
+        assert!(matches!(id_from(None), Err(Error::MissingId)));
+    }
+}
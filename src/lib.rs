@@ -0,0 +1,35 @@
+// Invariant checks compiled in only with the `paranoid` feature. Builds without any of the days
+// using it leave it unused.
+#[allow(unused_macros)]
+macro_rules! paranoid_assert {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "paranoid")]
+        assert!($($arg)*);
+    };
+}
+
+pub mod answers;
+pub mod client;
+pub mod description;
+pub mod doctor;
+mod error;
+pub mod input;
+#[cfg(feature = "serde")]
+pub mod leaderboard;
+#[cfg(feature = "memory")]
+mod memory;
+#[cfg(feature = "profile")]
+pub mod profile;
+pub mod render;
+pub mod runner;
+pub mod scaffold;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod solution;
+pub mod submit;
+pub mod utils;
+pub mod visualize;
+pub mod watch;
+pub mod year2022;
+
+pub use error::Error;
@@ -1,17 +1,1277 @@
-#![allow(dead_code)]
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-
-fn main() {
+use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+use std::{process::ExitCode, time::Duration};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use aoc22::{
+    answers::{self, KnownAnswers},
+    description,
+    doctor,
+    input::{self, Input, InputCache, IntoInput},
+    render::style::ColorSupport,
+    runner::{self, DayInput, PartRun},
+    scaffold,
+    solution::{self, Solution},
+    submit::{self, Verdict},
+    visualize::{self, Frame},
+    watch,
+};
+#[cfg(feature = "day5")]
+use aoc22::year2022::day05;
+#[cfg(feature = "day9")]
+use aoc22::year2022::day09;
+#[cfg(feature = "day10")]
+use aoc22::year2022::day10;
 
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+enum Format {
+    Text,
+    /// One JSON record per part, needs the serde feature.
+    Json,
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc22", about = "Advent of Code 2022 solutions", subcommand_required = true)]
+struct Cli {
+    /// Diagnostics on stderr: once for debug output, twice for every intermediate step.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[command(about = "Solve a day, every day, or check them all against the accepted answers")]
+    Run(RunArgs),
+    #[command(about = "Download a day's input into the cache and print where it is")]
+    Fetch {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        /// Drop the cached input and download it again.
+        #[arg(long)]
+        refresh: bool,
+    },
+    #[command(about = "Show a day's puzzle description, downloaded once and cached")]
+    Desc {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        /// Download it again, e.g. once the second part is unlocked.
+        #[arg(long)]
+        refresh: bool,
+    },
+    #[command(about = "Check the bundled examples, the session token, the cache and the terminal")]
+    Doctor {
+        /// Skip checking the session token against adventofcode.com.
+        #[arg(long)]
+        offline: bool,
+    },
+    #[command(about = "Solve a day part again each time its input or its module is saved")]
+    Watch {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// The cached input when not given.
+        #[arg(long)]
+        input: Option<Utf8PathBuf>,
+        /// Where the day's module is looked for, it is not watched when missing.
+        #[arg(long, default_value = "src")]
+        source: Utf8PathBuf,
+    },
+    #[command(about = "Submit an answer to adventofcode.com, the computed one unless given")]
+    Submit {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        #[arg(long)]
+        answer: Option<String>,
+        #[arg(long, conflicts_with = "answer")]
+        input: Option<Input>,
+    },
+    #[command(about = "Time repeated solves of a day's parts")]
+    Bench {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        /// Both parts when not given.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    #[command(about = "Create the module and data files of a new day")]
+    NewDay {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        #[arg(long, default_value = "src")]
+        source: Utf8PathBuf,
+    },
+    #[command(about = "Draw how a day's part is solved, in the terminal or to a picture, for days that support it")]
+    Visualize {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2), default_value_t = 1)]
+        part: u8,
+        /// A file, or - for stdin. The cached input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        /// Saves a GIF of every frame when it ends in .gif, a PNG of the last one otherwise. Needs the image feature.
+        #[arg(long)]
+        output: Option<Utf8PathBuf>,
+        /// Side of the square drawn for each cell in saved pictures, in pixels.
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..), default_value_t = 4)]
+        scale: u16,
+        /// How long each frame is shown, in milliseconds.
+        #[arg(long, default_value_t = 40)]
+        delay: u64,
+    },
+    #[command(about = "Check an input without solving it, day 10 programs against the screen as well")]
+    Validate {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        /// A file, or - for stdin. The cached input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    #[cfg(feature = "day1")]
+    #[command(about = "Compare two snapshots of a day's input, day 1 inventories only")]
+    Diff {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// A file, or - for stdin.
+        #[arg(long)]
+        before: Input,
+        /// A file, or - for stdin.
+        #[arg(long)]
+        after: Input,
+    },
+    #[cfg(feature = "day5")]
+    #[command(about = "Day 5 crane scenarios with checkpoints, written by hand")]
+    Scenario {
+        #[command(subcommand)]
+        command: ScenarioCommand,
+    },
+    #[cfg(feature = "day5")]
+    #[command(about = "Day 5 worked examples: the shortest list of moves bringing the wanted crates on top")]
+    Plan {
+        /// The crates wanted on top, one letter per stack.
+        #[arg(long)]
+        target: String,
+        /// The stacks are taken from its drawing, its moves are ignored. The cached day 5 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        /// 9000 moves crates one at a time, 9001 several at once.
+        #[arg(long, default_value = "9000")]
+        crane: day05::CraneModel,
+        /// The search gives up past this many moves, the number of states to try growing fast with it.
+        #[arg(long, default_value_t = 4)]
+        max_moves: usize,
+    },
+    #[cfg(feature = "day7")]
+    #[command(about = "Puzzle engines as standalone tools")]
+    Tools {
+        #[command(subcommand)]
+        command: ToolsCommand,
+    },
+    #[cfg(all(feature = "day8", feature = "image"))]
+    #[command(about = "Save a day 8 forest as a grayscale PNG heightmap, one pixel per tree")]
+    Heightmap {
+        /// A file, or - for stdin. The cached day 8 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        #[arg(long)]
+        output: Utf8PathBuf,
+        /// Tints the trees visible from outside the forest green.
+        #[arg(long)]
+        visibility: bool,
+        /// Marks the tree with the best scenic score in red.
+        #[arg(long)]
+        best_spot: bool,
+    },
+    #[cfg(feature = "day9")]
+    #[command(about = "Generate puzzle inputs, day 9 moves drawing L-system curves")]
+    Gen {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(long, value_enum)]
+        pattern: GenPattern,
+        /// Rewrites of the L-system, each one doubles (dragon) or quadruples (hilbert) the moves.
+        #[arg(long, default_value_t = 8)]
+        depth: u32,
+    },
+    #[cfg(feature = "day9")]
+    #[command(about = "Trace a day 9 rope and report how its knots moved together")]
+    Rope {
+        /// A file, or - for stdin. The cached day 9 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..), default_value_t = 10)]
+        knots: u8,
+        /// Prints the spread of the rope before the first move and after every step, one per line, for plotting.
+        #[arg(long)]
+        timeline: bool,
+        /// Walls as min_x,min_y,max_x,max_y: the head stops at them, dropping the rest of the command.
+        #[arg(long, allow_hyphen_values = true)]
+        bounds: Option<day09::Bounds>,
+    },
+    #[cfg(feature = "day11")]
+    #[command(about = "Tweak a day's puzzle interactively and see how the answer changes, day 11 only")]
+    Explore {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Starts from the rounds and relief of this part.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2), default_value_t = 1)]
+        part: u8,
+        /// A file. The cached input when not given, as commands are read from stdin.
+        #[arg(long)]
+        input: Option<Utf8PathBuf>,
+    },
+    #[cfg(feature = "day11")]
+    #[command(about = "Print a day's puzzle as a Graphviz DOT graph, day 11 monkeys and their throws only")]
+    Dot {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// A file, or - for stdin. The cached input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        /// Weights the throws by the items thrown over the rounds of this part.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        flow: Option<u8>,
+    },
+    #[cfg(feature = "day10")]
+    #[command(about = "Draw the screen of a day 10 program with a theme, in the terminal or to a picture")]
+    Crt {
+        /// A file, or - for stdin. The cached day 10 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        /// ascii, blocks, or braille packing 2x4 pixels in each character.
+        #[arg(long, default_value = "ascii")]
+        theme: day10::Theme,
+        /// Saves a PNG drawn the way the theme draws characters. Needs the image feature.
+        #[arg(long)]
+        output: Option<Utf8PathBuf>,
+        /// Side of the square drawn for each pixel in saved pictures, in pixels.
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..), default_value_t = 8)]
+        scale: u16,
+    },
+    #[cfg(all(feature = "day4", feature = "serde"))]
+    #[command(about = "Print the overlap analysis of every day 4 pair as JSON Lines")]
+    Pairs {
+        /// A file, or - for stdin. The cached day 4 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    #[cfg(all(feature = "day10", feature = "serde"))]
+    #[command(about = "Print every fetch, execution, drawn pixel and sample of a day 10 program as JSON")]
+    Events {
+        /// A file, or - for stdin. The cached day 10 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    #[cfg(feature = "serde")]
+    #[command(about = "Show a private leaderboard, with how long each member took between the two parts of every day")]
+    Leaderboard {
+        #[arg(long, value_parser = clap::value_parser!(u16).range(2015..), default_value_t = solution::DEFAULT_YEAR)]
+        year: u16,
+        /// The number at the end of the leaderboard's URL, AOC_LEADERBOARD when not given.
+        #[arg(long)]
+        id: Option<u64>,
+    },
+    #[cfg(feature = "serve")]
+    #[command(about = "Serve the solvers over HTTP: POST an input to /solve/{day}/{part} for a JSON answer")]
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8022")]
+        address: String,
+        #[arg(long, value_parser = clap::value_parser!(u16).range(2015..), default_value_t = solution::DEFAULT_YEAR)]
+        year: u16,
+    },
+    #[cfg(feature = "play")]
+    #[command(about = "Play day 2 rounds over TCP, scored under both readings of the second column")]
+    Play {
+        #[arg(long, default_value = "127.0.0.1:2022")]
+        address: String,
+        /// Appends every valid round, in the puzzle format.
+        #[arg(long)]
+        log: Option<Utf8PathBuf>,
+    },
+    #[command(about = "Print shell completions for bash, zsh, fish, elvish or powershell")]
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[cfg(feature = "day5")]
+#[derive(Debug, Subcommand)]
+enum ScenarioCommand {
+    #[command(about = "Run a scenario and check every checkpoint, failing if any top crates differ")]
+    Run {
+        file: Input,
+    },
+}
+
+#[cfg(feature = "day7")]
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+enum DuSort {
+    Name,
+    /// Largest first.
+    Size,
+}
+
+#[cfg(feature = "day9")]
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+enum GenPattern {
+    Dragon,
+    Hilbert,
+}
+
+#[cfg(feature = "day7")]
+#[derive(Debug, Subcommand)]
+enum ToolsCommand {
+    #[command(about = "Directory sizes of a day 7 terminal session, like du")]
+    Du {
+        /// A file, or - for stdin. The cached day 7 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        /// Directories below this depth are counted in their parent but not listed, / being at 0.
+        #[arg(long)]
+        depth: Option<usize>,
+        #[arg(long, value_enum, default_value_t = DuSort::Name)]
+        sort: DuSort,
+    },
+    #[command(about = "The filesystem of a day 7 terminal session as an indented tree")]
+    Tree {
+        /// A file, or - for stdin. The cached day 7 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+        /// Annotates every entry with its size and its share of its parent directory and of /.
+        #[arg(long)]
+        shares: bool,
+    },
+    #[command(about = "Shortest day 7 terminal session building the same filesystem, for small test cases")]
+    Minimize {
+        /// A file, or - for stdin. The cached day 7 input when not given.
+        #[arg(long)]
+        input: Option<Input>,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+struct Puzzle {
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2015..), default_value_t = solution::DEFAULT_YEAR)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Debug, clap::Args)]
+struct RunArgs {
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2015..), default_value_t = solution::DEFAULT_YEAR)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25), required_unless_present_any = ["all", "verify"])]
+    day: Option<u8>,
+    /// Both parts when running the example.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2), required_unless_present_any = ["all", "example", "verify"])]
+    part: Option<u8>,
+    /// Runs both parts of every day and prints a summary table.
+    #[arg(long, conflicts_with_all = ["day", "part", "input", "details"])]
+    all: bool,
+    /// Runs every day like --all, and fails if an answer differs from the accepted one.
+    #[arg(long, conflicts_with_all = ["day", "part", "input", "details", "all", "format"])]
+    verify: bool,
+    #[arg(long, default_value = "answers.toml")]
+    answers: Utf8PathBuf,
+    /// Where --all and --verify look for dayN_challenge.txt before falling back to the input cache,
+    /// src/yearYYYY/data by default.
+    #[arg(long)]
+    data: Option<Utf8PathBuf>,
+    /// A file, or - for stdin. Downloaded with the AOC_SESSION token when not given, and cached
+    /// under ~/.cache/aoc22.
+    #[arg(long)]
+    input: Option<Input>,
+    /// Runs on the bundled example, and checks the computed answers against the ones the puzzle gives.
+    #[arg(long, conflicts_with_all = ["all", "input"])]
+    example: bool,
+    /// Only use inputs that are already cached.
+    #[arg(long, conflicts_with = "input")]
+    offline: bool,
+    /// Drop the cached input and download it again.
+    #[arg(long, conflicts_with_all = ["input", "offline"])]
+    refresh: bool,
+    /// Solves the part another way, like flat for day 8 part 1, for days that have more than one.
+    #[arg(long, conflicts_with_all = ["all", "verify", "example", "format", "timeout"])]
+    variant: Option<String>,
+    /// Print how the answer was reached before the answer itself, for days that support it.
+    #[arg(long)]
+    details: bool,
+    #[arg(long, value_enum, default_value_t = Format::Text, conflicts_with = "details")]
+    format: Format,
+    /// Only the answer on stdout, failures are reported through the exit code alone.
+    #[arg(short, long, conflicts_with_all = ["verbose", "details", "all"])]
+    quiet: bool,
+    /// Writes a flamegraph of the solve, flamegraph.svg unless given. Needs the profile feature.
+    #[arg(long, num_args = 0..=1, default_missing_value = "flamegraph.svg", conflicts_with_all = ["all", "verify", "example", "format"])]
+    profile: Option<Utf8PathBuf>,
+    /// Gives up on a part still running after this many seconds, reporting it as timed out.
+    #[arg(long, value_parser = parse_timeout, conflicts_with = "profile")]
+    timeout: Option<Duration>,
+}
+
+fn parse_timeout(seconds: &str) -> Result<Duration, String> {
+    let seconds = seconds.parse::<f64>().map_err(|e| e.to_string())?;
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| "expected a positive number of seconds".to_string())
+}
+
+impl Cli {
+    fn quiet(&self) -> bool {
+        matches!(&self.command, Command::Run(args) if args.quiet)
+    }
+}
+
+// Stable exit codes for scripts: 0 on success, 1 for anything not listed here.
+const EXIT_INVALID_INPUT: u8 = 2;
+const EXIT_NO_SOLUTION: u8 = 3;
+const EXIT_TIMED_OUT: u8 = 4;
+// An answer differs from the accepted one, or from the one the puzzle gives for the example.
+const EXIT_WRONG_ANSWER: u8 = 5;
+
+fn exit_code_of(error: &aoc22::Error) -> u8 {
+    match error {
+        aoc22::Error::Parse(_) | aoc22::Error::Validation(_) => EXIT_INVALID_INPUT,
+        aoc22::Error::Solve(_) => EXIT_NO_SOLUTION,
+        aoc22::Error::TimedOut(_) => EXIT_TIMED_OUT,
+        _ => 1,
+    }
+}
+
+fn exit_code(error: &anyhow::Error) -> u8 {
+    error.downcast_ref::<aoc22::Error>().map_or(1, exit_code_of)
+}
+
+// The code of the first failed part, if any.
+fn runs_exit_code(runs: &[PartRun]) -> ExitCode {
+    runs.iter()
+        .find_map(|run| run.answer.as_ref().err())
+        .map_or(ExitCode::SUCCESS, |e| ExitCode::from(exit_code_of(e)))
+}
+
+fn lookup(year: u16, day: u8) -> anyhow::Result<&'static dyn Solution> {
+    solution::puzzle(year, day).with_context(|| format!("Day {} of {} is not available", day, year))
+}
+
+fn solve(year: u16, day: u8, part: u8, content: &str) -> anyhow::Result<String> {
+    Ok(lookup(year, day)?.solve(part, content)?.to_string())
+}
+
+fn solve_variant(year: u16, day: u8, part: u8, name: &str, content: &str) -> anyhow::Result<String> {
+    let solution = lookup(year, day)?;
+    let names = solution.variants(part);
+    anyhow::ensure!(
+        names.contains(&name),
+        "Day {} part {} has no variant {}, {}",
+        day, part, name,
+        if names.is_empty() { "nor any other".to_string() } else { format!("only {}", names.join(", ")) }
+    );
+    Ok(solution.variant(part, name, content)?.to_string())
+}
+
+fn cached_input(year: u16, day: u8, offline: bool, refresh: bool) -> Result<DayInput, aoc22::Error> {
+    let cache = InputCache::default_location(offline)?;
+    if refresh {
+        cache.invalidate(year, day)?;
+    }
+    Ok(DayInput { content: cache.get(year, day)?, source: cache.path(year, day).to_string() })
+}
+
+fn given_or_cached(input: Option<Input>, year: u16, day: u8) -> Result<DayInput, aoc22::Error> {
+    match input {
+        Some(input) => Ok(DayInput { source: input.to_string(), content: input.into_input()? }),
+        None => cached_input(year, day, false, false),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn json_lines(runs: &[PartRun]) -> anyhow::Result<String> {
+    Ok(runner::json_lines(runs))
+}
+
+#[cfg(not(feature = "serde"))]
+fn json_lines(_runs: &[PartRun]) -> anyhow::Result<String> {
+    anyhow::bail!("JSON output needs the serde feature")
+}
+
+#[cfg(feature = "profile")]
+fn profiled(path: &Utf8Path, quiet: bool, solve: impl FnMut() -> anyhow::Result<String>) -> anyhow::Result<String> {
+    let (answer, runs) = aoc22::profile::flamegraph(path, solve)?;
+    if !quiet {
+        eprintln!("Flamegraph of {} runs written to {}", runs, path);
+    }
+    answer
+}
+
+#[cfg(not(feature = "profile"))]
+fn profiled(_path: &Utf8Path, _quiet: bool, _solve: impl FnMut() -> anyhow::Result<String>) -> anyhow::Result<String> {
+    anyhow::bail!("Profiling needs the profile feature")
+}
+
+fn run_every_day(args: &RunArgs) -> Vec<PartRun> {
+    let data = args.data.clone().unwrap_or_else(|| format!("src/year{}/data", args.year).into());
+    runner::run_all(args.year, args.timeout, |day| {
+        let path = data.join(format!("day{}_challenge.txt", day));
+        if path.exists() {
+            Ok(DayInput { content: path.as_path().into_input()?, source: path.to_string() })
+        } else {
+            cached_input(args.year, day, args.offline, args.refresh)
+        }
+    })
+}
+
+fn run_all(args: &RunArgs) -> anyhow::Result<ExitCode> {
+    let runs = run_every_day(args);
+    match args.format {
+        Format::Text => print!("{}", runner::table(&runs)),
+        Format::Json => print!("{}", json_lines(&runs)?),
+    }
+    Ok(runs_exit_code(&runs))
+}
+
+fn run_verify(args: &RunArgs) -> anyhow::Result<ExitCode> {
+    let known = KnownAnswers::load(&args.answers).with_context(|| format!("Unable to load {}", args.answers))?;
+    let changes = answers::verify(&known, &run_every_day(args));
+
+    for change in &changes {
+        println!("{}", change);
+    }
+    if !args.quiet {
+        eprintln!("{} accepted answers checked, {} changed", known.len(), changes.len());
+    }
+    Ok(if changes.is_empty() { ExitCode::SUCCESS } else { ExitCode::from(EXIT_WRONG_ANSWER) })
+}
+
+// Multi-line answers, like rendered letters, get the expected one below rather than beside, and
+// the marker on a line of its own.
+fn with_expected(answer: &str, expected: Option<&str>) -> String {
+    let marker = |expected| if answers::same_answer(expected, answer) { "pass" } else { "FAIL" };
+    match expected {
+        None => answer.to_string(),
+        Some(expected) if !answer.contains('\n') && !expected.contains('\n') => {
+            format!("{} (expected {}) {}", answer, expected, marker(expected))
+        }
+        Some(expected) => format!("{}\nexpected:\n{}\n{}", answer, expected, marker(expected)),
+    }
+}
+
+fn run_example(args: &RunArgs, day: u8) -> anyhow::Result<ExitCode> {
+    let solution = lookup(args.year, day)?;
+    let example = solution.example();
+    let input = DayInput { source: "example".to_string(), content: example.input.to_string() };
+    let parts = args.part.map_or(vec![1, 2], |part| vec![part]);
+
+    let runs = parts.iter().map(|part| runner::run_part_within(args.year, day, *part, solution, &input, args.timeout)).collect::<Vec<_>>();
+    let wrong = runs.iter().any(|run| match (&run.answer, example.answer(run.part)) {
+        (Ok(answer), Some(expected)) => !answers::same_answer(expected, &answer.to_string()),
+        _ => false,
+    });
+    let code = if wrong { ExitCode::from(EXIT_WRONG_ANSWER) } else { ExitCode::SUCCESS };
+    if args.format == Format::Json {
+        print!("{}", json_lines(&runs)?);
+        return Ok(if runs.iter().any(|run| run.answer.is_err()) { runs_exit_code(&runs) } else { code });
+    }
+
+    for run in runs {
+        if args.details {
+            for line in solution.details(run.part, &input.content)? {
+                println!("{}", line);
+            }
+        }
+
+        let answer = run.answer?.to_string();
+        match (args.quiet, args.part) {
+            (true, _) => println!("{}", answer),
+            (false, Some(_)) => println!("{}", with_expected(&answer, example.answer(run.part))),
+            (false, None) => println!("Part {}: {}", run.part, with_expected(&answer, example.answer(run.part))),
+        }
+    }
+    Ok(code)
+}
+
+fn run_fetch(puzzle: &Puzzle, refresh: bool) -> anyhow::Result<ExitCode> {
+    let input = cached_input(puzzle.year, puzzle.day, false, refresh)?;
+    println!("{}", input.source);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_desc(puzzle: &Puzzle, refresh: bool) -> anyhow::Result<ExitCode> {
+    let cache = InputCache::default_location(false)?;
+    let page = description::get(&cache, puzzle.year, puzzle.day, refresh)?;
+    print!("{}", description::to_terminal(&page, puzzle.year, puzzle.day)?);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_doctor(offline: bool) -> anyhow::Result<ExitCode> {
+    let checks = doctor::run(offline);
+    for check in &checks {
+        println!("{}", check);
+    }
+    let failed = checks.iter().any(|check| check.status == doctor::Status::Error);
+    Ok(if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+// Once the module changed, runs go through cargo to pick up the new code, building with default features.
+fn run_watch(puzzle: &Puzzle, part: u8, input: Option<Utf8PathBuf>, source: &Utf8Path) -> anyhow::Result<ExitCode> {
+    let Puzzle { year, day } = *puzzle;
+    let input = match input {
+        Some(input) => input,
+        None => cached_input(year, day, false, false)?.source.into(),
+    };
+    let module = Some(scaffold::module_path(source, year, day)).filter(|module| module.exists());
+    match &module {
+        Some(module) => eprintln!("Watching {} and {}", input, module),
+        None => eprintln!("Watching {}", input),
+    }
+
+    let rebuild = |input: &Utf8Path| {
+        let output = std::process::Command::new("cargo")
+            .args(["run", "--quiet", "--", "run"])
+            .args(["--year", &year.to_string(), "--day", &day.to_string(), "--part", &part.to_string()])
+            .args(["--input", input.as_str()])
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+    };
+    watch::watch(&input, module.as_deref(), |content| solve(year, day, part, content), rebuild)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_submit(puzzle: &Puzzle, part: u8, answer: Option<String>, input: Option<Input>) -> anyhow::Result<ExitCode> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let input = given_or_cached(input, puzzle.year, puzzle.day)?;
+            solve(puzzle.year, puzzle.day, part, &input.content)?
+        }
+    };
+
+    let verdict = submit::submit(puzzle.year, puzzle.day, part, &answer, &input::session()?)?;
+    println!("{}: {}", answer, verdict);
+    Ok(match verdict {
+        Verdict::Correct => ExitCode::SUCCESS,
+        Verdict::Incorrect(_) => ExitCode::from(EXIT_WRONG_ANSWER),
+        _ => ExitCode::FAILURE,
+    })
+}
+
+fn run_bench(puzzle: &Puzzle, part: Option<u8>, runs: usize, input: Option<Input>) -> anyhow::Result<ExitCode> {
+    let solution = lookup(puzzle.year, puzzle.day)?;
+    let input = given_or_cached(input, puzzle.year, puzzle.day)?;
+
+    for part in part.map_or(vec![1, 2], |part| vec![part]) {
+        let (_, timings) = runner::bench_part(solution, part, &input.content, runs)?;
+        println!("Part {}: {}", part, timings);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_new_day(puzzle: &Puzzle, source: &Utf8Path) -> anyhow::Result<ExitCode> {
+    let files = scaffold::create(source, puzzle.year, puzzle.day)
+        .with_context(|| format!("Unable to create day {} of {}", puzzle.day, puzzle.year))?;
+    for file in files {
+        println!("Created {}", file);
+    }
+    println!("Still to register:");
+    for line in scaffold::registration(puzzle.year, puzzle.day) {
+        println!("  {}", line);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "image")]
+fn save_frames(frames: &[Frame], path: &Utf8Path, delay: Duration) -> anyhow::Result<()> {
+    match path.extension() {
+        Some("gif") => visualize::save_gif(frames, path, delay)?,
+        _ => frames.last().context("Nothing to draw")?.save_png(path)?,
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "image"))]
+fn save_frames(_frames: &[Frame], _path: &Utf8Path, _delay: Duration) -> anyhow::Result<()> {
+    anyhow::bail!("Saving pictures needs the image feature")
+}
+
+fn run_visualize(puzzle: &Puzzle, part: u8, input: Option<Input>, output: Option<&Utf8Path>, scale: u16, delay: u64) -> anyhow::Result<ExitCode> {
+    let Puzzle { year, day } = *puzzle;
+    let input = given_or_cached(input, year, day)?;
+    let frames = lookup(year, day)?.visualize(part, &input.content)?;
+    anyhow::ensure!(!frames.is_empty(), "Day {} of {} cannot be drawn", day, year);
+
+    let delay = Duration::from_millis(delay);
+    match output {
+        Some(path) => {
+            let frames = frames.iter().map(|frame| frame.scaled(scale.into())).collect::<Vec<_>>();
+            save_frames(&frames, path, delay)?;
+            eprintln!("Saved {}", path);
+        }
+        None => visualize::play(&frames, ColorSupport::detect(), delay, std::io::stdout().lock())?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// Days without checks of their own only have their input parsed.
+fn run_validate(puzzle: &Puzzle, input: Option<Input>) -> anyhow::Result<ExitCode> {
+    let Puzzle { year, day } = *puzzle;
+    let input = given_or_cached(input, year, day)?;
+    match (year, day) {
+        #[cfg(feature = "day10")]
+        (solution::DEFAULT_YEAR, 10) => {
+            use aoc22::year2022::day10::Program;
+
+            let program = Program::read(&input.content).map_err(aoc22::Error::from)?;
+            let excursion = program.max_register_excursion();
+            println!("{} cycles", program.cycle_count());
+            println!("X between {} and {}", excursion.start(), excursion.end());
+            println!("{}", program.validate());
+        }
+        #[cfg(feature = "day5")]
+        (solution::DEFAULT_YEAR, 5) => {
+            use day05::{CraneModel, ErrorPolicy, SimulationOptions};
+
+            let (stacks, actions) = day05::read_input(&input.content).map_err(aoc22::Error::from)?;
+            let mut valid = true;
+            for (name, model) in [("9000", CraneModel::CrateMover9000), ("9001", CraneModel::CrateMover9001)] {
+                let options = SimulationOptions { dry_run: true, on_error: ErrorPolicy::CollectErrors, ..SimulationOptions::new(model) };
+                let report = day05::simulate_with_options(&stacks, &actions, options);
+                println!("CrateMover {}: {} moves, {} failing", name, report.steps, report.errors.len());
+                for e in &report.errors {
+                    println!("  {}", e);
+                }
+                valid &= report.is_valid();
+            }
+            if !valid {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        _ => {
+            lookup(year, day)?.parse(&input.content)?;
+            println!("{} parses", input.source);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day1")]
+fn run_diff(day: u8, before: Input, after: Input) -> anyhow::Result<ExitCode> {
+    use aoc22::year2022::day01;
+
+    anyhow::ensure!(day == 1, "No diff for day {}", day);
+    let (before, after) = (before.into_input()?, after.into_input()?);
+    print!("{}", day01::diff_inventories(&before, &after).map_err(aoc22::Error::from)?);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day5")]
+fn run_scenario(command: ScenarioCommand) -> anyhow::Result<ExitCode> {
+    match command {
+        ScenarioCommand::Run { file } => {
+            let report = day05::run_scenario(file).map_err(aoc22::Error::from)?;
+            for checkpoint in &report.checkpoints {
+                println!("{}", checkpoint);
+            }
+            println!("final: {}", report.stacks.top());
+            Ok(if report.passed() { ExitCode::SUCCESS } else { ExitCode::from(EXIT_WRONG_ANSWER) })
+        }
+    }
+}
+
+#[cfg(feature = "day5")]
+fn run_plan(target: &str, input: Option<Input>, crane: day05::CraneModel, max_moves: usize) -> anyhow::Result<ExitCode> {
+    let input = given_or_cached(input, solution::DEFAULT_YEAR, 5)?;
+    print!("{}", day05::worked_example(input.content.as_str(), target, crane, max_moves).map_err(aoc22::Error::from)?);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day7")]
+fn run_tools(command: ToolsCommand) -> anyhow::Result<ExitCode> {
+    use aoc22::year2022::day07::{self, DuOrder, Filesystem, FsDiff, PrettyNode, SharesNode};
+
+    match command {
+        ToolsCommand::Du { input, depth, sort } => {
+            let input = given_or_cached(input, solution::DEFAULT_YEAR, 7)?;
+            let filesystem = Filesystem::read(&input.content).map_err(aoc22::Error::from)?;
+            let order = match sort {
+                DuSort::Name => DuOrder::Name,
+                DuSort::Size => DuOrder::Size,
+            };
+            print!("{}", filesystem.du(depth, order));
+            Ok(ExitCode::SUCCESS)
+        }
+        ToolsCommand::Tree { input, shares } => {
+            let input = given_or_cached(input, solution::DEFAULT_YEAR, 7)?;
+            let root = day07::read_input(&input.content).map_err(aoc22::Error::from)?;
+            if shares {
+                print!("{:?}", SharesNode(&root));
+            } else {
+                print!("{:?}", PrettyNode(&root));
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        ToolsCommand::Minimize { input } => {
+            let input = given_or_cached(input, solution::DEFAULT_YEAR, 7)?;
+            let minimized = day07::minimize_session(&input.content).map_err(aoc22::Error::from)?;
+            let diff = FsDiff::between(
+                &day07::read_input(&input.content).map_err(aoc22::Error::from)?,
+                &day07::read_input(&minimized).map_err(aoc22::Error::from)?,
+            );
+            anyhow::ensure!(diff.is_empty(), "The minimized session builds another filesystem:\n{}", diff);
+            print!("{}", minimized);
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+#[cfg(all(feature = "day8", feature = "image"))]
+fn run_heightmap(input: Option<Input>, output: &Utf8Path, visibility: bool, best_spot: bool) -> anyhow::Result<ExitCode> {
+    use aoc22::year2022::day08::{self, HeightmapOverlay};
+
+    let input = given_or_cached(input, solution::DEFAULT_YEAR, 8)?;
+    let trees = day08::read_input(&input.content).map_err(aoc22::Error::from)?;
+    trees.to_png(output, HeightmapOverlay { visibility, best_spot }).map_err(aoc22::Error::from)?;
+    eprintln!("Saved {}", output);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day9")]
+fn run_gen(day: u8, pattern: GenPattern, depth: u32) -> anyhow::Result<ExitCode> {
+    use std::io::Write;
+    use aoc22::year2022::day09::Pattern;
+
+    anyhow::ensure!(day == 9, "No generator for day {}", day);
+    let pattern = match pattern {
+        GenPattern::Dragon => Pattern::Dragon,
+        GenPattern::Hilbert => Pattern::Hilbert,
+    };
+    let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+    for command in day09::generate(pattern, depth).map_err(aoc22::Error::from)? {
+        writeln!(out, "{}", command)?;
+    }
+    out.flush()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day9")]
+fn run_rope(input: Option<Input>, knots: u8, timeline: bool, bounds: Option<day09::Bounds>) -> anyhow::Result<ExitCode> {
+    use std::io::Write;
+
+    let input = given_or_cached(input, solution::DEFAULT_YEAR, 9)?;
+    let commands = day09::read_input(&input.content).map_err(aoc22::Error::from)?;
+    let rope = day09::trace_bounded(&commands, knots.into(), bounds);
+    let stats = rope.stats();
+    if timeline {
+        let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+        for spread in &stats.spread_timeline {
+            writeln!(out, "{}", spread)?;
+        }
+        out.flush()?;
+    } else {
+        println!("tail visited: {}", rope.tail_visited().len());
+        println!("knots sharing a cell: {} times", stats.collisions);
+        println!("largest spread: {}", stats.max_spread);
+        println!("trace digest: {:016x}", rope.digest());
+        for clamp in &rope.clamps {
+            println!(
+                "line {}: the head stopped at {},{}, {} moves dropped",
+                clamp.command + 1, clamp.head.x, clamp.head.y, clamp.dropped,
+            );
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day11")]
+fn run_explore(day: u8, part: u8, input: Option<Utf8PathBuf>) -> anyhow::Result<ExitCode> {
+    use std::io::IsTerminal;
+    use aoc22::year2022::day11::{self, Explorer};
+
+    anyhow::ensure!(day == 11, "Day {} cannot be explored", day);
+    let input = given_or_cached(input.map(Input::Path), solution::DEFAULT_YEAR, day)?;
+    let monkeys = day11::read_input(&input.content).map_err(aoc22::Error::from)?;
+    let (worry_level_divider, rounds) = if part == 1 { (3, 20) } else { (1, 10_000) };
+
+    let stdin = std::io::stdin();
+    let prompt = stdin.is_terminal();
+    if prompt {
+        eprintln!("Exploring {}, type help for the commands", input.source);
+    }
+    Explorer::new(worry_level_divider, monkeys, rounds).interact(stdin.lock(), std::io::stdout(), prompt)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day11")]
+fn run_dot(day: u8, input: Option<Input>, flow: Option<u8>) -> anyhow::Result<ExitCode> {
+    use aoc22::year2022::day11;
+
+    anyhow::ensure!(day == 11, "No graph for day {}", day);
+    let input = given_or_cached(input, solution::DEFAULT_YEAR, day)?;
+    let monkeys = day11::read_input(&input.content).map_err(aoc22::Error::from)?;
+    let dot = match flow {
+        Some(part) => {
+            let (worry_level_divider, rounds) = if part == 1 { (3, 20) } else { (1, 10_000) };
+            let (monkeys, flow) = day11::simulate(rounds, worry_level_divider, monkeys);
+            day11::to_dot(&monkeys, Some(&flow))
+        }
+        None => day11::to_dot(&monkeys, None),
+    };
+    print!("{}", dot);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "day10")]
+fn run_crt(input: Option<Input>, theme: day10::Theme, output: Option<&Utf8Path>, scale: u16) -> anyhow::Result<ExitCode> {
+    use aoc22::visualize::Visualize;
+
+    let input = given_or_cached(input, solution::DEFAULT_YEAR, 10)?;
+    let machine = day10::run_challenge2(input.content.as_str()).map_err(aoc22::Error::from)?;
+    match output {
+        Some(path) => {
+            let frames = machine.themed(theme).frames().iter().map(|frame| frame.scaled(scale.into())).collect::<Vec<_>>();
+            save_frames(&frames, path, Duration::ZERO)?;
+            eprintln!("Saved {}", path);
+        }
+        None => print!("{}", machine.themed(theme)),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(feature = "day4", feature = "serde"))]
+fn run_pairs(input: Option<Input>) -> anyhow::Result<ExitCode> {
+    use std::io::Write;
+    use aoc22::year2022::day04;
+
+    let input = given_or_cached(input, solution::DEFAULT_YEAR, 4)?;
+    let pairs = day04::read_input(&input.content).map_err(aoc22::Error::from)?;
+    let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+    day04::to_jsonl(&pairs, &mut out).map_err(aoc22::Error::from)?;
+    out.flush()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(feature = "day10", feature = "serde"))]
+fn run_events(input: Option<Input>) -> anyhow::Result<ExitCode> {
+    let input = given_or_cached(input, solution::DEFAULT_YEAR, 10)?;
+    let events = day10::log_events(input.content.as_str()).map_err(aoc22::Error::from)?;
+    println!("{}", day10::events_to_json(&events).map_err(aoc22::Error::from)?);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "serde")]
+fn run_leaderboard(year: u16, id: Option<u64>) -> anyhow::Result<ExitCode> {
+    use aoc22::leaderboard;
+
+    let id = id.map_or_else(leaderboard::default_id, Ok)?;
+    let json = leaderboard::get(&InputCache::default_location(false)?, year, id)?;
+    print!("{}", leaderboard::table(&leaderboard::standings(&json)?));
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "serve")]
+fn run_serve(address: &str, year: u16) -> anyhow::Result<ExitCode> {
+    aoc22::serve::serve(address, year, |address| eprintln!("Listening on http://{}", address))
+        .with_context(|| format!("Unable to serve on {}", address))?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "play")]
+fn run_play(address: &str, log: Option<&Utf8Path>) -> anyhow::Result<ExitCode> {
+    let log: Box<dyn std::io::Write> = match log {
+        Some(path) => Box::new(std::fs::OpenOptions::new().create(true).append(true).open(path)?),
+        None => Box::new(std::io::sink()),
+    };
+    let listener = std::net::TcpListener::bind(address).with_context(|| format!("Unable to listen on {}", address))?;
+    eprintln!("Listening on {}, one round per line like A Y, quit to leave", listener.local_addr()?);
+
+    aoc22::year2022::day02::serve(listener, log)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_command(cli: Cli) -> anyhow::Result<ExitCode> {
+    match cli.command {
+        Command::Run(args) => run(&args),
+        Command::Fetch { puzzle, refresh } => run_fetch(&puzzle, refresh),
+        Command::Desc { puzzle, refresh } => run_desc(&puzzle, refresh),
+        Command::Doctor { offline } => run_doctor(offline),
+        Command::Watch { puzzle, part, input, source } => run_watch(&puzzle, part, input, &source),
+        Command::Submit { puzzle, part, answer, input } => run_submit(&puzzle, part, answer, input),
+        Command::Bench { puzzle, part, runs, input } => run_bench(&puzzle, part, runs, input),
+        Command::NewDay { puzzle, source } => run_new_day(&puzzle, &source),
+        Command::Visualize { puzzle, part, input, output, scale, delay } => {
+            run_visualize(&puzzle, part, input, output.as_deref(), scale, delay)
+        }
+        Command::Validate { puzzle, input } => run_validate(&puzzle, input),
+        #[cfg(feature = "day1")]
+        Command::Diff { day, before, after } => run_diff(day, before, after),
+        #[cfg(feature = "day5")]
+        Command::Scenario { command } => run_scenario(command),
+        #[cfg(feature = "day5")]
+        Command::Plan { target, input, crane, max_moves } => run_plan(&target, input, crane, max_moves),
+        #[cfg(feature = "day7")]
+        Command::Tools { command } => run_tools(command),
+        #[cfg(all(feature = "day8", feature = "image"))]
+        Command::Heightmap { input, output, visibility, best_spot } => run_heightmap(input, &output, visibility, best_spot),
+        #[cfg(feature = "day9")]
+        Command::Gen { day, pattern, depth } => run_gen(day, pattern, depth),
+        #[cfg(feature = "day9")]
+        Command::Rope { input, knots, timeline, bounds } => run_rope(input, knots, timeline, bounds),
+        #[cfg(feature = "day11")]
+        Command::Explore { day, part, input } => run_explore(day, part, input),
+        #[cfg(feature = "day11")]
+        Command::Dot { day, input, flow } => run_dot(day, input, flow),
+        #[cfg(feature = "day10")]
+        Command::Crt { input, theme, output, scale } => run_crt(input, theme, output.as_deref(), scale),
+        #[cfg(all(feature = "day4", feature = "serde"))]
+        Command::Pairs { input } => run_pairs(input),
+        #[cfg(all(feature = "day10", feature = "serde"))]
+        Command::Events { input } => run_events(input),
+        #[cfg(feature = "serde")]
+        Command::Leaderboard { year, id } => run_leaderboard(year, id),
+        #[cfg(feature = "serve")]
+        Command::Serve { address, year } => run_serve(&address, year),
+        #[cfg(feature = "play")]
+        Command::Play { address, log } => run_play(&address, log.as_deref()),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "aoc22", &mut std::io::stdout());
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn run(args: &RunArgs) -> anyhow::Result<ExitCode> {
+    if args.verify {
+        return run_verify(args);
+    }
+    let Some(day) = args.day else {
+        return run_all(args);
+    };
+    if args.example {
+        return run_example(args, day);
+    }
+    let part = args.part.context("--part is required")?;
+
+    let input = match args.input.clone() {
+        Some(input) => DayInput { source: input.to_string(), content: input.into_input()? },
+        None => cached_input(args.year, day, args.offline, args.refresh)?,
+    };
+    if args.format == Format::Json {
+        let runs = [runner::run_part_within(args.year, day, part, lookup(args.year, day)?, &input, args.timeout)];
+        print!("{}", json_lines(&runs)?);
+        return Ok(runs_exit_code(&runs));
+    }
+
+    if args.details {
+        for line in lookup(args.year, day)?.details(part, &input.content)? {
+            println!("{}", line);
+        }
+    }
+    let solve_part = || match &args.variant {
+        Some(name) => solve_variant(args.year, day, part, name, &input.content),
+        None => solve(args.year, day, part, &input.content),
+    };
+    let answer = match &args.profile {
+        Some(path) => profiled(path, args.quiet, solve_part)?,
+        None if args.timeout.is_some() => {
+            runner::run_part_within(args.year, day, part, lookup(args.year, day)?, &input, args.timeout).answer?.to_string()
+        }
+        None => solve_part()?,
+    };
+    println!("{}", answer);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    // Usage errors exit with 1 rather than clap's 2, which is taken by invalid inputs.
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            return ExitCode::from(if e.use_stderr() { 1 } else { 0 });
+        }
+    };
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(match (cli.quiet(), cli.verbose) {
+            (true, _) => tracing::Level::ERROR,
+            (_, 0) => tracing::Level::WARN,
+            (_, 1) => tracing::Level::DEBUG,
+            _ => tracing::Level::TRACE,
+        })
+        .init();
+
+    let quiet = cli.quiet();
+    match run_command(cli) {
+        Ok(code) => code,
+        Err(e) => {
+            if !quiet {
+                eprintln!("Error: {:?}", e);
+            }
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn solve_dispatches_days() -> anyhow::Result<()> {
+        #[cfg(feature = "day1")]
+        assert_eq!(solve(2022, 1, 2, include_str!("year2022/data/day1_example.txt"))?, "45000");
+        #[cfg(feature = "day12")]
+        assert_eq!(solve(2022, 12, 1, include_str!("year2022/data/day12_example.txt"))?, "31");
+        assert!(solve(2022, 1, 3, include_str!("year2022/data/day1_example.txt")).is_err());
+        assert!(solve(2022, 25, 1, "").is_err());
+        assert!(solve(2023, 1, 1, "").is_err());
+        #[cfg(feature = "day8")]
+        assert_eq!(solve_variant(2022, 8, 1, "flat", include_str!("year2022/data/day8_example.txt"))?, "21");
+        #[cfg(feature = "day8")]
+        assert!(solve_variant(2022, 8, 2, "flat", include_str!("year2022/data/day8_example.txt")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn subcommands() {
+        Cli::command().debug_assert();
+        let parse = |args: &[&str]| Cli::try_parse_from(["aoc22"].iter().chain(args));
+
+        assert!(matches!(parse(&["run", "--day", "7", "--part", "1", "-v"]), Ok(Cli { verbose: 1, command: Command::Run(RunArgs { day: Some(7), .. }) })));
+        assert!(parse(&["-q", "run", "--all"]).is_err());
+        assert!(matches!(parse(&["run", "--day", "7", "--part", "1", "--profile"]), Ok(Cli { command: Command::Run(RunArgs { profile: Some(path), .. }), .. }) if path == "flamegraph.svg"));
+        assert!(parse(&["run", "--all", "--profile", "all.svg"]).is_err());
+        assert!(parse(&["run", "--day", "7", "--part", "1", "--quiet", "-v"]).is_err());
+        assert!(parse(&["run", "--day", "7", "--part", "1", "--quiet"]).is_ok());
+        assert!(matches!(parse(&["run", "--all", "--timeout", "2.5"]), Ok(Cli { command: Command::Run(RunArgs { timeout: Some(timeout), .. }), .. }) if timeout == Duration::from_millis(2500)));
+        assert!(parse(&["run", "--all", "--timeout", "0"]).is_err());
+        assert!(matches!(parse(&["run", "--day", "8", "--part", "1", "--variant", "flat"]), Ok(Cli { command: Command::Run(RunArgs { variant: Some(name), .. }), .. }) if name == "flat"));
+        assert!(parse(&["run", "--all", "--variant", "flat"]).is_err());
+        assert!(matches!(parse(&["fetch", "--day", "3"]), Ok(Cli { command: Command::Fetch { puzzle: Puzzle { year: 2022, day: 3 }, .. }, .. })));
+        assert!(parse(&["submit", "--day", "3", "--part", "2", "--answer", "70", "--input", "-"]).is_err());
+        assert!(matches!(parse(&["desc", "--day", "14", "--refresh"]), Ok(Cli { command: Command::Desc { refresh: true, .. }, .. })));
+        assert!(matches!(parse(&["doctor", "--offline"]), Ok(Cli { command: Command::Doctor { offline: true }, .. })));
+        assert!(matches!(parse(&["watch", "--day", "13", "--part", "1"]), Ok(Cli { command: Command::Watch { input: None, .. }, .. })));
+        assert!(parse(&["watch", "--day", "13"]).is_err());
+        #[cfg(all(feature = "day8", feature = "image"))]
+        assert!(matches!(
+            parse(&["heightmap", "--output", "forest.png", "--best-spot"]),
+            Ok(Cli { command: Command::Heightmap { input: None, visibility: false, best_spot: true, .. }, .. })
+        ));
+        #[cfg(all(feature = "day8", feature = "image"))]
+        assert!(parse(&["heightmap", "--visibility"]).is_err());
+        #[cfg(feature = "day9")]
+        assert!(matches!(parse(&["gen", "--day", "9", "--pattern", "dragon", "--depth", "12"]), Ok(Cli { command: Command::Gen { depth: 12, .. }, .. })));
+        #[cfg(feature = "day9")]
+        assert!(parse(&["gen", "--day", "9", "--pattern", "koch"]).is_err());
+        #[cfg(feature = "day9")]
+        assert!(matches!(parse(&["rope", "--knots", "2", "--timeline"]), Ok(Cli { command: Command::Rope { knots: 2, timeline: true, .. }, .. })));
+        #[cfg(feature = "day9")]
+        assert!(parse(&["rope", "--knots", "0"]).is_err());
+        #[cfg(feature = "day9")]
+        assert!(matches!(parse(&["rope", "--bounds", "-5,-5,5,5"]), Ok(Cli { command: Command::Rope { bounds: Some(_), .. }, .. })));
+        #[cfg(feature = "day9")]
+        assert!(parse(&["rope", "--bounds", "1,1,5,5"]).is_err());
+        #[cfg(feature = "day11")]
+        assert!(matches!(parse(&["explore", "--day", "11"]), Ok(Cli { command: Command::Explore { day: 11, part: 1, input: None }, .. })));
+        #[cfg(feature = "day11")]
+        assert!(matches!(parse(&["dot", "--day", "11", "--flow", "2"]), Ok(Cli { command: Command::Dot { day: 11, flow: Some(2), .. }, .. })));
+        #[cfg(feature = "day10")]
+        assert!(matches!(parse(&["crt", "--theme", "braille"]), Ok(Cli { command: Command::Crt { theme: day10::Theme::Braille, output: None, scale: 8, .. }, .. })));
+        #[cfg(feature = "day10")]
+        assert!(parse(&["crt", "--theme", "dark"]).is_err());
+        #[cfg(all(feature = "day4", feature = "serde"))]
+        assert!(matches!(parse(&["pairs", "--input", "-"]), Ok(Cli { command: Command::Pairs { input: Some(Input::Stdin) }, .. })));
+        #[cfg(all(feature = "day10", feature = "serde"))]
+        assert!(matches!(parse(&["events", "--input", "-"]), Ok(Cli { command: Command::Events { input: Some(Input::Stdin) }, .. })));
+        #[cfg(feature = "day5")]
+        assert!(matches!(parse(&["plan", "--target", "ZMN", "--crane", "9001"]), Ok(Cli { command: Command::Plan { max_moves: 4, .. }, .. })));
+        #[cfg(feature = "day5")]
+        assert!(parse(&["plan", "--target", "ZMN", "--crane", "9002"]).is_err());
+        #[cfg(feature = "day1")]
+        assert!(matches!(parse(&["diff", "--day", "1", "--before", "a.txt", "--after", "-"]), Ok(Cli { command: Command::Diff { day: 1, after: Input::Stdin, .. }, .. })));
+        #[cfg(feature = "day1")]
+        assert!(parse(&["diff", "--day", "1", "--before", "a.txt"]).is_err());
+        #[cfg(feature = "day5")]
+        assert!(matches!(parse(&["scenario", "run", "cranes.txt"]), Ok(Cli { command: Command::Scenario { .. }, .. })));
+        #[cfg(feature = "day7")]
+        assert!(matches!(
+            parse(&["tools", "du", "--input", "session.txt", "--depth", "2", "--sort", "size"]),
+            Ok(Cli { command: Command::Tools { command: ToolsCommand::Du { depth: Some(2), sort: DuSort::Size, .. } }, .. })
+        ));
+        #[cfg(feature = "day7")]
+        assert!(matches!(
+            parse(&["tools", "tree", "--shares"]),
+            Ok(Cli { command: Command::Tools { command: ToolsCommand::Tree { input: None, shares: true } }, .. })
+        ));
+        #[cfg(feature = "day7")]
+        assert!(matches!(
+            parse(&["tools", "minimize", "--input", "-"]),
+            Ok(Cli { command: Command::Tools { command: ToolsCommand::Minimize { input: Some(Input::Stdin) } }, .. })
+        ));
+        assert!(matches!(parse(&["bench", "--day", "12"]), Ok(Cli { command: Command::Bench { part: None, runs: 10, .. }, .. })));
+        assert!(matches!(
+            parse(&["visualize", "--day", "12", "--output", "search.gif"]),
+            Ok(Cli { command: Command::Visualize { part: 1, scale: 4, output: Some(path), .. }, .. }) if path == "search.gif"
+        ));
+        assert!(parse(&["visualize", "--day", "12", "--scale", "0"]).is_err());
+        assert!(matches!(parse(&["validate", "--day", "10", "--input", "-"]), Ok(Cli { command: Command::Validate { puzzle: Puzzle { day: 10, .. }, input: Some(_) }, .. })));
+        assert!(matches!(parse(&["new-day", "--year", "2023", "--day", "1"]), Ok(Cli { command: Command::NewDay { .. }, .. })));
+        assert!(parse(&["--day", "1", "--part", "1"]).is_err());
+
+        // Descriptions come from the doc comments of the arguments.
+        let command = Cli::command();
+        let run = command.find_subcommand("run").unwrap();
+        assert!(["details", "quiet", "timeout"].iter().all(|id| run.get_arguments().any(|arg| arg.get_id() == id && arg.get_help().is_some())));
+
+        let mut completions = Vec::new();
+        clap_complete::generate(Shell::Bash, &mut Cli::command(), "aoc22", &mut completions);
+        let completions = String::from_utf8(completions).unwrap();
+        assert!(["run", "fetch", "desc", "doctor", "watch", "submit", "bench", "new-day", "visualize", "validate", "completions"].iter().all(|name| completions.contains(name)));
+    }
+
+    #[test]
+    fn expected_answers() {
+        assert_eq!(with_expected("31", None), "31");
+        assert_eq!(with_expected("31", Some("31")), "31 (expected 31) pass");
+        assert_eq!(with_expected("30", Some("31")), "30 (expected 31) FAIL");
+        assert_eq!(with_expected("7\n5", Some("7\n6")), "7\n5\nexpected:\n7\n6\nFAIL");
+    }
+
+    #[test]
+    fn exit_codes() {
+        let error = |day, content| solve(2022, day, 1, content).unwrap_err();
+        #[cfg(feature = "day5")]
+        assert_eq!(exit_code(&error(5, "[A]\n 1 \n\nmove x from 1 to 2\n")), EXIT_INVALID_INPUT);
+        #[cfg(feature = "day5")]
+        assert_eq!(exit_code(&error(5, "[A]\n 1 \n\nmove 1 from 1 to 2\n")), EXIT_INVALID_INPUT);
+        #[cfg(feature = "day12")]
+        assert_eq!(exit_code(&error(12, "SbE")), EXIT_NO_SOLUTION);
+        assert_eq!(exit_code_of(&aoc22::Error::TimedOut(std::time::Duration::from_secs(1))), EXIT_TIMED_OUT);
+        assert_eq!(exit_code(&error(25, "")), 1);
+    }
+
+    #[test]
+    fn missing_inputs() {
+        let runs = runner::run_all(2022, None, |day| {
+            let path = Utf8PathBuf::from(format!("no/such/day{}.txt", day));
+            Ok(DayInput { content: Input::Path(path.clone()).into_input()?, source: path.to_string() })
+        });
+        assert!(runs.iter().all(|run| matches!(run.answer, Err(aoc22::Error::Input(_)))));
+        assert!(runs.iter().all(|run| run.answer.as_ref().is_err_and(|e| exit_code_of(e) == 1)));
+        assert_eq!(runs_exit_code(&runs), if runs.is_empty() { ExitCode::SUCCESS } else { ExitCode::from(1) });
+    }
 }
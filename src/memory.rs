@@ -0,0 +1,91 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+// The system allocator, counting the bytes each thread holds. Counts are per thread so that parts
+// solved side by side on the rayon pool are measured separately; memory freed by another thread
+// than the one allocating it is not followed.
+pub struct CountingAllocator;
+
+thread_local! {
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+// Allocations made while a thread is torn down are not counted.
+fn record(delta: isize) {
+    let _ = CURRENT.try_with(|current| {
+        let now = current.get() + delta;
+        current.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size as isize - layout.size() as isize);
+        }
+        new
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// The most heap the current thread held on top of what it already had while running `f`, in bytes.
+pub fn peak_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let start = CURRENT.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+
+    let result = f();
+    let peak = PEAK.with(Cell::get);
+    (result, (peak - start).max(0) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::*;
+
+    #[test]
+    fn thread_peaks() {
+        let ((), peak) = peak_during(|| {
+            let buffer = vec![0_u8; 1 << 20];
+            std::hint::black_box(&buffer);
+        });
+        assert!((1 << 20..1 << 21).contains(&peak));
+
+        let (kept, peak) = peak_during(|| Vec::<u64>::with_capacity(1000));
+        assert_eq!(peak, 8000);
+        let ((), peak) = peak_during(|| drop(kept));
+        assert_eq!(peak, 0);
+
+        let ((), peak) = peak_during(|| {
+            std::thread::spawn(|| vec![0_u8; 1 << 20]).join().unwrap();
+        });
+        assert!(peak < 1 << 20);
+    }
+}
@@ -0,0 +1,61 @@
+use camino::Utf8Path;
+use std::{
+    fs::File,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Unable to profile: {0}")]
+    Pprof(#[from] pprof::Error),
+}
+
+// Samples per second.
+const FREQUENCY: i32 = 1000;
+// Most days are solved before the profiler takes a handful of samples, so runs are repeated
+// until this long has been sampled.
+const MIN_SAMPLING: Duration = Duration::from_secs(1);
+
+// The result of the last run, and how many runs the flamegraph covers.
+pub fn flamegraph<T>(path: &Utf8Path, mut run: impl FnMut() -> T) -> Result<(T, usize), Error> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+
+    let start = Instant::now();
+    let mut runs = 1;
+    let mut result = run();
+    while start.elapsed() < MIN_SAMPLING {
+        result = run();
+        runs += 1;
+    }
+
+    let report = guard.report().build()?;
+    report.flamegraph(File::create(path)?)?;
+    Ok((result, runs))
+}
+
+// Profiles day 7, so it only builds along with it.
+#[cfg(all(test, feature = "day7"))]
+mod tests {
+    use crate::profile::*;
+    use crate::solution;
+
+    #[test]
+    fn day_flamegraph() -> Result<(), Error> {
+        let path = crate::utils::unique_temp_path("aoc22_flamegraph").with_extension("svg");
+        let day7 = solution::puzzle(2022, 7).unwrap();
+
+        let (answer, runs) = flamegraph(&path, || day7.part1(day7.example().input).unwrap())?;
+        assert_eq!(answer, "95437");
+        assert!(runs > 1);
+        assert!(std::fs::read_to_string(&path)?.contains("<svg"));
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
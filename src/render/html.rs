@@ -0,0 +1,243 @@
+use crate::render::style::ColorSupport;
+use crate::visualize::Rgb;
+
+// Text is wrapped to this many columns, code blocks are left as they are.
+const WIDTH: usize = 80;
+// Code blocks and list items continuation lines.
+const INDENT: usize = 4;
+
+// The site's own colors: green titles, bright emphasis, code on a darker background.
+const HEADING: Rgb = [0, 204, 0];
+const EMPHASIS: Rgb = [255, 255, 255];
+const CODE: Rgb = [204, 204, 204];
+const CODE_BACKGROUND: Rgb = [16, 16, 26];
+
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let character = entity.and_then(|(name, _)| match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => name
+                .strip_prefix("#x")
+                .map_or_else(|| name.strip_prefix('#').and_then(|n| n.parse().ok()), |n| u32::from_str_radix(n, 16).ok())
+                .and_then(char::from_u32),
+        });
+        match (character, entity) {
+            (Some(character), Some((_, end))) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+struct Writer {
+    colors: ColorSupport,
+    out: String,
+    // Visible characters on the current line, escapes excluded.
+    column: usize,
+    indent: usize,
+    space: bool,
+    styles: Vec<String>,
+    // Code blocks are gathered whole, to be indented and styled line by line once closed.
+    pre: Option<String>,
+}
+
+impl Writer {
+    fn newline(&mut self) {
+        self.out.push('\n');
+        self.out.push_str(&" ".repeat(self.indent));
+        self.column = self.indent;
+        self.space = false;
+    }
+
+    // Blocks are separated by a blank line, however many tags open or close between them.
+    fn block(&mut self) {
+        self.indent = 0;
+        self.space = false;
+        if !self.out.is_empty() {
+            let trimmed = self.out.trim_end_matches([' ', '\n']).len();
+            self.out.truncate(trimmed);
+            self.out.push_str("\n\n");
+        }
+        self.column = 0;
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(pre) = &mut self.pre {
+            pre.push_str(&decode(text));
+            return;
+        }
+
+        let text = decode(text);
+        self.space |= text.starts_with(char::is_whitespace);
+        for (i, word) in text.split_whitespace().enumerate() {
+            let width = word.chars().count();
+            if i > 0 || self.space {
+                if self.column > self.indent && self.column + 1 + width > WIDTH {
+                    self.newline();
+                } else if self.column > self.indent {
+                    self.out.push(' ');
+                    self.column += 1;
+                }
+            }
+            self.out.push_str(word);
+            self.column += width;
+            self.space = false;
+        }
+        self.space = text.ends_with(char::is_whitespace);
+    }
+
+    fn push_style(&mut self, style: String) {
+        if self.space && self.column > self.indent {
+            self.out.push(' ');
+            self.column += 1;
+        }
+        self.space = false;
+        self.out.push_str(&style);
+        self.styles.push(style);
+    }
+
+    fn pop_style(&mut self) {
+        if self.styles.pop().is_some() {
+            self.out.push_str(self.colors.reset());
+            self.out.push_str(&self.styles.concat());
+        }
+    }
+
+    fn code_block(&mut self, code: &str) {
+        self.block();
+        let style = format!("{}{}", self.colors.foreground(CODE), self.colors.background(CODE_BACKGROUND));
+        let width = code.lines().map(|line| line.chars().count()).max().unwrap_or_default();
+        for line in code.trim_end_matches('\n').lines() {
+            let padding = if self.colors == ColorSupport::None { 0 } else { width - line.chars().count() };
+            self.out.push_str(&format!("{}{}{}{}{}\n", " ".repeat(INDENT), style, line, " ".repeat(padding), self.colors.reset()));
+        }
+        self.block();
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), closing) {
+            ("pre", false) => self.pre = Some(String::new()),
+            ("pre", true) => {
+                let code = self.pre.take().unwrap_or_default();
+                self.code_block(&code);
+            }
+            _ if self.pre.is_some() => {}
+            ("h1" | "h2" | "h3", false) => {
+                self.block();
+                let style = format!("{}{}", self.colors.bold(), self.colors.foreground(HEADING));
+                self.push_style(style);
+            }
+            ("h1" | "h2" | "h3", true) => {
+                self.pop_style();
+                self.block();
+            }
+            ("p" | "ul" | "ol", _) => self.block(),
+            ("li", false) => {
+                self.indent = 0;
+                if self.column > 0 {
+                    self.newline();
+                }
+                self.out.push_str("  - ");
+                self.indent = INDENT;
+                self.column = INDENT;
+                self.space = false;
+            }
+            ("br", _) => self.newline(),
+            ("em", false) => {
+                let style = format!("{}{}", self.colors.bold(), self.colors.foreground(EMPHASIS));
+                self.push_style(style);
+            }
+            ("code", false) => {
+                let style = format!("{}{}", self.colors.foreground(CODE), self.colors.background(CODE_BACKGROUND));
+                self.push_style(style);
+            }
+            ("em" | "code", true) => self.pop_style(),
+            _ => {}
+        }
+    }
+}
+
+// Just enough HTML for the puzzle pages: titles, paragraphs, lists, emphasis, inline code and code
+// blocks. Anything else is dropped, keeping its text.
+pub fn to_terminal(html: &str, colors: ColorSupport) -> String {
+    let mut writer = Writer { colors, out: String::new(), column: 0, indent: 0, space: false, styles: Vec::new(), pre: None };
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        match rest.find('<') {
+            Some(0) => match rest.find('>') {
+                Some(end) => {
+                    writer.tag(&rest[1..end]);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    writer.text(rest);
+                    rest = "";
+                }
+            },
+            Some(start) => {
+                writer.text(&rest[..start]);
+                rest = &rest[start..];
+            }
+            None => {
+                writer.text(rest);
+                rest = "";
+            }
+        }
+    }
+
+    writer.block();
+    writer.out.trim_end().to_string() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render::html::*;
+
+    #[test]
+    fn puzzle_text() {
+        let html = "<h2>--- Day 1: Calorie Counting ---</h2><p>The Elves take turns writing down the number of \
+            <em>Calories</em> contained by the various meals, <code>1000</code> &amp; more:</p>\n\
+            <pre><code>1000\n2000\n\n3000\n</code></pre>\n<ul><li>The first Elf</li><li>The <em>second</em> one</li></ul>\
+            <p>Find the Elf carrying the &lt;most&gt; Calories.&#x20;&#33;</p>";
+
+        assert_eq!(
+            to_terminal(html, ColorSupport::None),
+            "--- Day 1: Calorie Counting ---\n\n\
+            The Elves take turns writing down the number of Calories contained by the\n\
+            various meals, 1000 & more:\n\n    1000\n    2000\n    \n    3000\n\n  \
+            - The first Elf\n  - The second one\n\n\
+            Find the Elf carrying the <most> Calories. !\n"
+        );
+
+        let colored = to_terminal("<p>a <em>b <code>c</code> d</em> e</p><pre><code>ab\nc</code></pre>", ColorSupport::Ansi256);
+        assert!(colored.starts_with("a \x1b[1m\x1b[38;5;231mb \x1b[38;5;251m\x1b[48;5;16mc\x1b[0m\x1b[1m\x1b[38;5;231m d\x1b[0m e\n\n"));
+        assert!(colored.ends_with("    \x1b[38;5;251m\x1b[48;5;16mc \x1b[0m\n"));
+        assert_eq!(decode("&unknown; & &#65;"), "&unknown; & A");
+    }
+}
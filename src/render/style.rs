@@ -0,0 +1,134 @@
+use std::io::IsTerminal;
+use crate::visualize::{Rgb, gradient};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    None,
+}
+
+impl ColorSupport {
+    pub fn detect() -> Self {
+        Self::detect_from(|name| std::env::var(name).ok(), std::io::stdout().is_terminal())
+    }
+
+    // NO_COLOR and dumb terminals win over everything, pipes never get escapes.
+    fn detect_from(var: impl Fn(&str) -> Option<String>, terminal: bool) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        let no_color = var("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+        if !terminal || no_color || term.is_empty() || term == "dumb" {
+            ColorSupport::None
+        } else if matches!(var("COLORTERM").as_deref(), Some("truecolor" | "24bit")) {
+            ColorSupport::TrueColor
+        } else {
+            ColorSupport::Ansi256
+        }
+    }
+
+    // Empty when colors are not supported.
+    pub fn background(self, color: Rgb) -> String {
+        let [r, g, b] = color;
+        match self {
+            ColorSupport::TrueColor => format!("\x1b[48;2;{};{};{}m", r, g, b),
+            ColorSupport::Ansi256 => format!("\x1b[48;5;{}m", ansi256(color)),
+            ColorSupport::None => String::new(),
+        }
+    }
+
+    pub fn foreground(self, color: Rgb) -> String {
+        let [r, g, b] = color;
+        match self {
+            ColorSupport::TrueColor => format!("\x1b[38;2;{};{};{}m", r, g, b),
+            ColorSupport::Ansi256 => format!("\x1b[38;5;{}m", ansi256(color)),
+            ColorSupport::None => String::new(),
+        }
+    }
+
+    pub fn bold(self) -> &'static str {
+        match self {
+            ColorSupport::None => "",
+            _ => "\x1b[1m",
+        }
+    }
+
+    pub fn reset(self) -> &'static str {
+        match self {
+            ColorSupport::None => "",
+            _ => "\x1b[0m",
+        }
+    }
+}
+
+// Grays go to the 24 steps gray ramp, everything else to the closest level of the 6x6x6 cube.
+fn ansi256([r, g, b]: Rgb) -> u8 {
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            248..=255 => 231,
+            gray => 232 + (gray - 8) / 10,
+        };
+    }
+
+    let level = |c: u8| match c {
+        0..=47 => 0,
+        48..=114 => 1,
+        c => (c - 35) / 40,
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+const SHADES: &[u8] = b" .:-=+*#%@";
+
+// What a color degrades to without any color support, by perceived brightness.
+pub fn shade([r, g, b]: Rgb) -> char {
+    let luma = (299 * r as usize + 587 * g as usize + 114 * b as usize) / 1000;
+    SHADES[luma * (SHADES.len() - 1) / 255] as char
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    pub background: Rgb,
+    pub low: Rgb,
+    pub high: Rgb,
+}
+
+impl Palette {
+    pub fn ramp(&self, step: usize, steps: usize) -> Rgb {
+        gradient(self.low, self.high, step, steps)
+    }
+}
+
+// Day 8 visibility: hidden trees are dark, brighter the more directions they are seen from.
+pub const HEAT: Palette = Palette { background: [20, 40, 20], low: [40, 120, 40], high: [160, 255, 120] };
+// Day 12 terrain, from a to z.
+pub const ELEVATION: Palette = Palette { background: [0, 0, 0], low: [30, 30, 30], high: [180, 180, 180] };
+// Day 12 search frontiers, from the first step to the last.
+pub const SEARCH: Palette = Palette { background: [0, 0, 0], low: [40, 90, 255], high: [255, 60, 40] };
+// Day 10 screen, lit pixels use the high end.
+pub const CRT: Palette = Palette { background: [10, 20, 10], low: [60, 200, 60], high: [120, 255, 120] };
+
+#[cfg(test)]
+mod tests {
+    use crate::render::style::*;
+
+    #[test]
+    fn color_support() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+
+        assert_eq!(ColorSupport::detect_from(env(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]), true), ColorSupport::TrueColor);
+        assert_eq!(ColorSupport::detect_from(env(&[("TERM", "xterm-256color")]), true), ColorSupport::Ansi256);
+        assert_eq!(ColorSupport::detect_from(env(&[("TERM", "xterm-256color")]), false), ColorSupport::None);
+        assert_eq!(ColorSupport::detect_from(env(&[("TERM", "dumb"), ("COLORTERM", "truecolor")]), true), ColorSupport::None);
+        assert_eq!(ColorSupport::detect_from(env(&[("TERM", "xterm"), ("NO_COLOR", "1")]), true), ColorSupport::None);
+        assert_eq!(ColorSupport::detect_from(env(&[]), true), ColorSupport::None);
+
+        assert_eq!(ColorSupport::Ansi256.background([255, 0, 0]), "\x1b[48;5;196m");
+        assert_eq!(ColorSupport::Ansi256.background([128, 128, 128]), "\x1b[48;5;244m");
+        assert_eq!(ColorSupport::None.background([255, 0, 0]), "");
+        assert_eq!((shade([0, 0, 0]), shade([255, 255, 255])), (' ', '@'));
+    }
+}
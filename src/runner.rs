@@ -0,0 +1,364 @@
+use rayon::prelude::*;
+use std::{
+    fmt::{self, Write},
+    io,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+use crate::{
+    Error,
+    input,
+    solution::{self, Answer, Solution},
+    utils::size::human_size,
+};
+
+// A day's input along with where it came from, as reported in the output.
+#[derive(Clone, Debug)]
+pub struct DayInput {
+    pub source: String,
+    pub content: String,
+}
+
+#[derive(Debug)]
+pub struct PartRun {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer, Error>,
+    // Solving time only, input resolution is not included.
+    pub elapsed: Duration,
+    pub source: Option<String>,
+    // Most heap held while solving, in bytes. Only measured with the memory feature.
+    pub peak_memory: Option<usize>,
+}
+
+#[cfg(feature = "memory")]
+fn measured<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    let (result, peak) = crate::memory::peak_during(f);
+    (result, Some(peak))
+}
+
+#[cfg(not(feature = "memory"))]
+fn measured<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    (f(), None)
+}
+
+pub fn run_part(year: u16, day: u8, part: u8, solution: &dyn Solution, input: &DayInput) -> PartRun {
+    let start = Instant::now();
+    let (answer, peak_memory) = measured(|| solution.part(part, &input.content));
+    let elapsed = start.elapsed();
+
+    PartRun { year, day, part, answer, elapsed, source: Some(input.source.clone()), peak_memory }
+}
+
+// Solves on a worker thread, given up on after `timeout`. Threads cannot be stopped from the outside,
+// so a part that timed out keeps running in the background until the process exits.
+pub fn run_part_within(
+    year: u16,
+    day: u8,
+    part: u8,
+    solution: &'static dyn Solution,
+    input: &DayInput,
+    timeout: Option<Duration>,
+) -> PartRun {
+    let Some(timeout) = timeout else {
+        return run_part(year, day, part, solution, input);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let worker_input = input.clone();
+    let worker = thread::Builder::new()
+        .name(format!("day{} part{}", day, part))
+        .spawn(move || sender.send(run_part(year, day, part, solution, &worker_input)));
+    if let Err(e) = worker {
+        let answer = Err(Error::Io(e));
+        return PartRun { year, day, part, answer, elapsed: Duration::ZERO, source: Some(input.source.clone()), peak_memory: None };
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(run) => run,
+        // A panicking solution drops the sender, like Solution::part would have unwound.
+        Err(RecvTimeoutError::Disconnected) => {
+            let answer = Err(Error::Panicked(day, part));
+            PartRun { year, day, part, answer, elapsed: Duration::ZERO, source: Some(input.source.clone()), peak_memory: None }
+        }
+        Err(RecvTimeoutError::Timeout) => {
+            tracing::warn!("Day {} part {} still running after {:?}, left behind", day, part, timeout);
+            let answer = Err(Error::TimedOut(timeout));
+            PartRun { year, day, part, answer, elapsed: timeout, source: Some(input.source.clone()), peak_memory: None }
+        }
+    }
+}
+
+// Errors cannot be cloned, so the first part of a day whose input failed gets an error of the same
+// kind with the same message, the second one the original.
+fn same_failure(error: &Error) -> Error {
+    match error {
+        Error::Parse(source) => Error::parse(source.to_string()),
+        Error::Validation(source) => Error::validation(source.to_string()),
+        Error::Solve(source) => Error::solve(source.to_string()),
+        Error::Output(source) => Error::output(source.to_string()),
+        Error::Io(e) => Error::Io(io::Error::new(e.kind(), e.to_string())),
+        Error::Input(e) => Error::Input(input::Error::Io(io::Error::other(e.to_string()))),
+        Error::UnknownPart(part) => Error::UnknownPart(*part),
+        Error::UnknownVariant(part, name) => Error::UnknownVariant(*part, name.clone()),
+        Error::TimedOut(timeout) => Error::TimedOut(*timeout),
+        Error::Panicked(day, part) => Error::Panicked(*day, *part),
+    }
+}
+
+// Runs both parts of every registered day of a year, each within `timeout` when given. A day whose
+// input cannot be resolved reports that error for both parts instead of stopping the whole run.
+// Inputs are resolved one day after the other, as they may be downloaded, then every part is
+// solved on the rayon pool.
+pub fn run_all<F>(year: u16, timeout: Option<Duration>, mut input: F) -> Vec<PartRun>
+    where F: FnMut(u8) -> Result<DayInput, Error> {
+    let days = solution::days(year).collect::<Vec<_>>();
+    let inputs = days.iter().map(|(day, _)| input(*day)).collect::<Vec<_>>();
+
+    // Owned, so that the original error of a failed input goes through to its second part.
+    days.into_par_iter()
+        .zip(inputs.into_par_iter())
+        .flat_map_iter(|((day, solution), input)| {
+            let failed = |part, e| PartRun { year, day, part, answer: Err(e), elapsed: Duration::ZERO, source: None, peak_memory: None };
+            match input {
+                Ok(input) => [1, 2].map(|part| run_part_within(year, day, part, solution, &input, timeout)),
+                Err(e) => [failed(1, same_failure(&e)), failed(2, e)],
+            }
+        })
+        .collect()
+}
+
+// How long repeated solves of a part take. Every run is timed, the first one included.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timings {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "min {:.2?}, median {:.2?}, mean {:.2?} over {} runs", self.min, self.median, self.mean, self.runs)
+    }
+}
+
+// Stops at the first failed run, an answer that cannot be found once will not be found later.
+pub fn bench_part(solution: &dyn Solution, part: u8, input: &str, runs: usize) -> Result<(Answer, Timings), Error> {
+    let runs = runs.max(1);
+    let mut answer = None;
+    let mut elapsed = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        answer = Some(solution.part(part, input)?);
+        elapsed.push(start.elapsed());
+    }
+
+    elapsed.sort();
+    let timings = Timings {
+        runs,
+        min: elapsed[0],
+        median: elapsed[runs / 2],
+        mean: elapsed.iter().sum::<Duration>() / runs as u32,
+    };
+    Ok((answer.unwrap(), timings))
+}
+
+// Multi-line answers, like rendered letters, only show their first line.
+fn cell(answer: &Result<Answer, Error>) -> String {
+    let text = match answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    };
+
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or_default().to_string();
+    match lines.count() {
+        0 => first,
+        more => format!("{} (+{} lines)", first, more),
+    }
+}
+
+// The peak memory column only shows when it was measured.
+pub fn table(runs: &[PartRun]) -> String {
+    let rows = runs
+        .iter()
+        .map(|run| (
+            run.day.to_string(),
+            run.part.to_string(),
+            cell(&run.answer),
+            format!("{:.2?}", run.elapsed),
+            run.peak_memory.map(|peak| human_size(peak as u64)),
+        ))
+        .collect::<Vec<_>>();
+    let memory = rows.iter().any(|(_, _, _, _, peak)| peak.is_some());
+    let peak_column = |peak: &str| if memory { format!("  {:>10}", peak) } else { String::new() };
+
+    let answer_width = rows.iter().map(|(_, _, answer, _, _)| answer.chars().count()).max().unwrap_or_default().max(6);
+    let mut table = format!("{:>3}  {:>4}  {:<answer_width$}  {:>10}{}\n", "Day", "Part", "Answer", "Time", peak_column("Peak"));
+    for (day, part, answer, elapsed, peak) in rows {
+        let peak = peak_column(peak.as_deref().unwrap_or("-"));
+        writeln!(table, "{:>3}  {:>4}  {:<answer_width$}  {:>10}{}", day, part, answer, elapsed, peak).unwrap();
+    }
+
+    let total = runs.iter().map(|run| run.elapsed).sum::<Duration>();
+    let max_peak = runs.iter().filter_map(|run| run.peak_memory).max().map(|peak| human_size(peak as u64));
+    let peak = peak_column(max_peak.as_deref().unwrap_or("-"));
+    writeln!(table, "{:>3}  {:>4}  {:<answer_width$}  {:>10}{}", "", "", "Total", format!("{:.2?}", total), peak).unwrap();
+    table
+}
+
+// Multi-line answers, like rendered letters, become arrays of rows.
+#[cfg(feature = "serde")]
+pub fn json_record(run: &PartRun) -> serde_json::Value {
+    let mut record = serde_json::json!({
+        "year": run.year,
+        "day": run.day,
+        "part": run.part,
+        "duration_ms": run.elapsed.as_secs_f64() * 1000.0,
+        "input_source": run.source,
+        "peak_bytes": run.peak_memory,
+    });
+    match &run.answer {
+        Ok(answer) => {
+            let answer = answer.to_string();
+            record["answer"] = match answer.lines().count() {
+                0 | 1 => answer.into(),
+                _ => answer.lines().collect::<Vec<_>>().into(),
+            };
+        }
+        Err(e) => {
+            record["answer"] = serde_json::Value::Null;
+            record["error"] = e.to_string().into();
+        }
+    }
+    record
+}
+
+// One JSON object per line.
+#[cfg(feature = "serde")]
+pub fn json_lines(runs: &[PartRun]) -> String {
+    let mut lines = String::new();
+    for run in runs {
+        writeln!(lines, "{}", json_record(run)).unwrap();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::*;
+
+    #[test]
+    fn run_all_examples() {
+        let runs = run_all(2022, None, |day| {
+            let source = format!("src/year2022/data/day{}_example.txt", day);
+            match day {
+                3 => Err(crate::input::Error::MissingSession.into()),
+                _ => Ok(DayInput { content: std::fs::read_to_string(&source)?, source }),
+            }
+        });
+        assert_eq!(runs.len(), 2 * solution::days(2022).count());
+        assert!(runs.iter().map(|run| (run.day, run.part)).eq(solution::days(2022).flat_map(|(day, _)| [(day, 1), (day, 2)])));
+        assert!(run_all(2021, None, |_| unreachable!()).is_empty());
+        assert!(runs.iter().all(|run| run.answer.is_ok() != (run.day == 3)));
+        let missing = runs.iter().filter(|run| run.day == 3).map(|run| run.answer.as_ref().unwrap_err()).collect::<Vec<_>>();
+        assert!(missing.iter().all(|e| matches!(e, Error::Input(_)) && e.to_string() == crate::input::Error::MissingSession.to_string()));
+        assert!(missing.iter().skip(1).all(|e| matches!(e, Error::Input(crate::input::Error::MissingSession))));
+
+        let table = table(&runs);
+        assert_eq!(table.lines().any(|line| line.starts_with("  1     2  45000 ")), cfg!(feature = "day1"));
+        assert_eq!(
+            table.lines().any(|line| line.starts_with(" 10     2  ##..##..##..##..##..##..##..##..##..##.. (+5 lines)")),
+            cfg!(feature = "day10")
+        );
+        assert_eq!(table.lines().count(), runs.len() + 2);
+        assert_eq!(table.lines().next().unwrap().ends_with("Peak"), cfg!(feature = "memory"));
+        assert!(runs.iter().all(|run| run.peak_memory.is_some() == (cfg!(feature = "memory") && run.answer.is_ok())));
+    }
+
+    #[test]
+    fn timed_out_parts() {
+        struct Endless;
+
+        impl Solution for Endless {
+            fn example(&self) -> solution::Example {
+                solution::Example { input: "", answers: [None, None] }
+            }
+
+            fn part1(&self, input: &str) -> Result<Answer, Error> {
+                assert_ne!(input, "panic");
+                Ok(input.len().into())
+            }
+
+            fn part2(&self, _input: &str) -> Result<Answer, Error> {
+                loop {
+                    thread::sleep(Duration::from_millis(10));
+                }
+            }
+        }
+
+        let input = DayInput { source: "test".to_string(), content: "abc".to_string() };
+        let timeout = Some(Duration::from_millis(50));
+        let run = run_part_within(2022, 13, 2, &Endless, &input, timeout);
+        assert!(matches!(run.answer, Err(Error::TimedOut(timeout)) if timeout == Duration::from_millis(50)));
+        assert_eq!(run.elapsed, Duration::from_millis(50));
+
+        let run = run_part_within(2022, 13, 1, &Endless, &input, timeout);
+        assert_eq!(run.answer.ok(), Some("3".into()));
+        assert!(run_part_within(2022, 13, 1, &Endless, &input, None).answer.is_ok());
+
+        let input = DayInput { source: "test".to_string(), content: "panic".to_string() };
+        // Unwinding can take longer than the timeout above on a busy machine.
+        let panicked = run_part_within(2022, 13, 1, &Endless, &input, Some(Duration::from_secs(60)));
+        assert!(matches!(panicked.answer, Err(Error::Panicked(13, 1))));
+
+        let runs = run_all(2022, Some(Duration::from_secs(60)), |day| Ok(DayInput {
+            source: "example".to_string(),
+            content: solution::puzzle(2022, day).unwrap().example().input.to_string(),
+        }));
+        assert!(runs.iter().all(|run| run.answer.is_ok()));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn repeated_solves() -> Result<(), Error> {
+        let day1 = solution::puzzle(2022, 1).unwrap();
+        let (answer, timings) = bench_part(day1, 2, day1.example().input, 5)?;
+        assert_eq!(answer, "45000");
+        assert_eq!(timings.runs, 5);
+        assert!(timings.min <= timings.median && timings.min <= timings.mean);
+        assert_eq!(bench_part(day1, 1, day1.example().input, 0)?.1.runs, 1);
+        assert!(matches!(bench_part(day1, 3, "", 2), Err(Error::UnknownPart(3))));
+        Ok(())
+    }
+
+    #[cfg(all(feature = "serde", feature = "day1", feature = "day10", feature = "day12"))]
+    #[test]
+    fn json_records() {
+        let input = |day| DayInput {
+            source: format!("src/year2022/data/day{}_example.txt", day),
+            content: std::fs::read_to_string(format!("src/year2022/data/day{}_example.txt", day)).unwrap(),
+        };
+        let runs = [
+            run_part(2022, 1, 2, &crate::year2022::day01::Day1, &input(1)),
+            run_part(2022, 10, 2, &crate::year2022::day10::Day10, &input(10)),
+            run_part(2022, 12, 1, &crate::year2022::day12::Day12, &DayInput { source: "stdin".to_string(), content: "SbE".to_string() }),
+        ];
+
+        let records = json_lines(&runs)
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(records[0]["answer"], "45000");
+        assert_eq!((records[0]["year"].as_u64(), records[0]["day"].as_u64()), (Some(2022), Some(1)));
+        assert_eq!(records[0]["input_source"], "src/year2022/data/day1_example.txt");
+        assert!(records[0]["duration_ms"].is_f64());
+        assert_eq!(records[1]["answer"][0], "##..##..##..##..##..##..##..##..##..##..");
+        assert_eq!(records[1]["answer"].as_array().unwrap().len(), 6);
+        assert!(records[2]["answer"].is_null());
+        assert!(records[2]["error"].is_string());
+    }
+}
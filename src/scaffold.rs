@@ -0,0 +1,139 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use std::{fs, io::Write};
+
+// A new day that compiles and runs from the start: every part counts the lines of its input until
+// solved for real.
+pub fn day_module(year: u16, day: u8) -> String {
+    format!(r#"use thiserror::Error;
+use crate::{{
+    input::IntoInput,
+    solution::{{Answer, Example, Solution}},
+}};
+
+pub fn read_input(content: &str) -> Result<Vec<String>, Error> {{
+    Ok(content.lines().map(str::to_string).collect())
+}}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<usize, Error> {{
+    let content = input.into_input()?;
+    let lines = read_input(&content)?;
+    Ok(lines.len())
+}}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<usize, Error> {{
+    let content = input.into_input()?;
+    let lines = read_input(&content)?;
+    Ok(lines.len())
+}}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    fn example(&self) -> Example {{
+        Example {{ input: include_str!("data/day{day}_example.txt"), answers: [None, None] }}
+    }}
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {{
+        Ok(run_challenge1(input)?.into())
+    }}
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {{
+        Ok(run_challenge2(input)?.into())
+    }}
+}}
+
+#[derive(Debug, Error)]
+pub enum Error {{
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+}}
+
+impl From<Error> for crate::Error {{
+    fn from(e: Error) -> Self {{
+        match e {{
+            Error::Input(e) => crate::Error::Input(e),
+        }}
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use crate::year{year}::day{day:02}::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {{
+        let answer = run_challenge1(include_str!("data/day{day}_example.txt"))?;
+        println!("{{}}", answer);
+        Ok(())
+    }}
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {{
+        let answer = run_challenge2(include_str!("data/day{day}_example.txt"))?;
+        println!("{{}}", answer);
+        Ok(())
+    }}
+}}
+"#)
+}
+
+// What still has to be written by hand for the new day to be compiled in.
+pub fn registration(year: u16, day: u8) -> Vec<String> {
+    vec![
+        format!("Cargo.toml, in [features] and in default: day{} = []", day),
+        format!("src/year{}.rs: #[cfg(feature = \"day{}\")] pub mod day{:02};", year, day, day),
+        format!(
+            "src/solution.rs, in PUZZLES: #[cfg(feature = \"day{}\")] ({}, {}, &crate::year{}::day{:02}::Day{}),",
+            day, year, day, year, day, day,
+        ),
+    ]
+}
+
+// Where a day's module lives under `source`, usually src.
+pub fn module_path(source: &Utf8Path, year: u16, day: u8) -> Utf8PathBuf {
+    source.join(format!("year{}", year)).join(format!("day{:02}.rs", day))
+}
+
+// The module and its empty example and challenge inputs under `source`, usually src. Existing files
+// are never overwritten, so a half-solved day is safe from a second run.
+pub fn create(source: &Utf8Path, year: u16, day: u8) -> std::io::Result<Vec<Utf8PathBuf>> {
+    let directory = source.join(format!("year{}", year));
+    let files = [
+        (module_path(source, year, day), day_module(year, day)),
+        (directory.join("data").join(format!("day{}_example.txt", day)), String::new()),
+        (directory.join("data").join(format!("day{}_challenge.txt", day)), String::new()),
+    ];
+
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, format!("{} already exists", path)));
+    }
+
+    fs::create_dir_all(directory.join("data"))?;
+    for (path, content) in &files {
+        fs::File::create_new(path)?.write_all(content.as_bytes())?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::*;
+
+    #[test]
+    fn new_day_files() -> std::io::Result<()> {
+        let root = Utf8PathBuf::from_path_buf(std::env::temp_dir().join("aoc22_scaffold")).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        let files = create(&root, 2022, 13)?;
+        assert_eq!(files[0], root.join("year2022/day13.rs"));
+        assert_eq!(files[1], root.join("year2022/data/day13_example.txt"));
+        let module = fs::read_to_string(&files[0])?;
+        assert!(module.contains("pub struct Day13;"));
+        assert!(module.contains("use crate::year2022::day13::*;"));
+        assert!(module.contains("include_str!(\"data/day13_example.txt\")"));
+        assert!(matches!(create(&root, 2022, 13), Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists));
+
+        assert_eq!(registration(2022, 13)[1], "src/year2022.rs: #[cfg(feature = \"day13\")] pub mod day13;");
+        fs::remove_dir_all(&root)
+    }
+}
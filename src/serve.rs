@@ -0,0 +1,98 @@
+use axum::{
+    Json,
+    Router,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use serde_json::json;
+use tokio::net::TcpListener;
+use crate::{
+    Error,
+    runner::{self, DayInput},
+    solution,
+};
+
+fn status_of(error: &Error) -> StatusCode {
+    match error {
+        Error::Parse(_) | Error::Validation(_) | Error::Input(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        Error::UnknownPart(_) => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+// Pages served from anywhere may call the solvers.
+fn reply(status: StatusCode, body: serde_json::Value) -> Response {
+    (status, [(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")], Json(body)).into_response()
+}
+
+// The body is the raw input, the reply the same record as `run --format json`, with an error
+// instead of the answer when solving failed.
+async fn solve(State(year): State<u16>, Path((day, part)): Path<(u8, u8)>, input: String) -> Response {
+    let Some(solution) = solution::puzzle(year, day) else {
+        return reply(StatusCode::NOT_FOUND, json!({ "error": format!("No solution for day {} of {}", day, year) }));
+    };
+
+    // Solvers are CPU bound, they would hold up every other request on the runtime's threads.
+    let input = DayInput { source: "request".to_string(), content: input };
+    let run = tokio::task::spawn_blocking(move || runner::run_part(year, day, part, solution, &input)).await;
+    match run {
+        Ok(run) => {
+            let status = run.answer.as_ref().err().map_or(StatusCode::OK, status_of);
+            reply(status, runner::json_record(&run))
+        }
+        Err(e) => reply(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e.to_string() })),
+    }
+}
+
+pub fn router(year: u16) -> Router {
+    Router::new().route("/solve/{day}/{part}", post(solve)).with_state(year)
+}
+
+pub async fn serve_on(listener: TcpListener, year: u16) -> std::io::Result<()> {
+    axum::serve(listener, router(year)).await
+}
+
+// Blocks for as long as the server runs, `bound` is told the actual address once listening.
+pub fn serve(address: &str, year: u16, bound: impl FnOnce(std::net::SocketAddr)) -> std::io::Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_io().build()?;
+    runtime.block_on(async {
+        let listener = TcpListener::bind(address).await?;
+        bound(listener.local_addr()?);
+        serve_on(listener, year).await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serve::*;
+
+    #[test]
+    fn solve_requests() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || serve("127.0.0.1:0", 2022, |address| sender.send(address).unwrap()));
+        let address = receiver.recv().unwrap();
+
+        let agent = ureq::Agent::config_builder().http_status_as_error(false).build().new_agent();
+        let post = |path: &str, body: &str| {
+            let mut response = agent.post(&format!("http://{}{}", address, path)).send(body).unwrap();
+            let json: serde_json::Value = serde_json::from_str(&response.body_mut().read_to_string().unwrap()).unwrap();
+            (response.status().as_u16(), response.headers().contains_key("access-control-allow-origin"), json)
+        };
+
+        let (status, cors, json) = post("/solve/1/2", include_str!("year2022/data/day1_example.txt"));
+        assert_eq!((status, cors), (200, true));
+        assert_eq!((json["day"].as_u64(), json["part"].as_u64(), json["answer"].as_str()), (Some(1), Some(2), Some("45000")));
+
+        let (status, _, json) = post("/solve/10/2", include_str!("year2022/data/day10_example.txt"));
+        assert_eq!(status, 200);
+        assert_eq!(json["answer"].as_array().map(Vec::len), Some(6));
+
+        let (status, _, json) = post("/solve/1/1", "not calories");
+        assert_eq!(status, 422);
+        assert!(json["error"].as_str().is_some_and(|error| error.starts_with("Invalid input")));
+        assert_eq!(post("/solve/25/1", "").0, 404);
+        assert_eq!(post("/solve/1/3", "1\n").0, 404);
+    }
+}
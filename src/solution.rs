@@ -0,0 +1,159 @@
+use std::fmt::{Display, Formatter};
+use crate::{
+    Error,
+    input::IntoInput,
+    visualize::Frame,
+};
+
+// A puzzle answer as submitted: numbers, stack tops or rendered letters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer(String);
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($($from:ty),*) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(value: $from) -> Self {
+                    Answer(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from!(i64, u32, u64, usize, String, &str);
+
+// A day's bundled example input, with the answers the puzzle text gives for it when known.
+#[derive(Clone, Copy, Debug)]
+pub struct Example {
+    pub input: &'static str,
+    pub answers: [Option<&'static str>; 2],
+}
+
+impl Example {
+    pub fn answer(&self, part: u8) -> Option<&'static str> {
+        self.answers.get(usize::from(part).checked_sub(1)?).copied().flatten()
+    }
+}
+
+pub trait Solution: Sync {
+    fn example(&self) -> Example;
+
+    // Whether the input reads as this day's, without solving it. Days without a parser of their
+    // own solve the first part instead.
+    fn parse(&self, input: &str) -> Result<(), Error> {
+        self.part1(input).map(|_| ())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Error>;
+    fn part2(&self, input: &str) -> Result<Answer, Error>;
+
+    fn part(&self, part: u8, input: &str) -> Result<Answer, Error> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(Error::UnknownPart(part)),
+        }
+    }
+
+    // Other ways of solving a part, picked by name, for days that have more than one.
+    fn variants(&self, _part: u8) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn variant(&self, part: u8, name: &str, _input: &str) -> Result<Answer, Error> {
+        Err(Error::UnknownVariant(part, name.to_string()))
+    }
+
+    // Human-readable steps behind an answer, for days that can explain themselves.
+    fn details(&self, _part: u8, _input: &str) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    // How a part is solved, as a sequence of pictures, for days that can draw themselves.
+    fn visualize(&self, _part: u8, _input: &str) -> Result<Vec<Frame>, Error> {
+        Ok(Vec::new())
+    }
+}
+
+impl dyn Solution {
+    pub fn solve(&self, part: u8, input: impl IntoInput) -> Result<Answer, Error> {
+        self.part(part, &input.into_input()?)
+    }
+}
+
+// The year puzzles are taken from when none is given.
+pub const DEFAULT_YEAR: u16 = 2022;
+
+// Every compiled-in puzzle, ordered by year then day.
+pub static PUZZLES: &[(u16, u8, &dyn Solution)] = &[
+    #[cfg(feature = "day1")]
+    (2022, 1, &crate::year2022::day01::Day1),
+    #[cfg(feature = "day2")]
+    (2022, 2, &crate::year2022::day02::Day2),
+    #[cfg(feature = "day3")]
+    (2022, 3, &crate::year2022::day03::Day3),
+    #[cfg(feature = "day4")]
+    (2022, 4, &crate::year2022::day04::Day4),
+    #[cfg(feature = "day5")]
+    (2022, 5, &crate::year2022::day05::Day5),
+    #[cfg(feature = "day6")]
+    (2022, 6, &crate::year2022::day06::Day6),
+    #[cfg(feature = "day7")]
+    (2022, 7, &crate::year2022::day07::Day7),
+    #[cfg(feature = "day8")]
+    (2022, 8, &crate::year2022::day08::Day8),
+    #[cfg(feature = "day9")]
+    (2022, 9, &crate::year2022::day09::Day9),
+    #[cfg(feature = "day10")]
+    (2022, 10, &crate::year2022::day10::Day10),
+    #[cfg(feature = "day11")]
+    (2022, 11, &crate::year2022::day11::Day11),
+    #[cfg(feature = "day12")]
+    (2022, 12, &crate::year2022::day12::Day12),
+];
+
+pub fn puzzle(year: u16, day: u8) -> Option<&'static dyn Solution> {
+    days(year).find(|(number, _)| *number == day).map(|(_, solution)| solution)
+}
+
+pub fn days(year: u16) -> impl Iterator<Item=(u8, &'static dyn Solution)> {
+    PUZZLES.iter().filter(move |(of, _, _)| *of == year).map(|(_, day, solution)| (*day, *solution))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::*;
+
+    #[test]
+    fn every_day_solves_its_example() -> anyhow::Result<()> {
+        assert!(PUZZLES.windows(2).all(|puzzles| (puzzles[0].0, puzzles[0].1) < (puzzles[1].0, puzzles[1].1)));
+
+        for (year, number, solution) in PUZZLES {
+            let example = solution.example();
+            assert_eq!(example.input, std::fs::read_to_string(format!("src/year{}/data/day{}_example.txt", year, number))?);
+            for part in [1, 2] {
+                let answer = solution.part(part, example.input)?;
+                if let Some(expected) = example.answer(part) {
+                    assert_eq!(answer, expected, "day {} part {}", number, part);
+                }
+            }
+        }
+        #[cfg(feature = "day1")]
+        assert_eq!(puzzle(DEFAULT_YEAR, 1).unwrap().example().answer(3), None);
+        assert!(puzzle(2021, 1).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+use thiserror::Error;
+use crate::client::Client;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error("Unable to submit to {0}: {1}")]
+    Http(String, Box<ureq::Error>),
+    #[error("Multi-line answers cannot be submitted as they are, read the letters and give them instead")]
+    MultiLine,
+}
+
+// What the site answers to a submission, from the text of the page it sends back.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    // With the site's "too high" or "too low" hint when it gives one.
+    Incorrect(Option<String>),
+    // With how long is left to wait, as the site phrases it.
+    TooRecent(Option<String>),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("That's the right answer"),
+            Verdict::Incorrect(None) => f.write_str("That's not the right answer"),
+            Verdict::Incorrect(Some(hint)) => write!(f, "That's not the right answer, it is {}", hint),
+            Verdict::TooRecent(None) => f.write_str("An answer was given too recently"),
+            Verdict::TooRecent(Some(wait)) => write!(f, "An answer was given too recently, {} left to wait", wait),
+            Verdict::AlreadySolved => f.write_str("This part is already solved, or is not unlocked yet"),
+            Verdict::Unknown(text) => write!(f, "Unexpected response: {}", text),
+        }
+    }
+}
+
+pub fn url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}/answer", year, day)
+}
+
+// Only the <article> holds the verdict, the rest of the page is the site's chrome.
+fn verdict(page: &str) -> Verdict {
+    let article = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    let text = article
+        .split('<')
+        .map(|chunk| chunk.split_once('>').map_or(chunk, |(_, text)| text))
+        .collect::<String>();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = ["too high", "too low"].into_iter().find(|hint| text.contains(hint));
+        Verdict::Incorrect(hint.map(str::to_string))
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text
+            .split_once("you have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(wait, _)| wait.to_string());
+        Verdict::TooRecent(wait)
+    } else if text.contains("Did you already complete it?") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+pub fn submit(year: u16, day: u8, part: u8, answer: &str, session: &str) -> Result<Verdict, Error> {
+    if answer.contains('\n') {
+        return Err(Error::MultiLine);
+    }
+
+    let url = url(year, day);
+    let page = Client::new(session)
+        .post_form(&url, &[("level", &part.to_string()), ("answer", answer.trim())])
+        .map_err(|e| Error::Http(url, Box::new(e)))?;
+    Ok(verdict(&page))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::submit::*;
+
+    #[test]
+    fn verdicts() {
+        let page = |article: &str| format!("<html><main><article><p>{}</p></article></main></html>", article);
+
+        assert_eq!(verdict(&page("That's the right answer! You are <em>one gold star</em> closer.")), Verdict::Correct);
+        assert_eq!(
+            verdict(&page("That's not the right answer; your answer is too high. Please wait one minute.")),
+            Verdict::Incorrect(Some("too high".to_string())),
+        );
+        assert_eq!(verdict(&page("That's not the right answer.")), Verdict::Incorrect(None));
+        assert_eq!(
+            verdict(&page("You gave an answer too recently; you have 38s left to wait.")),
+            Verdict::TooRecent(Some("38s".to_string())),
+        );
+        assert_eq!(
+            verdict(&page("You don't seem to be solving the right level.  Did you already complete it?")),
+            Verdict::AlreadySolved,
+        );
+        assert_eq!(verdict("<p>Not\n found</p>"), Verdict::Unknown("Not found".to_string()));
+        assert_eq!(url(2022, 7), "https://adventofcode.com/2022/day/7/answer");
+        assert!(matches!(submit(2022, 10, 2, "#.\n.#", "token"), Err(Error::MultiLine)));
+    }
+}
@@ -0,0 +1,19 @@
+pub mod counter;
+pub mod geometry;
+pub mod pathfind;
+pub mod range_map;
+pub mod range_num;
+pub mod range_set;
+pub mod sections;
+pub mod size;
+
+// A path under the system temporary directory that no other test, in this run or a concurrent one,
+// uses: the process id and a counter follow the name.
+#[cfg(test)]
+pub fn unique_temp_path(name: &str) -> camino::Utf8PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let unique = format!("{}_{}_{}", name, std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed));
+    camino::Utf8PathBuf::from_path_buf(std::env::temp_dir().join(unique)).unwrap()
+}
@@ -0,0 +1,174 @@
+use std::{
+    collections::{HashMap, hash_map},
+    hash::Hash,
+    ops::{Add, Sub},
+};
+
+// Multiset of values with how many copies of each. Values whose count drops to zero are removed,
+// so two counters holding the same copies are always equal.
+#[derive(Clone, Debug)]
+pub struct Counter<T> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T> Default for Counter<T> {
+    fn default() -> Self {
+        Counter { counts: HashMap::new() }
+    }
+}
+
+impl<T: Eq + Hash> PartialEq for Counter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<T: Eq + Hash> Eq for Counter<T> {}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, value: T) {
+        self.add_count(value, 1);
+    }
+
+    pub fn add_count(&mut self, value: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(value).or_default() += count;
+        }
+    }
+
+    // Removes one copy, false when there was none.
+    pub fn remove(&mut self, value: &T) -> bool {
+        match self.counts.get_mut(value) {
+            Some(1) => {
+                self.counts.remove(value);
+                true
+            }
+            Some(count) => {
+                *count -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Removes as many copies as `other` holds, never going below zero.
+    pub fn subtract(&mut self, other: &Counter<T>) {
+        for (value, count) in &other.counts {
+            if let Some(own) = self.counts.get_mut(value) {
+                *own = own.saturating_sub(*count);
+            }
+        }
+        self.counts.retain(|_, count| *count > 0);
+    }
+
+    pub fn get(&self, value: &T) -> usize {
+        self.counts.get(value).copied().unwrap_or_default()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.counts.contains_key(value)
+    }
+
+    // Number of distinct values.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    // Number of copies of every value together.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    // In no particular order.
+    pub fn iter(&self) -> impl Iterator<Item=(&T, usize)> + '_ {
+        self.counts.iter().map(|(value, count)| (value, *count))
+    }
+}
+
+impl<T: Eq + Hash + Ord> Counter<T> {
+    // The `n` values with the most copies, ties broken by value so the order never depends on hashing.
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        let mut counts = self.iter().collect::<Vec<_>>();
+        counts.sort_by(|(left, left_count), (right, right_count)| right_count.cmp(left_count).then(left.cmp(right)));
+        counts.truncate(n);
+        counts
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<T> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Counter<T>;
+
+    fn add(mut self, other: Counter<T>) -> Counter<T> {
+        for (value, count) in other {
+            self.add_count(value, count);
+        }
+        self
+    }
+}
+
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Counter<T>;
+
+    fn sub(mut self, other: Counter<T>) -> Counter<T> {
+        self.subtract(&other);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::counter::*;
+
+    #[test]
+    fn multiset_arithmetic() {
+        let mut letters = "abracadabra".chars().collect::<Counter<_>>();
+        assert_eq!((letters.get(&'a'), letters.get(&'z'), letters.len(), letters.total()), (5, 0, 5, 11));
+        assert_eq!(letters.most_common(3), vec![(&'a', 5), (&'b', 2), (&'r', 2)]);
+
+        assert!(letters.remove(&'c'));
+        assert!(!letters.remove(&'c'));
+        assert!(!letters.contains(&'c'));
+
+        let more = "cab".chars().collect::<Counter<_>>();
+        let sum = letters.clone() + more.clone();
+        assert_eq!((sum.get(&'a'), sum.get(&'c')), (6, 1));
+        assert_eq!(sum - more.clone(), letters);
+
+        letters.subtract(&"aaaaaaabbd".chars().collect());
+        assert_eq!(letters, "rr".chars().collect());
+        assert!((more.clone() - more).is_empty());
+    }
+}
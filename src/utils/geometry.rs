@@ -0,0 +1,128 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// A point or an offset on a grid. Which way y grows is up to each day. Ordered row by row, so that
+// sorting points, or breaking ties between them, goes in reading order.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vec2 {
+    pub y: i32,
+    pub x: i32,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Vec2 { x, y }
+    }
+
+    // Grids are far smaller than i32::MAX cells a side.
+    pub fn from_grid(x: usize, y: usize) -> Self {
+        Vec2 { x: x as i32, y: y as i32 }
+    }
+
+    // Column then row, when neither is negative.
+    pub fn to_grid(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    pub fn manhattan(self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(self) -> i32 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    // One step, diagonal if need be, towards the offset.
+    pub fn signum(self) -> Self {
+        Vec2 { x: self.x.signum(), y: self.y.signum() }
+    }
+}
+
+impl From<(i32, i32)> for Vec2 {
+    fn from((x, y): (i32, i32)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl From<Vec2> for (i32, i32) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec2 { x: self.x + rhs.x, y: self.y + rhs.y }
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2 { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Vec2 { x: -self.x, y: -self.y }
+    }
+}
+
+impl Mul<i32> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Vec2 { x: self.x * rhs, y: self.y * rhs }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::geometry::*;
+
+    #[test]
+    fn vector_arithmetic() {
+        let (a, b) = (Vec2::new(3, -4), Vec2::new(-1, 2));
+        assert_eq!(a + b, Vec2::new(2, -2));
+        assert_eq!(a - b, Vec2::new(4, -6));
+        assert_eq!(-a, Vec2::new(-3, 4));
+        assert_eq!(b * 3, Vec2::new(-3, 6));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        assert_eq!((a.manhattan(), a.chebyshev()), (7, 4));
+        assert_eq!((a - b).signum(), Vec2::new(1, -1));
+        assert_eq!(Vec2::ZERO.signum(), Vec2::ZERO);
+
+        assert_eq!(Vec2::from((3, -4)), a);
+        assert_eq!(<(i32, i32)>::from(a), (3, -4));
+        assert_eq!(Vec2::from_grid(5, 2).to_grid(), Some((5, 2)));
+        assert_eq!(a.to_grid(), None);
+
+        // Reading order: rows first.
+        let mut points = vec![Vec2::new(0, 1), Vec2::new(1, 0), Vec2::new(0, 0)];
+        points.sort();
+        assert_eq!(points, vec![Vec2::new(0, 0), Vec2::new(1, 0), Vec2::new(0, 1)]);
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+// What a search found: the cost of the cheapest known way to every node it reached, and what is
+// needed to walk back from any of them to a start.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new() -> Self {
+        Search { distances: HashMap::new(), parents: HashMap::new(), goal: None }
+    }
+
+    // Once a goal is found, nodes that were reached but not expanded yet may have a better way.
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    // From the start it was reached from to the node itself.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+}
+
+// Level by level, each level in node order, so that the path found is the same from one run to the
+// next: a node is reached from the first node of the level before leading to it, and the search
+// stops on the first goal of a level, once that whole level is known.
+pub fn bfs<N, S, I>(starts: impl IntoIterator<Item=N>, mut successors: S, mut goal: impl FnMut(&N) -> bool) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash + Ord,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item=N>,
+{
+    let mut search = Search::new();
+    let mut current = starts.into_iter().collect::<BTreeSet<_>>();
+    search.distances.extend(current.iter().map(|start| (start.clone(), 0)));
+
+    let mut distance = 0;
+    while !current.is_empty() {
+        if let Some(found) = current.iter().find(|node| goal(node)) {
+            search.goal = Some(found.clone());
+            break;
+        }
+
+        distance += 1;
+        let mut next = BTreeSet::new();
+        for node in &current {
+            for successor in successors(node) {
+                if !search.distances.contains_key(&successor) {
+                    search.distances.insert(successor.clone(), distance);
+                    search.parents.insert(successor.clone(), node.clone());
+                    next.insert(successor);
+                }
+            }
+        }
+        current = next;
+    }
+
+    search
+}
+
+pub fn dijkstra<N, C, S, I>(starts: impl IntoIterator<Item=N>, successors: S, goal: impl FnMut(&N) -> bool) -> Search<N, C>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output=C> + Default,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item=(N, C)>,
+{
+    astar(starts, successors, |_| C::default(), goal)
+}
+
+// Successors come with the cost of moving to them, which cannot be negative, and the default cost
+// is zero. The heuristic must never overestimate the cost left to a goal for the path to be the
+// cheapest. Ties are expanded in node order.
+pub fn astar<N, C, S, I>(
+    starts: impl IntoIterator<Item=N>,
+    mut successors: S,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output=C> + Default,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item=(N, C)>,
+{
+    let mut search = Search::new();
+    let mut pending = BinaryHeap::new();
+    for start in starts {
+        search.distances.insert(start.clone(), C::default());
+        pending.push(Reverse((heuristic(&start), C::default(), start)));
+    }
+
+    while let Some(Reverse((_, cost, node))) = pending.pop() {
+        // Already expanded through a cheaper way.
+        if search.distances.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if search.distances.get(&next).is_none_or(|best| next_cost < *best) {
+                search.distances.insert(next.clone(), next_cost);
+                search.parents.insert(next.clone(), node.clone());
+                pending.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::pathfind::*;
+
+    // Open cells of a small maze, `#` being walls.
+    fn maze_successors(maze: &[&str]) -> impl FnMut(&(usize, usize)) -> Vec<(usize, usize)> {
+        let walls = maze.iter().map(|row| row.bytes().map(|cell| cell == b'#').collect::<Vec<_>>()).collect::<Vec<_>>();
+        move |&(y, x)| {
+            [(y.wrapping_sub(1), x), (y + 1, x), (y, x.wrapping_sub(1)), (y, x + 1)]
+                .into_iter()
+                .filter(|(y, x)| walls.get(*y).and_then(|row| row.get(*x)).is_some_and(|wall| !wall))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn breadth_first() {
+        let maze = ["....#", ".##.#", "...#.", "#...."];
+        let search = bfs([(0, 0)], maze_successors(&maze), |node| *node == (3, 4));
+        assert_eq!(search.goal(), Some(&(3, 4)));
+        assert_eq!(search.distance(&(3, 4)), Some(7));
+        assert_eq!(search.path(), Some(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (3, 2), (3, 3), (3, 4)]));
+        assert_eq!(search.path_to(&(0, 0)), Some(vec![(0, 0)]));
+
+        // Ties go to the first goal in node order.
+        let search = bfs([(0, 0)], maze_successors(&maze), |(y, x)| y + x == 3);
+        assert_eq!(search.goal(), Some(&(0, 3)));
+
+        let search = bfs([(0, 0)], maze_successors(&maze), |node| *node == (9, 9));
+        assert_eq!((search.goal(), search.path()), (None, None));
+        assert_eq!(search.distances().len(), 14);
+
+        let search = bfs([(2, 4), (0, 3)], maze_successors(&maze), |node| *node == (1, 3));
+        assert_eq!(search.path(), Some(vec![(0, 3), (1, 3)]));
+    }
+
+    #[test]
+    fn weighted_searches() {
+        // Going through b is longer in steps but cheaper.
+        let edges = HashMap::from([
+            ('a', vec![('b', 1), ('c', 7)]),
+            ('b', vec![('d', 2)]),
+            ('c', vec![('e', 1)]),
+            ('d', vec![('e', 2)]),
+            ('e', vec![]),
+        ]);
+        let successors = |node: &char| edges[node].clone();
+        let search = dijkstra(['a'], successors, |node| *node == 'e');
+        assert_eq!((search.distance(&'e'), search.path()), (Some(5), Some(vec!['a', 'b', 'd', 'e'])));
+
+        let search = dijkstra(['a'], successors, |_| false);
+        assert_eq!(search.distances(), &HashMap::from([('a', 0), ('b', 1), ('c', 7), ('d', 3), ('e', 5)]));
+        assert_eq!(search.goal(), None);
+
+        // With unit costs and the Manhattan distance, as short as breadth first.
+        let maze = ["....#", ".##.#", "...#.", "#...."];
+        let mut steps = maze_successors(&maze);
+        let goal: (usize, usize) = (3, 4);
+        let search = astar(
+            [(0, 0)],
+            |node| steps(node).into_iter().map(|next| (next, 1)).collect::<Vec<_>>(),
+            |&(y, x): &(usize, usize)| goal.0.abs_diff(y) + goal.1.abs_diff(x),
+            |node| *node == goal,
+        );
+        assert_eq!(search.distance(&goal), Some(7));
+        assert_eq!(search.path().map(|path| path.len()), Some(8));
+        assert!(search.distances().len() < 14);
+    }
+}
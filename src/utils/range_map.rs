@@ -0,0 +1,184 @@
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+};
+
+// Ordered, non-overlapping half-open intervals, each carrying a value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangeMap<K, V> {
+    intervals: BTreeMap<K, (K, V)>,
+}
+
+impl<K, V> RangeMap<K, V>
+    where K: Copy + Ord,
+          V: Clone {
+    pub fn new() -> Self {
+        RangeMap { intervals: BTreeMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.intervals
+            .range(..=*key)
+            .next_back()
+            .filter(|(_, (end, _))| key < end)
+            .map(|(_, (_, value))| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(Range<K>, &V)> + '_ {
+        self.intervals
+            .iter()
+            .map(|(start, (end, value))| (*start..*end, value))
+    }
+
+    pub fn overlapping(&self, range: Range<K>) -> impl Iterator<Item=(Range<K>, &V)> + '_ {
+        let first = self.intervals
+            .range(..range.start)
+            .next_back()
+            .filter(|(_, (end, _))| *end > range.start)
+            .map(|(start, _)| *start)
+            .unwrap_or(range.start);
+
+        self.intervals
+            .range(first..)
+            .take_while(move |(start, _)| **start < range.end)
+            .map(|(start, (end, value))| (*start..*end, value))
+    }
+
+    // Overwrites whatever was stored on the range, splitting partially covered intervals.
+    pub fn insert(&mut self, range: Range<K>, value: V) {
+        if range.is_empty() {
+            return;
+        }
+
+        self.split_at(range.start);
+        self.split_at(range.end);
+
+        let covered = self.intervals
+            .range(range.start..range.end)
+            .map(|(start, _)| *start)
+            .collect::<Vec<_>>();
+        for start in covered {
+            self.intervals.remove(&start);
+        }
+
+        self.intervals.insert(range.start, (range.end, value));
+    }
+
+    // Combines the value with the stored ones where they overlap, and fills the gaps with it.
+    pub fn merge<F>(&mut self, range: Range<K>, value: V, mut combine: F)
+        where F: FnMut(&V, &V) -> V {
+        if range.is_empty() {
+            return;
+        }
+
+        self.split_at(range.start);
+        self.split_at(range.end);
+
+        let existing = self.intervals
+            .range(range.start..range.end)
+            .map(|(start, (end, value))| (*start, *end, value.clone()))
+            .collect::<Vec<_>>();
+
+        let mut cursor = range.start;
+        for (start, end, current) in existing {
+            if cursor < start {
+                self.intervals.insert(cursor, (start, value.clone()));
+            }
+            self.intervals.insert(start, (end, combine(&current, &value)));
+            cursor = end;
+        }
+
+        if cursor < range.end {
+            self.intervals.insert(cursor, (range.end, value));
+        }
+    }
+
+    // Joins adjacent intervals holding equal values.
+    pub fn coalesce(&mut self)
+        where V: PartialEq {
+        let mut coalesced: BTreeMap<K, (K, V)> = BTreeMap::new();
+        let mut last: Option<K> = None;
+
+        for (start, (end, value)) in std::mem::take(&mut self.intervals) {
+            match last.and_then(|last| coalesced.get_mut(&last)) {
+                Some((last_end, last_value)) if *last_end == start && *last_value == value => *last_end = end,
+                _ => {
+                    coalesced.insert(start, (end, value));
+                    last = Some(start);
+                }
+            }
+        }
+
+        self.intervals = coalesced;
+    }
+
+    fn split_at(&mut self, at: K) {
+        let split = self.intervals
+            .range(..at)
+            .next_back()
+            .filter(|(_, (end, _))| *end > at)
+            .map(|(start, (end, value))| (*start, *end, value.clone()));
+
+        if let Some((start, end, value)) = split {
+            self.intervals.insert(start, (at, value.clone()));
+            self.intervals.insert(at, (end, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::range_map::*;
+
+    #[test]
+    fn insert_splits_existing_intervals() {
+        let mut map = RangeMap::new();
+        map.insert(0..10, 'a');
+        map.insert(3..5, 'b');
+
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(0..3, &'a'), (3..5, &'b'), (5..10, &'a')]);
+        assert_eq!(map.get(&2), Some(&'a'));
+        assert_eq!(map.get(&3), Some(&'b'));
+        assert_eq!(map.get(&9), Some(&'a'));
+        assert_eq!(map.get(&10), None);
+
+        map.insert(2..8, 'c');
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(0..2, &'a'), (2..8, &'c'), (8..10, &'a')]);
+    }
+
+    #[test]
+    fn merge_combines_and_fills_gaps() {
+        let mut map = RangeMap::new();
+        map.insert(2..4, 1);
+        map.insert(6..8, 1);
+        map.merge(0..10, 1, |a, b| a + b);
+
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(0..2, &1), (2..4, &2), (4..6, &1), (6..8, &2), (8..10, &1)]
+        );
+    }
+
+    #[test]
+    fn overlapping_and_coalesce() {
+        let mut map = RangeMap::new();
+        map.insert(0..5, true);
+        map.insert(5..10, true);
+        map.insert(12..15, false);
+
+        assert_eq!(map.overlapping(4..13).map(|(r, _)| r).collect::<Vec<_>>(), vec![0..5, 5..10, 12..15]);
+        assert_eq!(map.overlapping(10..12).count(), 0);
+
+        map.coalesce();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(0..10, &true), (12..15, &false)]);
+    }
+}
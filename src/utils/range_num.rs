@@ -0,0 +1,65 @@
+use std::{
+    fmt::Debug,
+    ops::RangeInclusive,
+};
+
+// Integer types usable as range bounds. Arithmetic goes through i128, which holds every implementor.
+pub trait RangeNum: Copy + Debug + Ord {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Option<Self>;
+
+    fn successor(self) -> Option<Self> {
+        Self::from_i128(self.to_i128() + 1)
+    }
+}
+
+macro_rules! impl_range_num {
+    ($($num:ty),*) => {
+        $(
+            impl RangeNum for $num {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    <$num>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_range_num!(u32, u64, usize, i32, i64);
+
+// None when either bound would leave the type.
+pub fn shift<N: RangeNum>(range: &RangeInclusive<N>, delta: i128) -> Option<RangeInclusive<N>> {
+    let start = N::from_i128(range.start().to_i128() + delta)?;
+    let end = N::from_i128(range.end().to_i128() + delta)?;
+    Some(start..=end)
+}
+
+pub fn width<N: RangeNum>(range: &RangeInclusive<N>) -> u128 {
+    (range.end().to_i128() + 1 - range.start().to_i128()).max(0) as u128
+}
+
+pub fn intersection<N: RangeNum>(left: &RangeInclusive<N>, right: &RangeInclusive<N>) -> RangeInclusive<N> {
+    *left.start().max(right.start())..=*left.end().min(right.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::range_num::*;
+
+    #[test]
+    fn generic_range_helpers() {
+        assert_eq!(shift(&(1_u32..=3), -1), Some(0..=2));
+        assert_eq!(shift(&(1_u32..=3), -2), None);
+        assert_eq!(shift(&(-5_i64..=-1), 3), Some(-2..=2));
+        assert_eq!(shift(&(0..=u64::MAX), 1), None);
+
+        assert_eq!(width(&(-2_i64..=2)), 5);
+        assert_eq!(width(&(0..=u64::MAX)), u64::MAX as u128 + 1);
+        assert_eq!(width(&intersection(&(0_u32..=3), &(5..=8))), 0);
+        assert_eq!(intersection(&(-10_i64..=4), &(-3..=20)), -3..=4);
+    }
+}
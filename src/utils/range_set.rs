@@ -0,0 +1,92 @@
+use std::{
+    marker::PhantomData,
+    ops::RangeInclusive,
+};
+use crate::utils::{
+    range_map::RangeMap,
+    range_num::RangeNum,
+};
+
+// Union of inclusive ranges. Bounds are kept as i128, so that the exclusive end of a range ending at
+// the largest value of its type still fits.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangeSet<N> {
+    intervals: RangeMap<i128, ()>,
+    values: PhantomData<N>,
+}
+
+impl<N: RangeNum> RangeSet<N> {
+    pub fn new() -> Self {
+        RangeSet { intervals: RangeMap::new(), values: PhantomData }
+    }
+
+    // Only the stored ranges overlapping or touching the new one are looked up and merged into it.
+    pub fn insert(&mut self, range: RangeInclusive<N>) {
+        let (mut start, mut end) = (range.start().to_i128(), range.end().to_i128() + 1);
+        if start >= end {
+            return;
+        }
+
+        let touching = self.intervals.overlapping(start - 1..end + 1).map(|(range, _)| range).collect::<Vec<_>>();
+        for range in touching {
+            start = start.min(range.start);
+            end = end.max(range.end);
+        }
+        self.intervals.insert(start..end, ());
+    }
+
+    pub fn contains(&self, value: N) -> bool {
+        self.intervals.get(&value.to_i128()).is_some()
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item=RangeInclusive<N>> + '_ {
+        self.intervals
+            .iter()
+            .map(|(range, _)| N::from_i128(range.start).unwrap()..=N::from_i128(range.end - 1).unwrap())
+    }
+
+    // Number of values in the set.
+    pub fn count(&self) -> u128 {
+        self.intervals.iter().map(|(range, _)| (range.end - range.start) as u128).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::range_set::*;
+
+    #[test]
+    fn negative_and_large_ranges() {
+        let mut set = RangeSet::new();
+        set.insert(-4_000_000_000_i64..=-2);
+        set.insert(-1..=3);
+        set.insert(10..=12);
+
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![-4_000_000_000..=3, 10..=12]);
+        assert_eq!(set.count(), 4_000_000_004 + 3);
+        assert!(set.contains(-4_000_000_000));
+        assert!(!set.contains(5));
+
+        let mut set = RangeSet::new();
+        set.insert(1_u32..=u32::MAX);
+        set.insert(0..=0);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![0..=u32::MAX]);
+        assert_eq!(set.count(), u32::MAX as u128 + 1);
+        assert!(set.contains(u32::MAX));
+    }
+
+    #[test]
+    fn many_inserts() {
+        let mut set = RangeSet::new();
+        for value in (0..200_000_u32).step_by(2).rev() {
+            set.insert(value..=value);
+        }
+        assert_eq!(set.ranges().count(), 100_000);
+        assert_eq!(set.ranges().nth(1), Some(2..=2));
+
+        set.insert(3..=3);
+        assert_eq!(set.ranges().nth(1), Some(2..=4));
+        set.insert(1..=199_998);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![0..=199_998]);
+    }
+}
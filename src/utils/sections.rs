@@ -0,0 +1,33 @@
+// Splits input into blocks separated by one or more blank lines. Each block keeps its line endings.
+pub fn sections(content: &str) -> impl Iterator<Item=&str> + '_ {
+    let mut rest = content;
+    std::iter::from_fn(move || {
+        let mut start = 0;
+        let mut end = 0;
+        for line in rest.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                if end > start {
+                    break;
+                }
+                start += line.len();
+            }
+            end += line.len();
+        }
+
+        let section = &rest[start..end];
+        rest = &rest[end..];
+        (!section.is_empty()).then_some(section)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::sections::*;
+
+    #[test]
+    fn blank_line_sections() {
+        let content = "\na\nb\n\n\r\nc\n  \nd";
+        assert_eq!(sections(content).collect::<Vec<_>>(), vec!["a\nb\n", "c\n", "d"]);
+        assert_eq!(sections("\n\n").count(), 0);
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    fmt::Write,
+    time::Duration,
+};
+#[cfg(feature = "image")]
+use camino::Utf8Path;
+use crate::render::style::{self, ColorSupport};
+
+pub type Rgb = [u8; 3];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Frame { width, height, pixels: vec![background; width * height] }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    // Two characters per pixel, so pixels look roughly square: colored spaces, or shade characters
+    // when the terminal has no colors.
+    pub fn to_terminal(&self, support: ColorSupport) -> String {
+        let mut text = String::new();
+        for row in self.pixels.chunks(self.width) {
+            for color in row {
+                match support {
+                    ColorSupport::None => {
+                        let shade = style::shade(*color);
+                        write!(text, "{}{}", shade, shade).unwrap();
+                    }
+                    _ => write!(text, "{}  ", support.background(*color)).unwrap(),
+                }
+            }
+            writeln!(text, "{}", support.reset()).unwrap();
+        }
+        text
+    }
+
+    // Every pixel becomes a square of `factor` pixels a side.
+    pub fn scaled(&self, factor: usize) -> Frame {
+        let mut scaled = Frame::new(self.width * factor, self.height * factor, [0, 0, 0]);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.set(x, y, self.get(x / factor, y / factor));
+            }
+        }
+        scaled
+    }
+
+    #[cfg(feature = "image")]
+    pub fn save_png(&self, path: &Utf8Path) -> image::ImageResult<()> {
+        let image = image::RgbImage::from_fn(self.width as u32, self.height as u32, |x, y| {
+            image::Rgb(self.get(x as usize, y as usize))
+        });
+        image.save_with_format(path, image::ImageFormat::Png)
+    }
+}
+
+// Loops forever, each frame shown for `delay`.
+#[cfg(feature = "image")]
+pub fn save_gif(frames: &[Frame], path: &Utf8Path, delay: Duration) -> image::ImageResult<()> {
+    use image::codecs::gif::{GifEncoder, Repeat};
+
+    let mut encoder = GifEncoder::new(std::io::BufWriter::new(std::fs::File::create(path)?));
+    encoder.set_repeat(Repeat::Infinite)?;
+    for frame in frames {
+        let image = image::RgbaImage::from_fn(frame.width as u32, frame.height as u32, |x, y| {
+            let [r, g, b] = frame.get(x as usize, y as usize);
+            image::Rgba([r, g, b, 255])
+        });
+        encoder.encode_frame(image::Frame::from_parts(image, 0, 0, image::Delay::from_saturating_duration(delay)))?;
+    }
+    Ok(())
+}
+
+// Draws each frame over the previous one, waiting `delay` in between. Without colors, the output
+// is likely not a terminal, so only the last frame is written.
+pub fn play(frames: &[Frame], support: ColorSupport, delay: Duration, mut out: impl std::io::Write) -> std::io::Result<()> {
+    if support == ColorSupport::None {
+        if let Some(frame) = frames.last() {
+            write!(out, "{}", frame.to_terminal(support))?;
+        }
+        return out.flush();
+    }
+
+    write!(out, "\x1b[2J")?;
+    for (index, frame) in frames.iter().enumerate() {
+        if index > 0 {
+            std::thread::sleep(delay);
+        }
+        write!(out, "\x1b[H{}", frame.to_terminal(support))?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+// Linear blend from one color to another, `step` out of `steps`.
+pub fn gradient(from: Rgb, to: Rgb, step: usize, steps: usize) -> Rgb {
+    let ratio = if steps == 0 { 0.0 } else { step as f64 / steps as f64 };
+    let blend = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * ratio).round() as u8;
+    [blend(from[0], to[0]), blend(from[1], to[1]), blend(from[2], to[2])]
+}
+
+pub trait Visualize {
+    fn frames(&self) -> Vec<Frame>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::visualize::*;
+
+    #[test]
+    fn frame_rendering() {
+        let mut frame = Frame::new(2, 1, [0, 0, 0]);
+        frame.set(1, 0, [255, 0, 10]);
+
+        assert_eq!(frame.get(1, 0), [255, 0, 10]);
+        assert_eq!(frame.to_terminal(ColorSupport::TrueColor), "\x1b[48;2;0;0;0m  \x1b[48;2;255;0;10m  \x1b[0m\n");
+        assert_eq!(frame.to_terminal(ColorSupport::Ansi256), "\x1b[48;5;16m  \x1b[48;5;196m  \x1b[0m\n");
+        assert_eq!(frame.to_terminal(ColorSupport::None), "  ::\n");
+        assert_eq!(gradient([0, 0, 0], [200, 100, 0], 1, 2), [100, 50, 0]);
+
+        let scaled = frame.scaled(2);
+        assert_eq!((scaled.width, scaled.height), (4, 2));
+        assert_eq!((scaled.get(1, 1), scaled.get(2, 1)), ([0, 0, 0], [255, 0, 10]));
+    }
+
+    #[test]
+    fn playback() -> std::io::Result<()> {
+        let frames = [Frame::new(1, 1, [0, 0, 0]), Frame::new(1, 1, [255, 255, 255])];
+
+        let mut plain = Vec::new();
+        play(&frames, ColorSupport::None, Duration::ZERO, &mut plain)?;
+        assert_eq!(plain, b"@@\n");
+
+        let mut colored = Vec::new();
+        play(&frames, ColorSupport::TrueColor, Duration::ZERO, &mut colored)?;
+        assert_eq!(String::from_utf8(colored).unwrap().matches("\x1b[H").count(), 2);
+        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn gif_export() -> image::ImageResult<()> {
+        use image::AnimationDecoder;
+
+        let frames = [Frame::new(2, 1, [0, 0, 0]), Frame::new(2, 1, [255, 255, 255])];
+        let path = crate::utils::unique_temp_path("aoc22_animation").with_extension("gif");
+        save_gif(&frames, &path, Duration::from_millis(100))?;
+
+        let decoder = image::codecs::gif::GifDecoder::new(std::io::BufReader::new(std::fs::File::open(&path)?))?;
+        let decoded = decoder.into_frames().collect_frames()?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[1].buffer().get_pixel(1, 0).0, [255, 255, 255, 255]);
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::{
+    fs,
+    path::Path,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Notify(#[from] notify::Error),
+    #[error("'{0}' has no parent directory to watch")]
+    NoParentDirectory(Utf8PathBuf),
+}
+
+#[derive(Debug)]
+struct Run {
+    answer: anyhow::Result<String>,
+    elapsed: Duration,
+}
+
+fn timed(run: impl FnOnce() -> anyhow::Result<String>) -> Run {
+    let start = Instant::now();
+    let answer = run();
+    Run { answer, elapsed: start.elapsed() }
+}
+
+fn run_once<F>(input: &Utf8Path, solve: &mut F) -> Result<Run, Error>
+    where F: FnMut(&str) -> anyhow::Result<String> {
+    let content = fs::read_to_string(input)?;
+    Ok(timed(|| solve(&content)))
+}
+
+fn print_run(run: &Run) {
+    match &run.answer {
+        Ok(answer) => println!("{} ({:?})", answer, run.elapsed),
+        Err(e) => println!("error: {} ({:?})", e, run.elapsed),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Change {
+    Input,
+    Source,
+}
+
+fn change(event: &Event, input: &Path, source: Option<&Path>) -> Option<Change> {
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return None;
+    }
+    if source.is_some_and(|source| event.paths.iter().any(|path| path == source)) {
+        Some(Change::Source)
+    } else if event.paths.iter().any(|path| path == input) {
+        Some(Change::Input)
+    } else {
+        None
+    }
+}
+
+// Re-runs the day each time the input or its module changes. The input is solved in process by
+// `solve` until the module changes, after which the compiled solution is stale and every run goes
+// through `rebuild` instead, given the input path.
+//
+// Editors often replace files instead of writing them in place, so parent directories are watched
+// and events are filtered on the watched paths.
+pub fn watch<F, G>(input: &Utf8Path, source: Option<&Utf8Path>, mut solve: F, mut rebuild: G) -> Result<(), Error>
+    where F: FnMut(&str) -> anyhow::Result<String>,
+          G: FnMut(&Utf8Path) -> anyhow::Result<String> {
+    let input = input.canonicalize_utf8()?;
+    let source = source.map(Utf8Path::canonicalize_utf8).transpose()?;
+    let mut directories = [Some(&input), source.as_ref()]
+        .into_iter()
+        .flatten()
+        .map(|path| path.parent().ok_or_else(|| Error::NoParentDirectory(path.clone())))
+        .collect::<Result<Vec<_>, _>>()?;
+    directories.sort();
+    directories.dedup();
+
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    for directory in directories {
+        watcher.watch(directory.as_std_path(), RecursiveMode::NonRecursive)?;
+    }
+
+    print_run(&run_once(&input, &mut solve)?);
+
+    let source_path = source.as_ref().map(|source| source.as_std_path());
+    let mut stale = false;
+    for event in receiver.iter() {
+        let Some(first) = change(&event?, input.as_std_path(), source_path) else {
+            continue;
+        };
+
+        // Drain the burst of events a single save usually produces, without missing a module
+        // saved along with the input.
+        let mut changes = vec![first];
+        while let Ok(event) = receiver.recv_timeout(Duration::from_millis(50)) {
+            changes.extend(event.ok().and_then(|event| change(&event, input.as_std_path(), source_path)));
+        }
+        stale |= changes.contains(&Change::Source);
+
+        let run = if stale { Ok(timed(|| rebuild(&input))) } else { run_once(&input, &mut solve) };
+        match run {
+            Ok(run) => print_run(&run),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::watch::*;
+
+    #[test]
+    fn run_once_reads_input() -> Result<(), Error> {
+        let path = Utf8PathBuf::from_path_buf(std::env::temp_dir().join("aoc22_watch_run_once.txt")).unwrap();
+        fs::write(&path, "1\n2\n3\n")?;
+
+        let mut solve = |content: &str| Ok(content.lines().count().to_string());
+        let run = run_once(&path, &mut solve)?;
+        assert_eq!(run.answer.ok(), Some("3".to_string()));
+
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn watched_changes() {
+        use notify::event::{AccessKind, CreateKind, ModifyKind};
+
+        let (input, source) = (Path::new("/tmp/input.txt"), Path::new("/src/year2022/day13.rs"));
+        let event = |kind, paths: &[&Path]| paths.iter().fold(Event::new(kind), |event, path| event.add_path(path.to_path_buf()));
+
+        assert_eq!(change(&event(EventKind::Modify(ModifyKind::Any), &[input]), input, Some(source)), Some(Change::Input));
+        assert_eq!(change(&event(EventKind::Create(CreateKind::File), &[source]), input, Some(source)), Some(Change::Source));
+        assert_eq!(change(&event(EventKind::Modify(ModifyKind::Any), &[input, source]), input, Some(source)), Some(Change::Source));
+        assert_eq!(change(&event(EventKind::Access(AccessKind::Any), &[input]), input, Some(source)), None);
+        assert_eq!(change(&event(EventKind::Modify(ModifyKind::Any), &[source]), input, None), None);
+        assert_eq!(change(&event(EventKind::Modify(ModifyKind::Any), &[Path::new("/tmp/other.txt")]), input, Some(source)), None);
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(feature = "day1")]
+pub mod day01;
+#[cfg(feature = "day2")]
+pub mod day02;
+#[cfg(feature = "day3")]
+pub mod day03;
+#[cfg(feature = "day4")]
+pub mod day04;
+#[cfg(feature = "day5")]
+pub mod day05;
+#[cfg(feature = "day6")]
+pub mod day06;
+#[cfg(feature = "day7")]
+pub mod day07;
+#[cfg(feature = "day8")]
+pub mod day08;
+#[cfg(feature = "day9")]
+pub mod day09;
+#[cfg(feature = "day10")]
+pub mod day10;
+#[cfg(feature = "day11")]
+pub mod day11;
+#[cfg(feature = "day12")]
+pub mod day12;
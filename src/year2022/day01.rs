@@ -0,0 +1,556 @@
+use nom::{
+    Finish,
+    IResult,
+    bytes::complete::take_while1,
+    character::complete,
+    combinator::{all_consuming, map, opt},
+    multi::many1,
+    sequence::{pair, terminated},
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+};
+use thiserror::Error;
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+
+// A line of the inventory, either plain calories or `label: calories` in the labeled dialect.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Item {
+    label: Option<String>,
+    calories: u64,
+}
+
+impl Item {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let label = terminated(
+            take_while1(|c: char| c != ':' && c != '\r' && c != '\n'),
+            pair(complete::char(':'), complete::space0),
+        );
+        map(
+            pair(opt(label), complete::u64),
+            |(label, calories): (Option<&str>, u64)| Item { label: label.map(|label| label.trim().to_string()), calories },
+        )(i)
+    }
+}
+
+fn parse_elf(i: &str) -> IResult<&str, Vec<Item>> {
+    terminated(
+        many1(
+            terminated(Item::parse, complete::line_ending),
+        ),
+        opt(complete::line_ending),
+    )(i)
+}
+
+// Both dialects may be mixed, even within a single elf.
+pub fn read_items(content: &str) -> Result<Vec<Vec<Item>>, Error> {
+    let (_, elves) = all_consuming(many1(parse_elf))(content)
+        .map_err(|e| e.to_owned())
+        .finish()?;
+
+    Ok(elves)
+}
+
+pub fn read_input(content: &str) -> Result<Vec<Vec<u64>>, Error> {
+    Ok(
+        read_items(content)?
+            .into_iter()
+            .map(|items| items.into_iter().map(|item| item.calories).collect())
+            .collect()
+    )
+}
+
+// The elf carrying it and the item, the first one in the input on ties.
+fn highest_item(elves: &[Vec<Item>]) -> Option<(usize, &Item)> {
+    elves
+        .iter()
+        .enumerate()
+        .flat_map(|(elf, items)| items.iter().map(move |item| (elf, item)))
+        .rev()
+        .max_by_key(|(_, item)| item.calories)
+}
+
+// Calories of every label across all elves, unlabeled items left out.
+fn label_totals(elves: &[Vec<Item>]) -> BTreeMap<&str, u64> {
+    let mut totals = BTreeMap::new();
+    for item in elves.iter().flatten() {
+        if let Some(label) = &item.label {
+            *totals.entry(label.as_str()).or_default() += item.calories;
+        }
+    }
+    totals
+}
+
+// Empty for plain inventories, which have nothing more to tell than the leaderboard.
+pub fn inventory_report(content: &str) -> Result<Vec<String>, Error> {
+    let elves = read_items(content)?;
+    let totals = label_totals(&elves);
+    if totals.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut report = Vec::new();
+    if let Some((elf, item)) = highest_item(&elves) {
+        let label = item.label.as_deref().unwrap_or("unlabeled item");
+        report.push(format!("highest item: {} ({} calories) carried by elf {}", label, item.calories, elf));
+    }
+    report.push("label,total".to_string());
+    report.extend(totals.into_iter().map(|(label, total)| format!("{},{}", label, total)));
+    Ok(report)
+}
+
+fn compute_calories(elves: Vec<Vec<u64>>) -> Vec<u64> {
+    elves
+        .into_iter()
+        .map(|elf| elf.into_iter().sum::<u64>())
+        .collect()
+}
+
+// A single food item, remembering which elf carried it in the input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Snack {
+    pub elf: usize,
+    pub index: usize,
+    pub calories: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+    pub snack: Snack,
+    pub to: usize,
+}
+
+#[derive(Debug)]
+pub struct Rebalancing {
+    pub moves: Vec<Move>,
+    pub distribution: Vec<Vec<Snack>>,
+    pub max_load: u64,
+}
+
+pub fn read_snacks(content: &str) -> Result<Vec<Snack>, Error> {
+    Ok(
+        read_input(content)?
+            .into_iter()
+            .enumerate()
+            .flat_map(|(elf, items)|
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, calories)| Snack { elf, index, calories })
+            )
+            .collect()
+    )
+}
+
+// Largest snack first, each to the least loaded elf (its own carrier on ties).
+fn greedy_assignment(snacks: &[Snack], elves: usize) -> Result<Vec<usize>, Error> {
+    if let Some(snack) = snacks.iter().find(|snack| snack.elf >= elves) {
+        return Err(Error::UnknownElf(snack.elf, elves));
+    }
+
+    let mut loads = vec![0_u64; elves];
+    let mut owners = vec![0_usize; snacks.len()];
+
+    let mut order = (0..snacks.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| std::cmp::Reverse(snacks[*i].calories));
+
+    for i in order {
+        let min_load = loads.iter().cloned().min().unwrap_or_default();
+        let owner = if loads[snacks[i].elf] == min_load {
+            snacks[i].elf
+        } else {
+            loads.iter().position(|load| *load == min_load).unwrap_or_default()
+        };
+
+        owners[i] = owner;
+        loads[owner] += snacks[i].calories;
+    }
+
+    Ok(owners)
+}
+
+// Branch and bound over every assignment, only improving on the best bound found so far.
+fn exact_assignment(snacks: &[Snack], elves: usize, bound: u64) -> Option<Vec<usize>> {
+    fn search(
+        snacks: &[Snack],
+        order: &[usize],
+        loads: &mut Vec<u64>,
+        owners: &mut Vec<usize>,
+        best: &mut (u64, Option<Vec<usize>>),
+    ) {
+        let current_max = loads.iter().cloned().max().unwrap_or_default();
+        if current_max >= best.0 {
+            return;
+        }
+
+        match order.split_first() {
+            None => *best = (current_max, Some(owners.clone())),
+            Some((i, rest)) => {
+                let mut tried: Vec<u64> = Vec::new();
+                for elf in 0..loads.len() {
+                    // Elves with the same load are interchangeable for the maximum.
+                    if tried.contains(&loads[elf]) {
+                        continue;
+                    }
+                    tried.push(loads[elf]);
+
+                    loads[elf] += snacks[*i].calories;
+                    owners[*i] = elf;
+                    search(snacks, rest, loads, owners, best);
+                    loads[elf] -= snacks[*i].calories;
+                }
+            }
+        }
+    }
+
+    let mut order = (0..snacks.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| std::cmp::Reverse(snacks[*i].calories));
+
+    let mut best = (bound, None);
+    search(snacks, &order, &mut vec![0; elves], &mut vec![0; snacks.len()], &mut best);
+    best.1
+}
+
+pub fn rebalance(snacks: &[Snack], elves: usize) -> Result<Rebalancing, Error> {
+    const EXACT_SEARCH_LIMIT: usize = 12;
+
+    let greedy = greedy_assignment(snacks, elves)?;
+    let owners = if snacks.len() <= EXACT_SEARCH_LIMIT {
+        let greedy_max = max_load(snacks, &greedy, elves);
+        exact_assignment(snacks, elves, greedy_max).unwrap_or(greedy)
+    } else {
+        greedy
+    };
+
+    let mut distribution = vec![Vec::new(); elves];
+    let mut moves = Vec::new();
+    for (snack, owner) in snacks.iter().zip(owners.iter()) {
+        distribution[*owner].push(*snack);
+        if snack.elf != *owner {
+            moves.push(Move { snack: *snack, to: *owner });
+        }
+    }
+
+    Ok(Rebalancing {
+        max_load: max_load(snacks, &owners, elves),
+        moves,
+        distribution,
+    })
+}
+
+fn max_load(snacks: &[Snack], owners: &[usize], elves: usize) -> u64 {
+    let mut loads = vec![0_u64; elves];
+    for (snack, owner) in snacks.iter().zip(owners) {
+        loads[*owner] += snack.calories;
+    }
+    loads.into_iter().max().unwrap_or_default()
+}
+
+// The load before and after rebalancing among the same elves, then every move it takes.
+pub fn rebalancing_report(content: &str) -> Result<Vec<String>, Error> {
+    let snacks = read_snacks(content)?;
+    let elves = snacks.iter().map(|snack| snack.elf + 1).max().unwrap_or_default();
+    let owners = snacks.iter().map(|snack| snack.elf).collect::<Vec<_>>();
+    let rebalancing = rebalance(&snacks, elves)?;
+
+    let mut report = vec![format!(
+        "rebalanced max load: {} (was {}) in {} moves",
+        rebalancing.max_load,
+        max_load(&snacks, &owners, elves),
+        rebalancing.moves.len(),
+    )];
+    report.extend(rebalancing.moves.iter().map(|Move { snack, to }|
+        format!("move item {} of elf {} ({} calories) to elf {}", snack.index, snack.elf, snack.calories, to)
+    ));
+    Ok(report)
+}
+
+// (rank, elf index, total), heaviest first. Ties keep input order so ranks never depend on the sort.
+fn standings(elves_calories: &[u64]) -> Vec<(usize, usize, u64)> {
+    let mut elves = elves_calories.iter().cloned().enumerate().collect::<Vec<_>>();
+    elves.sort_by(|(left_elf, left), (right_elf, right)| right.cmp(left).then(left_elf.cmp(right_elf)));
+    elves
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (elf, total))| (rank + 1, elf, total))
+        .collect()
+}
+
+pub fn leaderboard(content: &str) -> Result<Vec<(usize, usize, u64)>, Error> {
+    Ok(standings(&compute_calories(read_input(content)?)))
+}
+
+pub fn leaderboard_csv(leaderboard: &[(usize, usize, u64)]) -> String {
+    let mut csv = String::from("rank,elf,total\n");
+    for (rank, elf, total) in leaderboard {
+        csv.push_str(&format!("{},{},{}\n", rank, elf, total));
+    }
+    csv
+}
+
+#[cfg(feature = "serde")]
+pub fn leaderboard_json(leaderboard: &[(usize, usize, u64)]) -> Result<String, Error> {
+    let standings = leaderboard
+        .iter()
+        .map(|(rank, elf, total)| serde_json::json!({ "rank": rank, "elf": elf, "total": total }))
+        .collect::<Vec<_>>();
+    Ok(serde_json::to_string(&standings)?)
+}
+
+fn top_calories(elves_calories: &[u64], count: usize) -> u64 {
+    standings(elves_calories).iter().take(count).map(|(_, _, total)| total).sum()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CalorieChange {
+    pub elf: usize,
+    pub before: u64,
+    pub after: u64,
+}
+
+impl CalorieChange {
+    pub fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+// Elves are matched by their position in the input.
+#[derive(Debug, PartialEq)]
+pub struct InventoryDelta {
+    pub changes: Vec<CalorieChange>,
+    pub added: Vec<usize>,
+    pub removed: Vec<usize>,
+    pub top3_change: i64,
+}
+
+impl fmt::Display for InventoryDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "elf {}: {} -> {} ({:+})", change.elf, change.before, change.after, change.delta())?;
+        }
+        let elves = |elves: &[usize]| elves.iter().map(usize::to_string).collect::<Vec<_>>().join(", ");
+        if !self.added.is_empty() {
+            writeln!(f, "new elves: {}", elves(&self.added))?;
+        }
+        if !self.removed.is_empty() {
+            writeln!(f, "removed elves: {}", elves(&self.removed))?;
+        }
+        writeln!(f, "top 3: {:+}", self.top3_change)
+    }
+}
+
+pub fn diff_inventories(before: &str, after: &str) -> Result<InventoryDelta, Error> {
+    let before = compute_calories(read_input(before)?);
+    let after = compute_calories(read_input(after)?);
+
+    let changes = before.iter()
+        .zip(after.iter())
+        .enumerate()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(elf, (before, after))| CalorieChange { elf, before: *before, after: *after })
+        .collect();
+
+    Ok(InventoryDelta {
+        changes,
+        added: (before.len()..after.len()).collect(),
+        removed: (after.len()..before.len()).collect(),
+        top3_change: top_calories(&after, 3) as i64 - top_calories(&before, 3) as i64,
+    })
+}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<u64, Error> {
+    let content = input.into_input()?;
+    let elves = read_input(&content)?;
+    let elves = compute_calories(elves);
+    Ok(top_calories(&elves, 1))
+}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<u64, Error> {
+    let content = input.into_input()?;
+    let elves = read_input(&content)?;
+    let elves = compute_calories(elves);
+
+    Ok(top_calories(&elves, 3))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day1_example.txt"), answers: [Some("24000"), Some("45000")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    fn details(&self, _part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        let mut details = leaderboard_csv(&leaderboard(input)?).lines().map(str::to_string).collect::<Vec<_>>();
+        details.extend(inventory_report(input)?);
+        details.extend(rebalancing_report(input)?);
+        Ok(details)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Snack carried by elf {0}, but there are only {1} elves")]
+    UnknownElf(usize, usize),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            e @ Error::Nom(..) => crate::Error::parse(e),
+            #[cfg(feature = "serde")]
+            e @ Error::Json(..) => crate::Error::output(e),
+            e @ Error::UnknownElf(..) => crate::Error::validation(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day01::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let max = run_challenge1(include_str!("data/day1_example.txt"))?;
+        assert_eq!(max, 24000);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let max = run_challenge1(include_str!("data/day1_challenge.txt"))?;
+        println!("{}", max);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let top3 = run_challenge2(include_str!("data/day1_example.txt"))?;
+        assert_eq!(top3, 45000);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let top3 = run_challenge2(include_str!("data/day1_challenge.txt"))?;
+        println!("{}", top3);
+        Ok(())
+    }
+
+    #[test]
+    fn rebalancing() -> Result<(), Error> {
+        let snacks = read_snacks(include_str!("data/day1_example.txt"))?;
+        let elves = 5;
+        let total = snacks.iter().map(|s| s.calories).sum::<u64>();
+
+        let rebalancing = rebalance(&snacks, elves)?;
+        assert_eq!(rebalancing.max_load, 11000);
+        assert_eq!(rebalancing.distribution.iter().flatten().map(|s| s.calories).sum::<u64>(), total);
+        for Move { snack, to } in rebalancing.moves.iter() {
+            assert_ne!(snack.elf, *to);
+            assert!(rebalancing.distribution[*to].contains(snack));
+        }
+
+        let snacks = [3, 3, 2, 2, 2].into_iter()
+            .enumerate()
+            .map(|(index, calories)| Snack { elf: 0, index, calories })
+            .collect::<Vec<_>>();
+        assert_eq!(max_load(&snacks, &greedy_assignment(&snacks, 2)?, 2), 7);
+        assert_eq!(rebalance(&snacks, 2)?.max_load, 6);
+
+        // The example has five elves.
+        let snacks = read_snacks(include_str!("data/day1_example.txt"))?;
+        assert!(matches!(greedy_assignment(&snacks, 4), Err(Error::UnknownElf(4, 4))));
+        assert!(matches!(rebalance(&[], 0), Ok(Rebalancing { max_load: 0, .. })));
+
+        let report = rebalancing_report(include_str!("data/day1_example.txt"))?;
+        assert_eq!(report[0], "rebalanced max load: 11000 (was 24000) in 4 moves");
+        assert_eq!(report.len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn inventory_delta() -> Result<(), Error> {
+        let before = include_str!("data/day1_example.txt");
+        let after = "1000\n2000\n3000\n\n4500\n\n5000\n6000\n\n7000\n8000\n9000\n";
+
+        let delta = diff_inventories(before, after)?;
+        assert_eq!(delta.changes, vec![CalorieChange { elf: 1, before: 4000, after: 4500 }]);
+        assert_eq!(delta.changes[0].delta(), 500);
+        assert_eq!(delta.removed, vec![4]);
+        assert!(delta.added.is_empty());
+        assert_eq!(delta.top3_change, -4000);
+
+        let delta = diff_inventories(after, before)?;
+        assert_eq!(delta.added, vec![4]);
+        assert_eq!(delta.top3_change, 4000);
+        assert_eq!(delta.to_string(), "elf 1: 4500 -> 4000 (-500)\nnew elves: 4\ntop 3: +4000\n");
+        Ok(())
+    }
+
+    #[test]
+    fn leaderboard_ties() -> Result<(), Error> {
+        let board = leaderboard("5\n\n7\n\n2\n3\n\n7\n")?;
+        assert_eq!(board, vec![(1, 1, 7), (2, 3, 7), (3, 0, 5), (4, 2, 5)]);
+        assert_eq!(leaderboard_csv(&board[..2]), "rank,elf,total\n1,1,7\n2,3,7\n");
+        Ok(())
+    }
+
+    #[test]
+    fn labeled_inventory() -> Result<(), Error> {
+        let content = "chocolate: 5000\napple:300\n\n2000\ncandy bar : 7000\n\nchocolate: 4000\nnuts: 7000\n";
+        let elves = read_items(content)?;
+        assert_eq!(elves[1][0], Item { label: None, calories: 2000 });
+        assert_eq!(highest_item(&elves), Some((1, &Item { label: Some("candy bar".to_string()), calories: 7000 })));
+        assert_eq!(
+            label_totals(&elves).into_iter().collect::<Vec<_>>(),
+            vec![("apple", 300), ("candy bar", 7000), ("chocolate", 9000), ("nuts", 7000)]
+        );
+        assert_eq!(run_challenge1(content)?, 11000);
+        assert_eq!(run_challenge2(content)?, 25300);
+        assert_eq!(
+            inventory_report(content)?,
+            vec!["highest item: candy bar (7000 calories) carried by elf 1", "label,total", "apple,300", "candy bar,7000", "chocolate,9000", "nuts,7000"]
+        );
+
+        assert!(inventory_report(include_str!("data/day1_example.txt"))?.is_empty());
+        assert!(read_items("chocolate: \n").is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn leaderboard_export() -> Result<(), Error> {
+        let board = leaderboard(include_str!("data/day1_example.txt"))?;
+        assert_eq!(
+            leaderboard_json(&board[..1])?,
+            r#"[{"elf":3,"rank":1,"total":24000}]"#
+        );
+        Ok(())
+    }
+}
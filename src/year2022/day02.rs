@@ -0,0 +1,687 @@
+use nom::{
+    Finish,
+    IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete,
+    combinator::{all_consuming, map, opt, value},
+    multi::many1,
+    sequence::separated_pair,
+};
+use nom::sequence::terminated;
+use std::fmt;
+use thiserror::Error;
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+
+#[derive(Clone, Debug)]
+enum Outcome {
+    Win,
+    Draw,
+    Lost,
+}
+
+impl Outcome {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            value(Outcome::Lost, tag("X")),
+            value(Outcome::Draw, tag("Y")),
+            value(Outcome::Win, tag("Z")),
+        ))(i)
+    }
+
+    fn score(&self) -> u32 {
+        match self {
+            Outcome::Win => 6_u32,
+            Outcome::Draw => 3_u32,
+            Outcome::Lost => 0_u32,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Win => "win",
+            Outcome::Draw => "draw",
+            Outcome::Lost => "loss",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            value(Shape::Rock, tag("X")),
+            value(Shape::Rock, tag("A")),
+            value(Shape::Paper, tag("Y")),
+            value(Shape::Paper, tag("B")),
+            value(Shape::Scissors, tag("Z")),
+            value(Shape::Scissors, tag("C")),
+        ))(i)
+    }
+
+    fn against(&self, other: &Shape) -> Outcome {
+        match (self, other) {
+            (first, second) if first == second => Outcome::Draw,
+            (Shape::Rock, Shape::Scissors) => Outcome::Win,
+            (Shape::Scissors, Shape::Paper) => Outcome::Win,
+            (Shape::Paper, Shape::Rock) => Outcome::Win,
+            _ => Outcome::Lost
+        }
+    }
+
+    fn score(&self) -> u32 {
+        match self {
+            Shape::Rock => 1_u32,
+            Shape::Paper => 2_u32,
+            Shape::Scissors => 3_u32,
+        }
+    }
+
+    fn deduce_from_outcome(&self, outcome: &Outcome) -> Shape {
+        match (self, outcome) {
+            (shape, Outcome::Draw) => shape.to_owned(),
+            (Shape::Rock, Outcome::Lost) => Shape::Scissors,
+            (Shape::Rock, Outcome::Win) => Shape::Paper,
+            (Shape::Paper, Outcome::Lost) => Shape::Rock,
+            (Shape::Paper, Outcome::Win) => Shape::Scissors,
+            (Shape::Scissors, Outcome::Lost) => Shape::Paper,
+            (Shape::Scissors, Outcome::Win) => Shape::Rock,
+        }
+    }
+}
+
+// A round resolved to both shapes and its outcome, whichever column meaning produced it.
+#[derive(Debug)]
+struct Play {
+    elf: Shape,
+    me: Shape,
+    outcome: Outcome,
+}
+
+impl Play {
+    fn score(&self) -> u32 {
+        self.me.score() + self.outcome.score()
+    }
+
+    fn explain(&self) -> String {
+        format!(
+            "You chose {:?} vs {:?}: {} ({}) + shape ({}) = {}",
+            self.me, self.elf, self.outcome.name(), self.outcome.score(), self.me.score(), self.score()
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Round {
+    elf: Shape,
+    me: Shape,
+}
+
+impl Round {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        terminated(
+            map(
+                separated_pair(Shape::parse, complete::space1, Shape::parse),
+                |(elf, me)| Round { elf, me },
+            ),
+            opt(complete::line_ending),
+        )(i)
+    }
+
+    fn play(&self) -> Play {
+        Play { elf: self.elf.clone(), me: self.me.clone(), outcome: self.me.against(&self.elf) }
+    }
+
+    fn score(&self) -> u32 {
+        self.play().score()
+    }
+
+    fn explain(&self) -> String {
+        self.play().explain()
+    }
+}
+
+#[derive(Debug)]
+struct RoundV2 {
+    elf: Shape,
+    me: Outcome,
+}
+
+impl RoundV2 {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        terminated(
+            map(
+                separated_pair(Shape::parse, complete::space1, Outcome::parse),
+                |(elf, me)| RoundV2 { elf, me },
+            ),
+            opt(complete::line_ending),
+        )(i)
+    }
+
+    fn play(&self) -> Play {
+        Play { elf: self.elf.clone(), me: self.elf.deduce_from_outcome(&self.me), outcome: self.me.clone() }
+    }
+
+    fn score(&self) -> u32 {
+        self.play().score()
+    }
+
+    fn explain(&self) -> String {
+        self.play().explain()
+    }
+}
+
+// Probabilities of playing each of Shape::ALL.
+pub type Distribution = [f64; 3];
+
+pub fn distribution(shapes: &[Shape]) -> Distribution {
+    let mut distribution = [0.0; 3];
+    for shape in shapes {
+        distribution[Shape::ALL.iter().position(|s| s == shape).unwrap()] += 1.0;
+    }
+
+    let total = shapes.len().max(1) as f64;
+    distribution.map(|count| count / total)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Strategy {
+    Always(Shape),
+    Mixed(Distribution),
+}
+
+impl Strategy {
+    pub fn distribution(&self) -> Distribution {
+        match self {
+            Strategy::Always(shape) => Shape::ALL.map(|s| if s == *shape { 1.0 } else { 0.0 }),
+            Strategy::Mixed(distribution) => *distribution,
+        }
+    }
+
+    pub fn expected_score(&self, elf: &Distribution) -> f64 {
+        let me = self.distribution();
+        let mut expected = 0.0;
+
+        for (me_probability, me_shape) in me.iter().zip(Shape::ALL) {
+            for (elf_probability, elf_shape) in elf.iter().zip(Shape::ALL) {
+                let round = Round { elf: elf_shape, me: me_shape.clone() };
+                expected += me_probability * elf_probability * round.score() as f64;
+            }
+        }
+
+        expected
+    }
+}
+
+// Shapes never played are left out of a mix.
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Always(shape) => write!(f, "always {:?}", shape),
+            Strategy::Mixed(distribution) => {
+                let weights = Shape::ALL.iter()
+                    .zip(distribution)
+                    .filter(|(_, probability)| **probability > 0.0)
+                    .map(|(shape, probability)| format!("{:?} {:.0}%", shape, probability * 100.0))
+                    .collect::<Vec<_>>();
+                write!(f, "mix of {}", weights.join(", "))
+            }
+        }
+    }
+}
+
+// Scores are linear in the mixing weights, so any mix of the best fixed responses is optimal.
+pub fn optimal_strategy(elf: &Distribution) -> Strategy {
+    let scores = Shape::ALL.map(|shape| Strategy::Always(shape).expected_score(elf));
+    let best = scores.iter().cloned().fold(f64::MIN, f64::max);
+    let best_shapes = scores.map(|score| if (score - best).abs() < 1e-9 { 1.0 } else { 0.0 });
+    let ties = best_shapes.iter().sum::<f64>();
+
+    match Shape::ALL.into_iter().zip(best_shapes).filter(|(_, tie)| *tie > 0.0).collect::<Vec<_>>().as_slice() {
+        [(shape, _)] => Strategy::Always(shape.clone()),
+        _ => Strategy::Mixed(best_shapes.map(|tie| tie / ties)),
+    }
+}
+
+// Fixed responses and the uniform mix, best first.
+pub fn rank_strategies(elf: &Distribution) -> Vec<(Strategy, f64)> {
+    let mut ranked = Shape::ALL
+        .into_iter()
+        .map(Strategy::Always)
+        .chain([Strategy::Mixed([1.0 / 3.0; 3])])
+        .map(|strategy| {
+            let score = strategy.expected_score(elf);
+            (strategy, score)
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+    ranked
+}
+
+// How the elf plays, the expected score of each strategy against it, then the best one.
+pub fn strategy_report(rounds: &[Round]) -> Vec<String> {
+    let elf = distribution(&rounds.iter().map(|round| round.elf.clone()).collect::<Vec<_>>());
+
+    let mut report = vec![format!("elf plays {}", Strategy::Mixed(elf))];
+    report.extend(rank_strategies(&elf).into_iter().map(|(strategy, score)| format!("{}: {:.2}", strategy, score)));
+    report.push(format!("optimal: {}", optimal_strategy(&elf)));
+    report
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dialect {
+    Letters,
+    LowercaseLetters,
+    Words,
+    LowercaseWords,
+}
+
+impl Dialect {
+    const DETECTION_LINES: usize = 5;
+
+    fn detect(content: &str) -> Result<Self, Error> {
+        let mut detected: Option<Dialect> = None;
+
+        for line in content.lines().filter(|l| !l.trim().is_empty()).take(Self::DETECTION_LINES) {
+            let token = line.split_whitespace().next().unwrap_or_default();
+            let dialect = match token {
+                "A" | "B" | "C" => Dialect::Letters,
+                "a" | "b" | "c" => Dialect::LowercaseLetters,
+                "Rock" | "Paper" | "Scissors" => Dialect::Words,
+                "rock" | "paper" | "scissors" => Dialect::LowercaseWords,
+                _ => Err(Error::UnknownDialect(line.to_string()))?,
+            };
+
+            match detected {
+                Some(previous) if previous != dialect => Err(Error::UnknownDialect(line.to_string()))?,
+                _ => detected = Some(dialect),
+            }
+        }
+
+        detected.ok_or_else(|| Error::UnknownDialect(String::new()))
+    }
+
+    // Maps a token of this dialect to its letter in the official format.
+    fn letter(self, column: usize, token: &str) -> Option<&'static str> {
+        let lowercase = matches!(self, Dialect::LowercaseLetters | Dialect::LowercaseWords);
+        if lowercase != (token.to_lowercase() == token) {
+            return None;
+        }
+
+        match (self, column, token.to_lowercase().as_str()) {
+            (Dialect::Letters | Dialect::LowercaseLetters, 0, "a") => Some("A"),
+            (Dialect::Letters | Dialect::LowercaseLetters, 0, "b") => Some("B"),
+            (Dialect::Letters | Dialect::LowercaseLetters, 0, "c") => Some("C"),
+            (Dialect::Letters | Dialect::LowercaseLetters, 1, "x") => Some("X"),
+            (Dialect::Letters | Dialect::LowercaseLetters, 1, "y") => Some("Y"),
+            (Dialect::Letters | Dialect::LowercaseLetters, 1, "z") => Some("Z"),
+            (Dialect::Words | Dialect::LowercaseWords, 0, "rock") => Some("A"),
+            (Dialect::Words | Dialect::LowercaseWords, 0, "paper") => Some("B"),
+            (Dialect::Words | Dialect::LowercaseWords, 0, "scissors") => Some("C"),
+            (Dialect::Words | Dialect::LowercaseWords, 1, "rock" | "lose") => Some("X"),
+            (Dialect::Words | Dialect::LowercaseWords, 1, "paper" | "draw") => Some("Y"),
+            (Dialect::Words | Dialect::LowercaseWords, 1, "scissors" | "win") => Some("Z"),
+            _ => None,
+        }
+    }
+
+    fn normalize(self, content: &str) -> Result<String, Error> {
+        let mut normalized = String::with_capacity(content.len());
+
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            let tokens = line.split_whitespace()
+                .enumerate()
+                .map(|(column, token)| self.letter(column, token).ok_or_else(|| Error::InvalidToken(token.to_string(), self)))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            normalized.push_str(&tokens.join(" "));
+            normalized.push('\n');
+        }
+
+        Ok(normalized)
+    }
+}
+
+fn read_with_dialect<T>(content: &str, parser: fn(&str) -> IResult<&str, T>) -> Result<(Dialect, Vec<T>), Error> {
+    let dialect = Dialect::detect(content)?;
+    let normalized = dialect.normalize(content)?;
+
+    let (_, rounds) = all_consuming(many1(parser))(normalized.as_str())
+        .map_err(|e| e.to_owned())
+        .finish()?;
+
+    Ok((dialect, rounds))
+}
+
+// Running scores of an interactive game, under both readings of the second column.
+#[derive(Debug, Default)]
+pub struct Game {
+    rounds: usize,
+    shape_score: u32,
+    outcome_score: u32,
+}
+
+impl Game {
+    // A single round in any dialect. Returns it as a line of the puzzle format.
+    pub fn play(&mut self, line: &str) -> Result<String, Error> {
+        let normalized = Dialect::detect(line)?.normalize(line)?;
+        let (_, round) = all_consuming(Round::parse)(&normalized)
+            .map_err(|e| e.to_owned())
+            .finish()?;
+        let (_, round_v2) = all_consuming(RoundV2::parse)(&normalized)
+            .map_err(|e| e.to_owned())
+            .finish()?;
+
+        self.rounds += 1;
+        self.shape_score += round.score();
+        self.outcome_score += round_v2.score();
+        Ok(normalized)
+    }
+
+    pub fn status(&self) -> String {
+        format!("round {}: {} with shapes, {} with outcomes", self.rounds, self.shape_score, self.outcome_score)
+    }
+}
+
+// Plays rounds as they come until the input ends or says quit. Every valid round is answered with
+// both running scores and written to the log, so the log is an input of its own; invalid ones
+// are answered with the error and skipped.
+#[cfg(feature = "play")]
+pub fn play(input: impl std::io::BufRead, mut output: impl std::io::Write, mut log: impl std::io::Write) -> Result<(u32, u32), Error> {
+    let mut game = Game::default();
+    for line in input.lines() {
+        let line = line?;
+        match line.trim() {
+            "" => continue,
+            "quit" => break,
+            line => match game.play(line) {
+                Ok(normalized) => {
+                    log.write_all(normalized.as_bytes())?;
+                    log.flush()?;
+                    writeln!(output, "{}", game.status())?;
+                }
+                Err(e) => writeln!(output, "error: {}", e)?,
+            },
+        }
+        output.flush()?;
+    }
+    Ok((game.shape_score, game.outcome_score))
+}
+
+// How long a player may stay silent before their game ends and the next player gets a turn.
+#[cfg(feature = "play")]
+pub const IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+// One player at a time, all sharing the log. A connection failing, whether it is being accepted
+// or in the middle of a game, only ends that game.
+#[cfg(feature = "play")]
+pub fn serve(listener: std::net::TcpListener, mut log: impl std::io::Write) -> Result<(), Error> {
+    for stream in listener.incoming() {
+        let (stream, peer) = match stream.and_then(|stream| stream.peer_addr().map(|peer| (stream, peer))) {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                tracing::warn!("Unable to accept a player: {}", e);
+                continue;
+            }
+        };
+        tracing::info!("{} connected", peer);
+
+        let input = stream
+            .set_read_timeout(Some(IDLE_TIMEOUT))
+            .and_then(|_| stream.try_clone())
+            .map(std::io::BufReader::new);
+        match input.map_err(Error::from).and_then(|input| play(input, &stream, &mut log)) {
+            Ok((shapes, outcomes)) => tracing::info!("{} left with {} with shapes, {} with outcomes", peer, shapes, outcomes),
+            Err(e) => tracing::warn!("{} dropped: {}", peer, e),
+        }
+    }
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[error("Unable to detect the input format from line '{0}'")]
+    UnknownDialect(String),
+    #[error("Invalid token '{0}' for format {1:?}")]
+    InvalidToken(String, Dialect),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ (Error::Nom(..) | Error::UnknownDialect(..) | Error::InvalidToken(..)) => crate::Error::parse(e),
+        }
+    }
+}
+
+// Any dialect goes, the official one being letters.
+pub fn read_rounds(content: &str) -> Result<Vec<Round>, Error> {
+    let (dialect, rounds) = read_with_dialect(content, Round::parse)?;
+    tracing::debug!("{:?} dialect", dialect);
+    Ok(rounds)
+}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<u32, Error> {
+    let content = input.into_input()?;
+    let rounds = read_rounds(&content)?;
+    Ok(rounds.iter().map(Round::score).sum())
+}
+
+fn read_rounds_v2(content: &str) -> Result<Vec<RoundV2>, Error> {
+    let (dialect, rounds) = read_with_dialect(content, RoundV2::parse)?;
+    tracing::debug!("{:?} dialect", dialect);
+    Ok(rounds)
+}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<u32, Error> {
+    let content = input.into_input()?;
+    let rounds = read_rounds_v2(&content)?;
+    Ok(rounds.iter().map(RoundV2::score).sum())
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day2_example.txt"), answers: [Some("15"), Some("12")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_rounds(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    fn details(&self, part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        match part {
+            1 => {
+                let rounds = read_rounds(input)?;
+                let mut details = rounds.iter().map(Round::explain).collect::<Vec<_>>();
+                details.extend(strategy_report(&rounds));
+                Ok(details)
+            }
+            2 => Ok(read_rounds_v2(input)?.iter().map(RoundV2::explain).collect()),
+            _ => Err(crate::Error::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day02::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), anyhow::Error> {
+        let score = run_challenge1(include_str!("data/day2_example.txt"))?;
+        assert_eq!(score, 15);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), anyhow::Error> {
+        let score = run_challenge1(include_str!("data/day2_challenge.txt"))?;
+        println!("{}", score);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), anyhow::Error> {
+        let score = run_challenge2(include_str!("data/day2_example.txt"))?;
+        assert_eq!(score, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), anyhow::Error> {
+        let score = run_challenge2(include_str!("data/day2_challenge.txt"))?;
+        println!("{}", score);
+        Ok(())
+    }
+
+    #[test]
+    fn dialect_detection() -> Result<(), Error> {
+        let (dialect, rounds) = read_with_dialect(include_str!("data/day2_example.txt"), Round::parse)?;
+        assert_eq!(dialect, Dialect::Letters);
+        assert_eq!(rounds.iter().map(Round::score).sum::<u32>(), 15);
+
+        let (dialect, rounds) = read_with_dialect("a y\nb x\nc z\n", Round::parse)?;
+        assert_eq!(dialect, Dialect::LowercaseLetters);
+        assert_eq!(rounds.iter().map(Round::score).sum::<u32>(), 15);
+
+        let (dialect, rounds) = read_with_dialect("Rock Paper\nPaper Rock\nScissors Scissors", Round::parse)?;
+        assert_eq!(dialect, Dialect::Words);
+        assert_eq!(rounds.iter().map(Round::score).sum::<u32>(), 15);
+
+        let (dialect, rounds) = read_with_dialect("rock draw\npaper lose\nscissors win\n", RoundV2::parse)?;
+        assert_eq!(dialect, Dialect::LowercaseWords);
+        assert_eq!(rounds.iter().map(RoundV2::score).sum::<u32>(), 12);
+
+        assert!(matches!(read_with_dialect("Rock paper", Round::parse), Err(Error::InvalidToken(_, Dialect::Words))));
+        assert!(matches!(read_with_dialect("A Y\nrock paper", Round::parse), Err(Error::UnknownDialect(_))));
+
+        assert_eq!(run_challenge1("Rock Paper\nPaper Rock\nScissors Scissors")?, 15);
+        assert_eq!(run_challenge2("rock draw\npaper lose\nscissors win\n")?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn strategy_ranking() -> Result<(), Error> {
+        let rounds = read_rounds(include_str!("data/day2_example.txt"))?;
+        let elf = distribution(&rounds.into_iter().map(|round| round.elf).collect::<Vec<_>>());
+
+        let ranked = rank_strategies(&elf);
+        assert_eq!(ranked[0], (Strategy::Always(Shape::Scissors), 6.0));
+        let scores = ranked.iter().map(|(_, score)| (score * 1000.0).round() / 1000.0).collect::<Vec<_>>();
+        assert_eq!(scores, vec![6.0, 5.0, 5.0, 4.0]);
+        assert_eq!(optimal_strategy(&elf), Strategy::Always(Shape::Scissors));
+
+        let mut elf_shapes = vec![Shape::Rock; 5];
+        elf_shapes.extend(vec![Shape::Scissors; 4]);
+        assert_eq!(optimal_strategy(&distribution(&elf_shapes)), Strategy::Mixed([0.5, 0.5, 0.0]));
+
+        assert_eq!(strategy_report(&read_rounds(include_str!("data/day2_example.txt"))?), [
+            "elf plays mix of Rock 33%, Paper 33%, Scissors 33%",
+            "always Scissors: 6.00",
+            "mix of Rock 33%, Paper 33%, Scissors 33%: 5.00",
+            "always Paper: 5.00",
+            "always Rock: 4.00",
+            "optimal: always Scissors",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn explanations() -> Result<(), Error> {
+        let content = include_str!("data/day2_example.txt");
+        let first = read_rounds(content)?;
+        assert_eq!(first[0].explain(), "You chose Paper vs Rock: win (6) + shape (2) = 8");
+
+        let second = read_rounds_v2(content)?;
+        assert_eq!(second[1].explain(), "You chose Rock vs Paper: loss (0) + shape (1) = 1");
+
+        assert_eq!(Day2.details(2, content).unwrap()[1], second[1].explain());
+        assert!(matches!(Day2.details(3, content), Err(crate::Error::UnknownPart(3))));
+        Ok(())
+    }
+
+    #[test]
+    fn running_game() -> Result<(), Error> {
+        let mut game = Game::default();
+        assert_eq!(game.play("A Y")?, "A Y\n");
+        assert_eq!(game.play("paper rock")?, "B X\n");
+        assert!(matches!(game.play("A"), Err(Error::Nom(_))));
+        assert!(matches!(game.play("D X"), Err(Error::UnknownDialect(_))));
+        assert_eq!(game.play("c z")?, "C Z\n");
+        assert_eq!(game.status(), "round 3: 15 with shapes, 12 with outcomes");
+        Ok(())
+    }
+
+    #[cfg(feature = "play")]
+    #[test]
+    fn played_session() -> Result<(), Error> {
+        let mut output = Vec::new();
+        let mut log = Vec::new();
+        let scores = play("A Y\n\nB Q\nB X\nquit\nC Z\n".as_bytes(), &mut output, &mut log)?;
+
+        assert_eq!(scores, (9, 5));
+        assert_eq!(String::from_utf8(log).unwrap(), "A Y\nB X\n");
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().collect::<Vec<_>>()[0], "round 1: 8 with shapes, 4 with outcomes");
+        assert!(output.lines().nth(1).unwrap().starts_with("error: "));
+        assert_eq!(output.lines().count(), 3);
+        Ok(())
+    }
+
+    #[cfg(feature = "play")]
+    #[test]
+    fn tcp_game() -> Result<(), Error> {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        std::thread::spawn(move || serve(listener, std::io::sink()));
+
+        let mut stream = std::net::TcpStream::connect(address)?;
+        stream.write_all(b"A Y\nB X\n")?;
+        let mut replies = BufReader::new(stream.try_clone()?).lines();
+        assert_eq!(replies.next().transpose()?.as_deref(), Some("round 1: 8 with shapes, 4 with outcomes"));
+        assert_eq!(replies.next().transpose()?.as_deref(), Some("round 2: 9 with shapes, 5 with outcomes"));
+        stream.write_all(b"quit\n")?;
+        assert!(replies.next().is_none());
+
+        // A player leaving without a word does not stop the server.
+        drop(std::net::TcpStream::connect(address)?);
+        let mut stream = std::net::TcpStream::connect(address)?;
+        stream.write_all(b"C Z\nquit\n")?;
+        let mut replies = BufReader::new(stream).lines();
+        assert_eq!(replies.next().transpose()?.as_deref(), Some("round 1: 6 with shapes, 7 with outcomes"));
+        Ok(())
+    }
+}
@@ -0,0 +1,555 @@
+use nom::{
+    Finish,
+    IResult,
+    character::complete,
+    combinator::{all_consuming, map, map_parser, opt},
+    multi::many1,
+    sequence::terminated,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    iter::Chain,
+    slice::Iter,
+};
+use thiserror::Error;
+use crate::utils::{counter::Counter, sections::sections};
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Item {
+    id: char,
+}
+
+impl Item {
+    fn parse(i: &str) -> IResult<&str, Vec<Self>> {
+        map(
+            map_parser(
+                complete::alpha1,
+                many1(complete::anychar),
+            ),
+            |ids| ids.iter().cloned().map(|id| Item { id }).collect(),
+        )(i)
+    }
+
+    fn priority(&self) -> u32 {
+        if self.id.is_lowercase() {
+            (self.id as u8 - b'a') as u32 + 1
+        } else {
+            (self.id as u8 - b'A') as u32 + 27
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Compartment {
+    items: Vec<Item>,
+}
+
+impl Compartment {
+    fn iter(&self) -> Iter<'_, Item> {
+        self.items.iter()
+    }
+
+    fn count(&self, item: &Item) -> usize {
+        self.items.iter().filter(|i| *i == item).count()
+    }
+
+    fn contains(&self, item: &Item) -> bool {
+        self.items.contains(item)
+    }
+
+    fn take_all(&mut self, item: &Item) -> Vec<Item> {
+        let (taken, kept) = self.items.drain(..).partition(|i| i == item);
+        self.items = kept;
+        taken
+    }
+
+    fn take(&mut self, items: &[Item]) -> Vec<Item> {
+        let mut taken = Vec::new();
+        for item in items {
+            if let Some(index) = self.items.iter().position(|i| i == item) {
+                taken.push(self.items.remove(index));
+            }
+        }
+        taken
+    }
+
+    fn put(&mut self, items: Vec<Item>) {
+        self.items.extend(items);
+    }
+
+    // Item types with how many copies of each, sorted by id.
+    fn groups(&self) -> Vec<(Item, usize)> {
+        let mut groups = self.items.iter().cloned().collect::<Counter<_>>().into_iter().collect::<Vec<_>>();
+        groups.sort_unstable_by_key(|(item, _)| item.id);
+        groups
+    }
+}
+
+impl<'a> IntoIterator for &'a Compartment {
+    type Item = &'a Item;
+    type IntoIter = Iter<'a, Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Which {
+    First,
+    Second,
+}
+
+// Move every copy of `duplicate` into `to`, and `swapped` out of `to` to keep both sides the same size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepackPlan {
+    pub rucksack: usize,
+    pub duplicate: Item,
+    pub to: Which,
+    pub swapped: Vec<Item>,
+}
+
+impl fmt::Display for RepackPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let to = match self.to {
+            Which::First => "first",
+            Which::Second => "second",
+        };
+        let swapped = self.swapped.iter().map(|item| item.id).collect::<String>();
+        write!(f, "rucksack {}: {} into the {} compartment, {} out of it", self.rucksack + 1, self.duplicate.id, to, swapped)
+    }
+}
+
+// Equal rucksacks hold the same items in the same order, which makes them usable as cache keys.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Rucksack {
+    first_compartment: Compartment,
+    second_compartment: Compartment,
+}
+
+impl Rucksack {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        map(
+            terminated(
+                Item::parse,
+                opt(complete::line_ending),
+            ),
+            |items| {
+                let (f, s) = items.split_at(items.len() / 2);
+                Rucksack {
+                    first_compartment: Compartment { items: f.to_vec() },
+                    second_compartment: Compartment { items: s.to_vec() },
+                }
+            },
+        )(i)
+    }
+
+    fn common(&self) -> Result<&Item, Error> {
+        let Rucksack { first_compartment, second_compartment } = self;
+        common_element(first_compartment, vec![second_compartment])
+            .map_err(|x| Error::InvalidRuckSack(self.clone(), x))
+    }
+
+    fn elements(&self) -> Chain<Iter<'_, Item>, Iter<'_, Item>> {
+        self.first_compartment.iter().chain(self.second_compartment.iter())
+    }
+
+    fn compartments_mut(&mut self, to: Which) -> (&mut Compartment, &mut Compartment) {
+        match to {
+            Which::First => (&mut self.first_compartment, &mut self.second_compartment),
+            Which::Second => (&mut self.second_compartment, &mut self.first_compartment),
+        }
+    }
+
+    // Tries to gather the duplicate on the side needing the fewest moves first.
+    fn repack_plan(&self, rucksack: usize) -> Option<RepackPlan> {
+        let duplicate = self.common().ok()?.clone();
+
+        let mut directions = [
+            (Which::First, &self.first_compartment, &self.second_compartment),
+            (Which::Second, &self.second_compartment, &self.first_compartment),
+        ];
+        directions.sort_by_key(|(_, _, from)| from.count(&duplicate));
+
+        directions.into_iter().find_map(|(to, receiving, giving)| {
+            let candidates = receiving
+                .groups()
+                .into_iter()
+                .filter(|(item, _)| *item != duplicate && !giving.contains(item))
+                .collect::<Vec<_>>();
+
+            pick_groups(&candidates, giving.count(&duplicate))
+                .map(|swapped| RepackPlan { rucksack, duplicate: duplicate.clone(), to, swapped })
+        })
+    }
+
+    pub fn repack(&mut self, plan: &RepackPlan) {
+        let (receiving, giving) = self.compartments_mut(plan.to);
+        let duplicates = giving.take_all(&plan.duplicate);
+        let swapped = receiving.take(&plan.swapped);
+        receiving.put(duplicates);
+        giving.put(swapped);
+    }
+}
+
+// Whole item groups whose copies add up exactly to the target (a small subset-sum).
+fn pick_groups(groups: &[(Item, usize)], target: usize) -> Option<Vec<Item>> {
+    let mut reachable: Vec<Option<Vec<usize>>> = vec![None; target + 1];
+    reachable[0] = Some(Vec::new());
+
+    for (index, (_, count)) in groups.iter().enumerate() {
+        for sum in (*count..=target).rev() {
+            if reachable[sum].is_none() {
+                if let Some(previous) = &reachable[sum - count] {
+                    let mut picked = previous.clone();
+                    picked.push(index);
+                    reachable[sum] = Some(picked);
+                }
+            }
+        }
+    }
+
+    reachable[target].take().map(|picked|
+        picked
+            .into_iter()
+            .flat_map(|index| {
+                let (item, count) = &groups[index];
+                std::iter::repeat_n(item.clone(), *count)
+            })
+            .collect()
+    )
+}
+
+// One plan for each rucksack with an item in both compartments, when the sizes allow it.
+pub fn repack_plans(rucksacks: &[Rucksack]) -> Vec<RepackPlan> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, rucksack)| rucksack.repack_plan(index))
+        .collect()
+}
+
+fn intersect<'a, I, T>(head: I, tail: Vec<I>) -> HashSet<&'a T>
+    where I: IntoIterator<Item=&'a T>,
+          T: Eq + Hash + 'a {
+    let mut intersection: HashSet<&T> = HashSet::from_iter(head);
+
+    for item in tail {
+        intersection = HashSet::from_iter(item)
+            .intersection(&intersection)
+            .cloned()
+            .collect();
+    }
+
+    intersection
+}
+
+fn common_element<'a, I, T>(head: I, tail: Vec<I>) -> Result<&'a T, CommonElementError<T>>
+    where I: IntoIterator<Item=&'a T>,
+          T: Clone + Eq + Hash + 'a {
+    let common: HashSet<&T> = intersect(head, tail);
+
+    if common.len() > 1 {
+        Err(CommonElementError::TooManyCommonItems(common.into_iter().cloned().collect()))
+    } else {
+        match common.iter().next() {
+            None => Err(CommonElementError::NoCommonItem),
+            Some(item) => Ok(*item)
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CommonElementError<I> {
+    #[error("No common item found")]
+    NoCommonItem,
+    #[error("Too many common items founds {0:?}")]
+    TooManyCommonItems(Vec<I>),
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[error("Invalid rucksack {0:?} - {1}")]
+    InvalidRuckSack(Rucksack, CommonElementError<Item>),
+    #[error("Invalid rucksack group {0:?} - {1}")]
+    InvalidGroup(Vec<Rucksack>, CommonElementError<Item>),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            e @ Error::Nom(..) => crate::Error::parse(e),
+            e @ (Error::InvalidRuckSack(..) | Error::InvalidGroup(..)) => crate::Error::validation(e),
+        }
+    }
+}
+
+pub fn read_input(content: &str) -> Result<Vec<Rucksack>, Error> {
+    let (_, rs) = all_consuming(many1(Rucksack::parse))(content)
+        .map_err(|e| e.to_owned())
+        .finish()?;
+
+    Ok(rs)
+}
+
+// How rucksacks are split into elf groups.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Grouping {
+    // Blank lines when the input has any, otherwise chunks of three.
+    Auto,
+    Chunks(usize),
+    BlankLines,
+}
+
+impl Grouping {
+    fn detect(content: &str) -> Self {
+        if sections(content).nth(1).is_some() {
+            Grouping::BlankLines
+        } else {
+            Grouping::Chunks(3)
+        }
+    }
+}
+
+fn read_groups(content: &str, grouping: Grouping) -> Result<Vec<Vec<Rucksack>>, Error> {
+    match grouping {
+        Grouping::Auto => read_groups(content, Grouping::detect(content)),
+        Grouping::Chunks(size) => Ok(
+            read_input(content)?
+                .chunks_exact(size)
+                .map(<[Rucksack]>::to_vec)
+                .collect()
+        ),
+        Grouping::BlankLines => sections(content).map(read_input).collect(),
+    }
+}
+
+fn group_badge(group: &[Rucksack]) -> Result<&Item, Error> {
+    let (head, tail) = group.split_first().unwrap();
+    common_element(head.elements(), tail.iter().map(Rucksack::elements).collect())
+        .map_err(|x| Error::InvalidGroup(group.to_vec(), x))
+}
+
+fn badges_priority(content: &str, grouping: Grouping) -> Result<u32, Error> {
+    let groups = read_groups(content, grouping)?;
+    let badges = groups
+        .iter()
+        .map(|group| group_badge(group))
+        .collect::<Result<Vec<&Item>, Error>>()?;
+
+    Ok(
+        badges
+            .iter()
+            .cloned()
+            .map(Item::priority)
+            .sum()
+    )
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+// Badge priorities of the groups already seen, keyed by their sorted rucksacks since the order of
+// a group does not change its badge. Invalid groups are not cached.
+#[derive(Debug, Default)]
+pub struct BadgeCache {
+    priorities: HashMap<Vec<Rucksack>, u32>,
+    stats: CacheStats,
+}
+
+impl BadgeCache {
+    pub fn priority(&mut self, group: &[Rucksack]) -> Result<u32, Error> {
+        let mut key = group.to_vec();
+        key.sort_unstable();
+
+        if let Some(priority) = self.priorities.get(&key) {
+            self.stats.hits += 1;
+            return Ok(*priority);
+        }
+
+        self.stats.misses += 1;
+        let priority = group_badge(group)?.priority();
+        self.priorities.insert(key, priority);
+        Ok(priority)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+// Part 2 over many inputs, sharing the badges of groups that show up again.
+pub fn batch_badges_priority(contents: &[&str], grouping: Grouping, cache: &mut BadgeCache) -> Result<Vec<u32>, Error> {
+    contents
+        .iter()
+        .map(|content|
+            read_groups(content, grouping)?
+                .iter()
+                .map(|group| cache.priority(group))
+                .sum()
+        )
+        .collect()
+}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<u32, Error> {
+    let content = input.into_input()?;
+    let rucksacks: Vec<Rucksack> = read_input(&content)?;
+
+    let common = rucksacks
+        .iter()
+        .map(Rucksack::common)
+        .collect::<Result<Vec<&Item>, Error>>()?;
+
+    Ok(
+        common
+            .iter()
+            .cloned()
+            .map(Item::priority)
+            .sum()
+    )
+}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<u32, Error> {
+    let content = input.into_input()?;
+    badges_priority(&content, Grouping::Auto)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day3_example.txt"), answers: [Some("157"), Some("70")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    // How to repack the rucksacks so that no item is in both compartments.
+    fn details(&self, part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        match part {
+            1 => Ok(repack_plans(&read_input(input)?).iter().map(RepackPlan::to_string).collect()),
+            2 => {
+                let mut cache = BadgeCache::default();
+                batch_badges_priority(&[input], Grouping::Auto, &mut cache)?;
+                let CacheStats { hits, misses } = cache.stats();
+                Ok(vec![format!("groups: {}, {} of them already seen", hits + misses, hits)])
+            }
+            _ => Err(crate::Error::UnknownPart(part)),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day03::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let score = run_challenge1(include_str!("data/day3_example.txt"))?;
+        assert_eq!(score, 157);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let score = run_challenge1(include_str!("data/day3_challenge.txt"))?;
+        println!("{}", score);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let score = run_challenge2(include_str!("data/day3_example.txt"))?;
+        assert_eq!(score, 70);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let score = run_challenge2(include_str!("data/day3_challenge.txt"))?;
+        println!("{}", score);
+        Ok(())
+    }
+
+    #[test]
+    fn repacking() -> Result<(), Error> {
+        let mut rucksacks = read_input(include_str!("data/day3_example.txt"))?;
+        let plans = repack_plans(&rucksacks);
+        assert_eq!(plans.len(), rucksacks.len());
+
+        for plan in plans.iter() {
+            let rucksack = &mut rucksacks[plan.rucksack];
+            let size = rucksack.first_compartment.items.len();
+            rucksack.repack(plan);
+
+            assert_eq!(rucksack.first_compartment.items.len(), size);
+            assert_eq!(rucksack.second_compartment.items.len(), size);
+            assert!(matches!(rucksack.common(), Err(Error::InvalidRuckSack(_, CommonElementError::NoCommonItem))));
+        }
+
+        let rucksack = read_input("abcdaefg")?.remove(0);
+        let plan = rucksack.repack_plan(0).unwrap();
+        assert_eq!(plan, RepackPlan { rucksack: 0, duplicate: Item { id: 'a' }, to: Which::First, swapped: vec![Item { id: 'b' }] });
+        assert_eq!(Day3.details(1, "abcdaefg").unwrap(), ["rucksack 1: a into the first compartment, b out of it"]);
+        Ok(())
+    }
+
+    #[test]
+    fn cached_badges() -> Result<(), Error> {
+        let example = include_str!("data/day3_example.txt");
+        let challenge = include_str!("data/day3_challenge.txt");
+        let swapped = example.lines().rev().collect::<Vec<_>>().join("\n");
+
+        let mut cache = BadgeCache::default();
+        let priorities = batch_badges_priority(&[example, challenge, example, &swapped], Grouping::Auto, &mut cache)?;
+        assert_eq!(priorities[0], badges_priority(example, Grouping::Auto)?);
+        assert_eq!(priorities[1], badges_priority(challenge, Grouping::Auto)?);
+        assert_eq!(&priorities[2..], &[70, 70]);
+        assert_eq!(Day3.details(2, &format!("{}\n{}", example.trim_end(), swapped)).unwrap(), ["groups: 4, 2 of them already seen"]);
+
+        let challenge_groups = read_groups(challenge, Grouping::Auto)?.len();
+        assert_eq!(cache.stats(), CacheStats { hits: 4, misses: 2 + challenge_groups });
+
+        assert!(matches!(cache.priority(&read_input("ab\ncd\nef\n")?), Err(Error::InvalidGroup(..))));
+        assert_eq!(cache.stats().misses, 3 + challenge_groups);
+        Ok(())
+    }
+
+    #[test]
+    fn blank_line_groups() -> Result<(), Error> {
+        let content = "abXe\ncdXf\n\nghqZ\nijZr\nklsZ\nZtmn\n";
+        let groups = read_groups(content, Grouping::Auto)?;
+        assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(badges_priority(content, Grouping::BlankLines)?, 50 + 52);
+
+        let example = include_str!("data/day3_example.txt");
+        assert_eq!(Grouping::detect(example), Grouping::Chunks(3));
+        assert!(matches!(read_groups(example, Grouping::BlankLines)?.as_slice(), [group] if group.len() == 6));
+        Ok(())
+    }
+}
@@ -0,0 +1,530 @@
+use nom::{
+    Finish,
+    IResult,
+    character::complete,
+    combinator::{all_consuming, map},
+    multi::separated_list1,
+    sequence::separated_pair,
+};
+use std::fmt;
+use std::ops::RangeInclusive;
+use thiserror::Error;
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+use crate::utils::{
+    range_map::RangeMap,
+    range_num::{self, RangeNum},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Left => write!(f, "left"),
+            Side::Right => write!(f, "right"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reassignment<N = u32> {
+    pub pair: usize,
+    pub side: Side,
+    pub range: RangeInclusive<N>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElfPair<N = u32> {
+    left: RangeInclusive<N>,
+    right: RangeInclusive<N>,
+}
+
+impl<N: RangeNum> ElfPair<N> {
+    fn overlap_fully(&self) -> bool {
+        self.left.contains(self.right.start()) && self.left.contains(self.right.end()) ||
+            self.right.contains(self.left.start()) && self.right.contains(self.left.end())
+    }
+
+    fn overlap_partially(&self) -> bool {
+        self.left.contains(self.right.start()) ||
+            self.left.contains(self.right.end()) ||
+            self.right.contains(self.left.start()) ||
+            self.right.contains(self.left.end())
+    }
+
+    pub fn side(&self, side: Side) -> &RangeInclusive<N> {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
+    pub fn with(&self, side: Side, range: RangeInclusive<N>) -> Self {
+        match side {
+            Side::Left => ElfPair { left: range, right: self.right.clone() },
+            Side::Right => ElfPair { left: self.left.clone(), right: range },
+        }
+    }
+
+    // Smallest shift of a single range (the shorter one on ties) that ends the full overlap.
+    pub fn minimal_reassignment(&self) -> Option<(Side, RangeInclusive<N>)> {
+        if !self.overlap_fully() {
+            return None;
+        }
+
+        let mut sides = [Side::Left, Side::Right];
+        sides.sort_by_key(|side| range_num::width(self.side(*side)));
+
+        sides
+            .into_iter()
+            .flat_map(|side| {
+                let moved = self.side(side);
+                let other = match side {
+                    Side::Left => &self.right,
+                    Side::Right => &self.left,
+                };
+
+                let (moved_start, moved_end) = (moved.start().to_i128(), moved.end().to_i128());
+                let (other_start, other_end) = (other.start().to_i128(), other.end().to_i128());
+                let towards_start = -((moved_start - other_start).max(moved_end - other_end) + 1);
+                let towards_end = (other_start - moved_start).max(other_end - moved_end) + 1;
+
+                [towards_start, towards_end]
+                    .into_iter()
+                    .filter_map(move |delta| range_num::shift(moved, delta).map(|range| (delta.abs(), side, range)))
+            })
+            .min_by_key(|(distance, _, _)| *distance)
+            .map(|(_, side, range)| (side, range))
+    }
+
+    pub fn overlap_length(&self) -> u128 {
+        range_num::width(&range_num::intersection(&self.left, &self.right))
+    }
+
+    pub fn analysis(&self) -> PairAnalysis<N> {
+        PairAnalysis {
+            left: self.left.clone(),
+            right: self.right.clone(),
+            full_overlap: self.overlap_fully(),
+            partial_overlap: self.overlap_partially(),
+            overlap_length: self.overlap_length(),
+        }
+    }
+}
+
+impl ElfPair {
+    fn parse_range(i: &str) -> IResult<&str, RangeInclusive<u32>> {
+        map(
+            separated_pair(complete::u32, complete::char('-'), complete::u32),
+            |(start, end)| start..=end,
+        )(i)
+    }
+
+    fn parse(i: &str) -> IResult<&str, Self> {
+        map(
+            separated_pair(ElfPair::parse_range, complete::char(','), ElfPair::parse_range),
+            |(left, right)| ElfPair { left, right },
+        )(i)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PairAnalysis<N = u32> {
+    pub left: RangeInclusive<N>,
+    pub right: RangeInclusive<N>,
+    pub full_overlap: bool,
+    pub partial_overlap: bool,
+    pub overlap_length: u128,
+}
+
+#[cfg(feature = "serde")]
+pub fn to_jsonl(pairs: &[ElfPair], mut writer: impl std::io::Write) -> Result<(), Error> {
+    for pair in pairs {
+        serde_json::to_writer(&mut writer, &pair.analysis())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+pub fn suggest_reassignments<N: RangeNum>(pairs: &[ElfPair<N>]) -> Vec<Reassignment<N>> {
+    pairs
+        .iter()
+        .enumerate()
+        .filter_map(|(pair, elves)|
+            elves
+                .minimal_reassignment()
+                .map(|(side, range)| Reassignment { pair, side, range })
+        )
+        .collect()
+}
+
+// Number of elves assigned to each section. Intervals are half-open, so the largest section of the
+// type cannot be counted.
+pub fn section_coverage<N: RangeNum>(pairs: &[ElfPair<N>]) -> Result<RangeMap<N, usize>, Error> {
+    let mut coverage = RangeMap::new();
+    for range in pairs.iter().flat_map(|pair| [&pair.left, &pair.right]) {
+        let end = range.end().successor().ok_or_else(|| Error::SectionAtLimit(format!("{:?}", range)))?;
+        coverage.merge(*range.start()..end, 1, |count, one| count + one);
+    }
+    coverage.coalesce();
+    Ok(coverage)
+}
+
+// A single elf's assignment, numbered across the whole input: the left elf of pair p is 2p, the right one 2p + 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Elf<N = u32> {
+    pub id: usize,
+    pub pair: usize,
+    pub side: Side,
+    pub sections: RangeInclusive<N>,
+}
+
+pub fn elves<N: RangeNum>(pairs: &[ElfPair<N>]) -> Vec<Elf<N>> {
+    pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(pair, elves)|
+            [Side::Left, Side::Right]
+                .into_iter()
+                .enumerate()
+                .map(move |(offset, side)| Elf { id: 2 * pair + offset, pair, side, sections: elves.side(side).clone() })
+        )
+        .collect()
+}
+
+// Every two elves sharing at least one section are linked, whether they are in the same pair or not.
+#[derive(Debug)]
+pub struct OverlapGraph<N = u32> {
+    elves: Vec<Elf<N>>,
+    // Lower id first, sorted.
+    edges: Vec<(usize, usize)>,
+}
+
+impl<N: RangeNum> OverlapGraph<N> {
+    // Sweeps the elves by first section, keeping the ones whose assignment is not over yet.
+    pub fn new(pairs: &[ElfPair<N>]) -> Self {
+        let elves = elves(pairs);
+        let mut order = (0..elves.len()).collect::<Vec<_>>();
+        order.sort_by_key(|id| (*elves[*id].sections.start(), *id));
+
+        let mut active: Vec<usize> = Vec::new();
+        let mut edges = Vec::new();
+        for id in order {
+            let start = *elves[id].sections.start();
+            active.retain(|other| *elves[*other].sections.end() >= start);
+            if range_num::width(&elves[id].sections) == 0 {
+                continue;
+            }
+
+            edges.extend(active.iter().map(|other| (id.min(*other), id.max(*other))));
+            active.push(id);
+        }
+        edges.sort_unstable();
+
+        OverlapGraph { elves, edges }
+    }
+
+    // Each component lists its elf ids in order, components come by their lowest id.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        fn root(parents: &mut [usize], id: usize) -> usize {
+            let mut id = id;
+            while parents[id] != id {
+                parents[id] = parents[parents[id]];
+                id = parents[id];
+            }
+            id
+        }
+
+        let mut parents = (0..self.elves.len()).collect::<Vec<_>>();
+        for (left, right) in &self.edges {
+            let (left, right) = (root(&mut parents, *left), root(&mut parents, *right));
+            parents[left.max(right)] = left.min(right);
+        }
+
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        let mut component_of_root: Vec<Option<usize>> = vec![None; self.elves.len()];
+        for id in 0..self.elves.len() {
+            let root = root(&mut parents, id);
+            match component_of_root[root] {
+                Some(component) => components[component].push(id),
+                None => {
+                    component_of_root[root] = Some(components.len());
+                    components.push(vec![id]);
+                }
+            }
+        }
+        components
+    }
+
+    // The elf assigned the most sections, the lowest id on ties.
+    pub fn widest(&self) -> Option<&Elf<N>> {
+        self.elves
+            .iter()
+            .max_by(|left, right|
+                range_num::width(&left.sections).cmp(&range_num::width(&right.sections)).then(right.id.cmp(&left.id))
+            )
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Sections {0} end at the largest value of their type, their coverage cannot be counted")]
+    SectionAtLimit(String),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ Error::Nom(..) => crate::Error::parse(e),
+            #[cfg(feature = "serde")]
+            e @ Error::Json(..) => crate::Error::output(e),
+            e @ Error::SectionAtLimit(..) => crate::Error::validation(e),
+        }
+    }
+}
+
+pub fn read_input(content: &str) -> Result<Vec<ElfPair>, Error> {
+    let (_, elves) = all_consuming(separated_list1(complete::line_ending, ElfPair::parse))(content)
+        .map_err(|e| e.to_owned())
+        .finish()?;
+
+    Ok(elves)
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge1(input: impl IntoInput) -> Result<u32, Error> {
+    let content = input.into_input()?;
+    let pairs: Vec<ElfPair> = read_input(&content)?;
+    tracing::trace!("{:?}", pairs);
+
+    let overlaps: Vec<ElfPair> = pairs.into_iter().filter(ElfPair::overlap_fully).collect();
+    tracing::debug!("{:?}", overlaps);
+
+    Ok(overlaps.len() as u32)
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge2(input: impl IntoInput) -> Result<u32, Error> {
+    let content = input.into_input()?;
+    let pairs: Vec<ElfPair> = read_input(&content)?;
+    tracing::trace!("{:?}", pairs);
+
+    let overlaps: Vec<ElfPair> = pairs.into_iter().filter(ElfPair::overlap_partially).collect();
+    tracing::debug!("{:?}", overlaps);
+
+    Ok(overlaps.len() as u32)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day4_example.txt"), answers: [Some("2"), Some("4")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    fn details(&self, part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        let pairs = read_input(input)?;
+        let coverage = section_coverage(&pairs)?;
+        let mut details = Vec::new();
+        if let Some(most) = coverage.iter().map(|(_, elves)| *elves).max() {
+            let busiest = coverage
+                .iter()
+                .filter(|(_, elves)| **elves == most)
+                .map(|(sections, _)| format!("{}-{}", sections.start, sections.end - 1))
+                .collect::<Vec<_>>();
+            details.push(format!("Most elves on a section: {}, on {}", most, busiest.join(", ")));
+        }
+        if part == 1 {
+            details.extend(suggest_reassignments(&pairs).into_iter().map(|Reassignment { pair, side, range }|
+                format!("line {}: moving the {} elf to {}-{} ends the full overlap", pair + 1, side, range.start(), range.end())
+            ));
+        } else {
+            let graph = OverlapGraph::new(&pairs);
+            let components = graph.components();
+            let largest = components.iter().map(Vec::len).max().unwrap_or_default();
+            details.push(format!("Groups of elves sharing sections: {}, the largest with {} elves", components.len(), largest));
+            if let Some(Elf { id, pair, side, sections }) = graph.widest() {
+                details.push(format!(
+                    "Most sections: elf {}, the {} one of line {}, on {}-{}",
+                    id, side, pair + 1, sections.start(), sections.end(),
+                ));
+            }
+        }
+        Ok(details)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day04::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let score = run_challenge1(include_str!("data/day4_example.txt"))?;
+        assert_eq!(score, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let score = run_challenge1(include_str!("data/day4_challenge.txt"))?;
+        println!("{}", score);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let score = run_challenge2(include_str!("data/day4_example.txt"))?;
+        assert_eq!(score, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let score = run_challenge2(include_str!("data/day4_challenge.txt"))?;
+        println!("{}", score);
+        Ok(())
+    }
+
+    #[test]
+    fn section_coverage_example() -> Result<(), Error> {
+        let pairs = read_input(include_str!("data/day4_example.txt"))?;
+        let coverage = section_coverage(&pairs)?;
+
+        assert_eq!(coverage.get(&1), None);
+        assert_eq!(coverage.get(&2), Some(&4));
+        assert_eq!(coverage.get(&6), Some(&8));
+        assert_eq!(coverage.get(&9), Some(&1));
+        assert_eq!(coverage.get(&10), None);
+
+        assert_eq!(Day4.details(2, include_str!("data/day4_example.txt")).unwrap()[0], "Most elves on a section: 8, on 6-6");
+
+        let pairs = read_input("1-4294967295,2-3")?;
+        assert!(matches!(section_coverage(&pairs), Err(Error::SectionAtLimit(_))));
+        assert!(matches!(section_coverage(&pairs).map_err(crate::Error::from), Err(crate::Error::Validation(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn reassignment_suggestions() -> Result<(), Error> {
+        let mut pairs = read_input(include_str!("data/day4_example.txt"))?;
+        let suggestions = suggest_reassignments(&pairs);
+
+        assert_eq!(
+            suggestions,
+            vec![
+                Reassignment { pair: 3, side: Side::Right, range: 1..=5 },
+                Reassignment { pair: 4, side: Side::Left, range: 7..=7 },
+            ]
+        );
+
+        for Reassignment { pair, side, range } in suggestions {
+            pairs[pair] = pairs[pair].with(side, range);
+        }
+        assert!(pairs.iter().all(|pair| !pair.overlap_fully()));
+        assert_eq!(Day4.details(1, include_str!("data/day4_example.txt")).unwrap()[1..], [
+            "line 4: moving the right elf to 1-5 ends the full overlap",
+            "line 5: moving the left elf to 7-7 ends the full overlap",
+        ]);
+
+        let pair = ElfPair::<u32> { left: 0..=3, right: 0..=3 };
+        assert_eq!(pair.minimal_reassignment(), Some((Side::Left, 1..=4)));
+        Ok(())
+    }
+
+    #[test]
+    fn pair_analysis() -> Result<(), Error> {
+        let pairs = read_input(include_str!("data/day4_example.txt"))?;
+        let lengths = pairs.iter().map(|pair| pair.analysis().overlap_length).collect::<Vec<_>>();
+        assert_eq!(lengths, vec![0, 0, 1, 5, 1, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn signed_sections() {
+        let pair = ElfPair::<i64> { left: -5..=3, right: 2..=4_000_000_000 };
+        assert_eq!(pair.overlap_length(), 2);
+        assert!(pair.overlap_partially());
+        assert!(!pair.overlap_fully());
+
+        let coverage = section_coverage(&[pair]).unwrap();
+        assert_eq!(coverage.get(&-5), Some(&1));
+        assert_eq!(coverage.get(&3), Some(&2));
+        assert_eq!(coverage.get(&3_999_999_999), Some(&1));
+    }
+
+    #[test]
+    fn overlap_graph() -> Result<(), Error> {
+        let graph = OverlapGraph::new(&read_input(include_str!("data/day4_example.txt"))?);
+        assert_eq!(graph.components(), vec![(0..12).collect::<Vec<_>>()]);
+        assert_eq!(graph.widest(), Some(&Elf { id: 6, pair: 3, side: Side::Left, sections: 2..=8 }));
+        assert!(!graph.edges.contains(&(0, 1)));
+        assert!(graph.edges.contains(&(1, 5)));
+
+        let graph = OverlapGraph::new(&read_input("1-2,3-4\n10-12,11-11\n4-4,9-8")?);
+        assert_eq!(graph.components(), vec![vec![0], vec![1, 4], vec![2, 3], vec![5]]);
+        assert_eq!(graph.widest().map(|elf| elf.id), Some(2));
+        assert_eq!(Day4.details(2, "1-2,3-4\n10-12,11-11\n4-4,9-8").unwrap()[1..], [
+            "Groups of elves sharing sections: 4, the largest with 2 elves",
+            "Most sections: elf 2, the left one of line 2, on 10-12",
+        ]);
+
+        let pairs = read_input(include_str!("data/day4_challenge.txt"))?;
+        let elves = elves(&pairs);
+        let brute_force = (0..elves.len())
+            .flat_map(|left| (left + 1..elves.len()).map(move |right| (left, right)))
+            .filter(|(left, right)| range_num::width(&range_num::intersection(&elves[*left].sections, &elves[*right].sections)) > 0)
+            .collect::<Vec<_>>();
+        assert_eq!(OverlapGraph::new(&pairs).edges, brute_force);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn jsonl_output() -> Result<(), Error> {
+        let pairs = read_input(include_str!("data/day4_example.txt"))?;
+        let mut output = Vec::new();
+        to_jsonl(&pairs, &mut output)?;
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 6);
+        assert_eq!(
+            output.lines().nth(3),
+            Some(r#"{"left":{"start":2,"end":8},"right":{"start":3,"end":7},"full_overlap":true,"partial_overlap":true,"overlap_length":5}"#)
+        );
+        Ok(())
+    }
+}
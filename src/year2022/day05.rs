@@ -0,0 +1,1200 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::{Debug, Display, Formatter, Write},
+    rc::Rc,
+    str::{self, FromStr},
+};
+use thiserror::Error;
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+
+// Storage of a single stack, so that backends can be compared. Crates are indexed from the bottom.
+pub trait StackStore: Clone + Default {
+    fn len(&self) -> usize;
+    fn crate_at(&self, index: usize) -> Option<char>;
+    // The top `count` crates, bottom-most first.
+    fn take_top(&mut self, count: usize) -> Vec<char>;
+    fn put_on_top(&mut self, crates: impl IntoIterator<Item=char>);
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn top_crate(&self) -> Option<char> {
+        self.len().checked_sub(1).and_then(|index| self.crate_at(index))
+    }
+
+    fn crates(&self) -> Vec<char> {
+        (0..self.len()).filter_map(|index| self.crate_at(index)).collect()
+    }
+}
+
+impl StackStore for Vec<char> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn crate_at(&self, index: usize) -> Option<char> {
+        self.as_slice().get(index).copied()
+    }
+
+    fn take_top(&mut self, count: usize) -> Vec<char> {
+        self.split_off(Vec::len(self) - count)
+    }
+
+    fn put_on_top(&mut self, crates: impl IntoIterator<Item=char>) {
+        self.extend(crates)
+    }
+}
+
+impl StackStore for VecDeque<char> {
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn crate_at(&self, index: usize) -> Option<char> {
+        VecDeque::get(self, index).copied()
+    }
+
+    fn take_top(&mut self, count: usize) -> Vec<char> {
+        self.split_off(VecDeque::len(self) - count).into()
+    }
+
+    fn put_on_top(&mut self, crates: impl IntoIterator<Item=char>) {
+        self.extend(crates)
+    }
+}
+
+#[derive(Debug, Eq, Hash, PartialEq)]
+struct PersistentNode {
+    value: char,
+    below: Option<Rc<PersistentNode>>,
+}
+
+// Singly linked from the top, so clones share every crate and cost nothing until modified.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PersistentStack {
+    top: Option<Rc<PersistentNode>>,
+    len: usize,
+}
+
+impl PersistentStack {
+    fn nodes(&self) -> impl Iterator<Item=&PersistentNode> {
+        std::iter::successors(self.top.as_deref(), |node| node.below.as_deref())
+    }
+}
+
+// Dropping the nodes one by one, as the derived drop recurses once per crate and overflows the
+// stack of tall piles. Nodes still shared with a clone stop the walk.
+impl Drop for PersistentStack {
+    fn drop(&mut self) {
+        let mut top = self.top.take();
+        while let Some(node) = top {
+            top = Rc::try_unwrap(node).ok().and_then(|mut node| node.below.take());
+        }
+    }
+}
+
+impl StackStore for PersistentStack {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    // Walks down from the top, so this is linear in the depth of the crate: callers going through
+    // a whole stack should prefer `crates`, or a `Vec` store.
+    fn crate_at(&self, index: usize) -> Option<char> {
+        let depth = self.len.checked_sub(index + 1)?;
+        self.nodes().nth(depth).map(|node| node.value)
+    }
+
+    fn crates(&self) -> Vec<char> {
+        let mut crates: Vec<char> = self.nodes().map(|node| node.value).collect();
+        crates.reverse();
+        crates
+    }
+
+    fn take_top(&mut self, count: usize) -> Vec<char> {
+        let mut crates = Vec::with_capacity(count);
+        for _ in 0..count {
+            let node = self.top.take().unwrap();
+            crates.push(node.value);
+            self.top = node.below.clone();
+        }
+        self.len -= count;
+
+        crates.reverse();
+        crates
+    }
+
+    fn put_on_top(&mut self, crates: impl IntoIterator<Item=char>) {
+        for value in crates {
+            self.top = Some(Rc::new(PersistentNode { value, below: self.top.take() }));
+            self.len += 1;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Stacks<S = Vec<char>> {
+    stacks: Vec<S>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum CraneModel {
+    CrateMover9000,
+    CrateMover9001,
+}
+
+impl FromStr for CraneModel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "9000" => Ok(CraneModel::CrateMover9000),
+            "9001" => Ok(CraneModel::CrateMover9001),
+            other => Err(Error::InvalidCrane(other.to_string())),
+        }
+    }
+}
+
+impl<S: StackStore> Display for Stacks<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(max_height) = self.stacks.iter().map(S::len).max() {
+            for i in (0..max_height).rev() {
+                let crates_at_index = self.stacks.iter().map(|stack| stack.crate_at(i));
+                for (index, maybe_crate) in crates_at_index.enumerate() {
+                    match maybe_crate {
+                        None => f.write_str("   ")?,
+                        Some(c) => f.write_fmt(format_args!("[{}]", c))?
+                    };
+
+                    if index != self.stacks.len() - 1 {
+                        f.write_str(" ")?;
+                    }
+                }
+
+                f.write_str("\n")?;
+            }
+
+            for i in 0..self.stacks.len() {
+                f.write_fmt(format_args!(" {}  ", i + 1))?;
+            }
+
+            Ok(())
+        } else {
+            f.write_str("<empty>\n")
+        }
+    }
+}
+
+impl<S: StackStore> Stacks<S> {
+    pub fn new() -> Self {
+        Stacks {
+            stacks: Vec::new(),
+        }
+    }
+
+    pub fn with_store<T: StackStore>(&self) -> Stacks<T> {
+        Stacks {
+            stacks: self.stacks
+                .iter()
+                .map(|stack| {
+                    let mut store = T::default();
+                    store.put_on_top(stack.crates());
+                    store
+                })
+                .collect(),
+        }
+    }
+
+    pub fn apply(&mut self, action: &CraneAction, model: CraneModel) -> Result<(), Error> {
+        match *action {
+            CraneAction::Move { number_crates, from_stack, to_stack, .. } => {
+                if from_stack == 0 || from_stack > self.stacks.len() {
+                    Err(Error::InvalidStackReference(from_stack, action.clone()))
+                } else if to_stack == 0 || to_stack > self.stacks.len() {
+                    Err(Error::InvalidStackReference(to_stack, action.clone()))
+                } else if self.stacks[from_stack - 1].len() < number_crates {
+                    Err(Error::ImpossibleToApplyAction(self.stacks[from_stack - 1].crates(), action.clone()))
+                } else {
+                    #[cfg(feature = "paranoid")]
+                    let crates = self.crate_counts();
+
+                    let mut moved = self.stacks[from_stack - 1].take_top(number_crates);
+
+                    if let CraneModel::CrateMover9000 = model {
+                        moved.reverse();
+                    }
+
+                    self.stacks[to_stack - 1].put_on_top(moved);
+                    paranoid_assert!(self.crate_counts() == crates, "crates lost or duplicated applying {}", action);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    pub fn top(&self) -> String {
+        self.stacks
+            .iter()
+            .flat_map(S::top_crate)
+            .collect()
+    }
+
+    // How many crates of each letter, which no crane action ever changes.
+    #[cfg(any(test, feature = "paranoid"))]
+    fn crate_counts(&self) -> crate::utils::counter::Counter<char> {
+        self.stacks.iter().flat_map(S::crates).collect()
+    }
+}
+
+impl Stacks {
+    fn accept(&self, action: &CraneAction) -> Result<Self, Error> {
+        match *action {
+            CraneAction::Move { number_crates, from_stack, to_stack, .. } => {
+                if from_stack == 0 || from_stack > self.stacks.len() {
+                    Err(Error::InvalidStackReference(from_stack, action.clone()))
+                } else if to_stack == 0 || to_stack > self.stacks.len() {
+                    Err(Error::InvalidStackReference(to_stack, action.clone()))
+                } else if self.stacks[from_stack - 1].len() < number_crates {
+                    Err(Error::ImpossibleToApplyAction(self.stacks[from_stack - 1].clone(), action.clone()))
+                } else {
+                    let mut stacks = self.stacks.clone();
+
+                    let from_index = stacks[from_stack - 1].len() - number_crates;
+                    let to_index = stacks[from_stack - 1].len();
+
+                    // Add to
+                    let to_move: Vec<char> = stacks[from_stack - 1].as_slice()[from_index..to_index].to_vec();
+                    for item in to_move.iter().rev() {
+                        stacks[to_stack - 1].push(*item);
+                    }
+
+                    // Remove in the from stack
+                    stacks[from_stack - 1].splice(from_index..to_index, vec![]);
+
+                    Ok(Stacks { stacks })
+                }
+            }
+        }
+    }
+
+    fn accept_v2(&self, action: &CraneAction) -> Result<Self, Error> {
+        match *action {
+            CraneAction::Move { number_crates, from_stack, to_stack, .. } => {
+                if from_stack == 0 || from_stack > self.stacks.len() {
+                    Err(Error::InvalidStackReference(from_stack, action.clone()))
+                } else if to_stack == 0 || to_stack > self.stacks.len() {
+                    Err(Error::InvalidStackReference(to_stack, action.clone()))
+                } else if self.stacks[from_stack - 1].len() < number_crates {
+                    Err(Error::ImpossibleToApplyAction(self.stacks[from_stack - 1].clone(), action.clone()))
+                } else {
+                    let mut stacks = self.stacks.clone();
+
+                    let from_index = stacks[from_stack - 1].len() - number_crates;
+                    let to_index = stacks[from_stack - 1].len();
+
+                    // Add to
+                    let to_move: Vec<char> = stacks[from_stack - 1].as_slice()[from_index..to_index].to_vec();
+                    for item in to_move.iter() {
+                        stacks[to_stack - 1].push(*item);
+                    }
+
+                    // Remove in the from stack
+                    stacks[from_stack - 1].splice(from_index..to_index, vec![]);
+
+                    Ok(Stacks { stacks })
+                }
+            }
+        }
+    }
+
+    fn possible_actions(&self) -> impl Iterator<Item=CraneAction> + '_ {
+        let stacks_number = self.stacks.len();
+        (0..stacks_number).flat_map(move |from| {
+            (0..stacks_number)
+                .filter(move |to| *to != from)
+                .flat_map(move |to| {
+                    (1..=self.stacks[from].len()).map(move |number_crates| CraneAction::Move {
+                        number_crates,
+                        from_stack: from + 1,
+                        to_stack: to + 1,
+                        line: None,
+                    })
+                })
+        })
+    }
+}
+
+// Breadth-first search over crane actions, so the first plan found is one of the shortest.
+pub fn plan_actions(stacks: &Stacks, target: &str, model: CraneModel, max_actions: usize) -> Result<Vec<CraneAction>, Error> {
+    let mut seen: HashSet<Stacks> = HashSet::new();
+    let mut queue: VecDeque<(Stacks, Vec<CraneAction>)> = VecDeque::new();
+
+    seen.insert(stacks.clone());
+    queue.push_back((stacks.clone(), Vec::new()));
+
+    while let Some((current, actions)) = queue.pop_front() {
+        if current.top() == target {
+            return Ok(actions);
+        }
+
+        if actions.len() == max_actions {
+            continue;
+        }
+
+        for action in current.possible_actions() {
+            let mut next = current.clone();
+            next.apply(&action, model)?;
+
+            if seen.insert(next.clone()) {
+                let mut next_actions = actions.clone();
+                next_actions.push(action);
+                queue.push_back((next, next_actions));
+            }
+        }
+    }
+
+    Err(Error::NoPlanFound(target.to_string(), max_actions))
+}
+
+// A puzzle input for teaching: the stacks drawn in the given input, then a shortest list of actions
+// bringing the target crates on top. The input's own actions are ignored.
+pub fn worked_example(input: impl IntoInput, target: &str, model: CraneModel, max_actions: usize) -> Result<String, Error> {
+    let (stacks, _) = read_input(&input.into_input()?)?;
+    let actions = plan_actions(&stacks, target, model, max_actions)?;
+
+    let mut example = format!("{}\n\n", stacks);
+    for action in actions {
+        writeln!(example, "{}", action).unwrap();
+    }
+    Ok(example)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorPolicy {
+    StopOnError,
+    // Failing actions are skipped, leaving the stacks as they were.
+    CollectErrors,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SimulationOptions {
+    pub model: CraneModel,
+    pub max_steps: Option<usize>,
+    // Only validates actions against the evolving stacks, without keeping each intermediate state.
+    pub dry_run: bool,
+    pub on_error: ErrorPolicy,
+}
+
+impl SimulationOptions {
+    pub fn new(model: CraneModel) -> Self {
+        SimulationOptions { model, max_steps: None, dry_run: false, on_error: ErrorPolicy::StopOnError }
+    }
+}
+
+#[derive(Debug)]
+pub struct SimulationReport {
+    // Actions attempted, whether they succeeded or not.
+    pub steps: usize,
+    pub truncated: bool,
+    pub errors: Vec<Error>,
+    // Stacks after each attempted action, empty on dry runs.
+    pub history: Vec<Stacks>,
+    pub stacks: Stacks,
+}
+
+impl SimulationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+pub fn simulate_with_options(stacks: &Stacks, actions: &[CraneAction], options: SimulationOptions) -> SimulationReport {
+    let mut report = SimulationReport {
+        steps: 0,
+        truncated: false,
+        errors: Vec::new(),
+        history: Vec::new(),
+        stacks: stacks.clone(),
+    };
+
+    for action in actions {
+        if options.max_steps.is_some_and(|max_steps| report.steps == max_steps) {
+            report.truncated = true;
+            break;
+        }
+
+        report.steps += 1;
+        if let Err(e) = report.stacks.apply(action, options.model) {
+            report.errors.push(e);
+            if options.on_error == ErrorPolicy::StopOnError {
+                break;
+            }
+        }
+
+        if !options.dry_run {
+            report.history.push(report.stacks.clone());
+        }
+    }
+
+    report
+}
+
+impl TryFrom<Vec<StackLine>> for Stacks {
+    type Error = Error;
+
+    fn try_from(lines: Vec<StackLine>) -> Result<Self, Self::Error> {
+        if lines.is_empty() {
+            Ok(Stacks::new())
+        } else {
+            let stacks_number = lines[0].crates.len();
+
+            if lines.iter().all(|line| line.crates.len() == stacks_number) {
+                let mut stacks: Vec<Vec<char>> = Vec::with_capacity(stacks_number);
+                for _ in 0..stacks_number {
+                    stacks.push(Vec::new());
+                }
+
+                for line in lines {
+                    for (index, c) in line.crates.iter().enumerate() {
+                        if let Some(c) = c {
+                            stacks[index].insert(0, *c);
+                        }
+                    }
+                }
+
+                Ok(Stacks { stacks })
+            } else {
+                Err(Error::InvalidStacks(lines))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StackLine {
+    crates: Vec<Option<char>>,
+}
+
+impl FromStr for StackLine {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(
+            StackLine {
+                crates: s
+                    .as_bytes()
+                    .chunks(4)
+                    .map(|c| {
+                        if c.iter().all(|x| *x == b' ') {
+                            Ok(None)
+                        } else if c.len() >= 3 && c[0] == b'[' && c[2] == b']' {
+                            Ok(Some(c[1] as char))
+                        } else {
+                            Err(
+                                Error::InvalidCrate(
+                                    str::from_utf8(c).unwrap().to_string()
+                                )
+                            )
+                        }
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CraneAction {
+    Move {
+        number_crates: usize,
+        from_stack: usize,
+        to_stack: usize,
+        line: Option<usize>,
+    }
+}
+
+impl CraneAction {
+    fn at_line(self, line: usize) -> Self {
+        match self {
+            CraneAction::Move { number_crates, from_stack, to_stack, .. } =>
+                CraneAction::Move { number_crates, from_stack, to_stack, line: Some(line) }
+        }
+    }
+}
+
+impl Display for CraneAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CraneAction::Move { number_crates, from_stack, to_stack, line } => {
+                write!(f, "move {} from {} to {}", number_crates, from_stack, to_stack)?;
+                if let Some(line) = line {
+                    write!(f, " (line {})", line)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for CraneAction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref MOVE_RE: Regex = Regex::new("move (\\d+) from (\\d+) to (\\d+)").unwrap();
+        }
+
+        if let Some(capture) = MOVE_RE.captures(s) {
+            if capture.len() == 4 {
+                Ok(
+                    CraneAction::Move {
+                        number_crates: capture[1].parse()?,
+                        from_stack: capture[2].parse()?,
+                        to_stack: capture[3].parse()?,
+                        line: None,
+                    }
+                )
+            } else {
+                Err(
+                    Error::InvalidMove(s.to_string())
+                )
+            }
+        } else {
+            Err(
+                Error::InvalidMove(s.to_string())
+            )
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid crate '{0}'")]
+    InvalidCrate(String),
+    #[error("Invalid stacks '{0:?}'")]
+    InvalidStacks(Vec<StackLine>),
+    #[error("Invalid move '{0:?}'")]
+    InvalidMove(String),
+    #[error(transparent)]
+    ParseInt(#[from] std::num::ParseIntError),
+    #[error("Invalid stack '{0}' referenced in action '{1}'")]
+    InvalidStackReference(usize, CraneAction),
+    #[error("Impossible to apply action '{1}' on stack '{0:?}'")]
+    ImpossibleToApplyAction(Vec<char>, CraneAction),
+    #[error("No plan reaching '{0}' in at most {1} actions")]
+    NoPlanFound(String, usize),
+    #[error("Line {0}: {1}")]
+    InvalidLine(usize, Box<Error>),
+    #[error("Unknown crane model '{0}', expected 9000 or 9001")]
+    InvalidCrane(String),
+    #[error("Invalid scenario step '{0}', expected a move or a checkpoint")]
+    InvalidStep(String),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ (Error::InvalidCrate(..) | Error::InvalidStacks(..) | Error::InvalidMove(..) | Error::ParseInt(..) | Error::InvalidLine(..)) => crate::Error::parse(e),
+            e @ (Error::InvalidCrane(..) | Error::InvalidStep(..)) => crate::Error::parse(e),
+            e @ (Error::InvalidStackReference(..) | Error::ImpossibleToApplyAction(..)) => crate::Error::validation(e),
+            e @ Error::NoPlanFound(..) => crate::Error::solve(e),
+        }
+    }
+}
+
+enum ReadAction {
+    ReadStackLines,
+    Skip(u16, Box<ReadAction>),
+    ReadCraneActions,
+}
+
+pub fn read_input(content: &str) -> Result<(Stacks, Vec<CraneAction>), Error> {
+    let mut stack_lines: Vec<StackLine> = Vec::new();
+    let mut actions: Vec<CraneAction> = Vec::new();
+
+    let mut read = ReadAction::ReadStackLines;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let invalid_line = |e| Error::InvalidLine(line_number, Box::new(e));
+
+        match read {
+            ReadAction::ReadStackLines => {
+                if line.starts_with('[') || line.starts_with("    ") {
+                    stack_lines.push(line.parse().map_err(invalid_line)?);
+                } else {
+                    read = ReadAction::Skip(1_u16, Box::new(ReadAction::ReadCraneActions));
+                }
+            }
+
+            ReadAction::Skip(lines, next) => {
+                if lines == 1 {
+                    read = *next;
+                } else {
+                    read = ReadAction::Skip(lines - 1, next);
+                }
+            }
+
+            ReadAction::ReadCraneActions => {
+                let action: CraneAction = line.parse().map_err(invalid_line)?;
+                actions.push(action.at_line(line_number));
+            }
+        }
+    }
+
+    Ok(
+        (
+            Stacks::try_from(stack_lines)?,
+            actions
+        )
+    )
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge1(input: impl IntoInput) -> Result<String, Error> {
+    let content = input.into_input()?;
+    let (mut stacks, actions) = read_input(&content)?;
+
+    tracing::debug!("Initial state:\n{}", stacks);
+
+    for (index, action) in actions.iter().enumerate() {
+        stacks = stacks.accept(action)?;
+        tracing::trace!("Step {} - {}:\n{}", index + 1, action, stacks);
+    };
+
+    Ok(
+        stacks.stacks
+            .iter()
+            .flat_map(|x| x.last())
+            .cloned()
+            .collect()
+    )
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge2(input: impl IntoInput) -> Result<String, Error> {
+    let content = input.into_input()?;
+    let (mut stacks, actions) = read_input(&content)?;
+
+    tracing::debug!("Initial state:\n{}", stacks);
+
+    for (index, action) in actions.iter().enumerate() {
+        stacks = stacks.accept_v2(action)?;
+        tracing::trace!("Step {} - {}:\n{}", index + 1, action, stacks);
+    };
+
+    Ok(
+        stacks.stacks
+            .iter()
+            .flat_map(|x| x.last())
+            .cloned()
+            .collect()
+    )
+}
+
+// A crane puzzle of one's own, checked as it runs:
+//
+//     crane 9001
+//
+//         [D]
+//     [N] [C]
+//     [Z] [M] [P]
+//      1   2   3
+//
+//     move 1 from 2 to 1
+//     checkpoint after the first move: DCP
+//
+// The crane line is optional and defaults to the CrateMover 9000, `#` starts a comment line, and
+// each checkpoint gives the top crates expected at that point.
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    model: CraneModel,
+    stacks: Stacks,
+    steps: Vec<ScenarioStep>,
+}
+
+#[derive(Clone, Debug)]
+enum ScenarioStep {
+    Action(CraneAction),
+    Checkpoint { label: String, expected: String, line: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckpointResult {
+    pub label: String,
+    pub line: usize,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl CheckpointResult {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+impl Display for CheckpointResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.passed() {
+            write!(f, "ok   {} (line {}): {}", self.label, self.line, self.actual)
+        } else {
+            write!(f, "FAIL {} (line {}): expected {}, got {}", self.label, self.line, self.expected, self.actual)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScenarioReport {
+    pub checkpoints: Vec<CheckpointResult>,
+    pub stacks: Stacks,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.checkpoints.iter().all(CheckpointResult::passed)
+    }
+}
+
+impl Scenario {
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let mut model = CraneModel::CrateMover9000;
+        let mut drawing = Vec::new();
+        let mut steps = Vec::new();
+
+        let mut lines = content.lines().enumerate().map(|(index, line)| (index + 1, line)).peekable();
+        let skipped = |line: &str| line.trim().is_empty() || line.trim_start().starts_with('#');
+
+        while let Some((number, line)) = lines.next_if(|(_, line)| skipped(line) || line.starts_with("crane ")) {
+            if let Some(crane) = line.strip_prefix("crane ") {
+                model = crane.trim().parse().map_err(|e| Error::InvalidLine(number, Box::new(e)))?;
+            }
+        }
+        while let Some(line) = lines.next_if(|(_, line)| line.starts_with('[') || line.starts_with("    ")) {
+            drawing.push(line);
+        }
+        // Unlike puzzle inputs, hand-written drawings rarely keep the trailing spaces of short stacks.
+        let width = drawing.iter().map(|(_, line)| line.len()).max().unwrap_or_default();
+        let stack_lines = drawing
+            .into_iter()
+            .map(|(number, line)| format!("{:<width$}", line).parse().map_err(|e| Error::InvalidLine(number, Box::new(e))))
+            .collect::<Result<Vec<StackLine>, _>>()?;
+        // The stack numbers under the drawing.
+        lines.next_if(|(_, line)| line.trim_start().starts_with(|c: char| c.is_ascii_digit()));
+
+        for (number, line) in lines.filter(|(_, line)| !skipped(line)) {
+            let step = if let Some(checkpoint) = line.strip_prefix("checkpoint ") {
+                let (label, expected) = checkpoint
+                    .split_once(':')
+                    .ok_or_else(|| Error::InvalidLine(number, Box::new(Error::InvalidStep(line.to_string()))))?;
+                ScenarioStep::Checkpoint { label: label.trim().to_string(), expected: expected.trim().to_string(), line: number }
+            } else if line.starts_with("move ") {
+                let action: CraneAction = line.parse().map_err(|e| Error::InvalidLine(number, Box::new(e)))?;
+                ScenarioStep::Action(action.at_line(number))
+            } else {
+                return Err(Error::InvalidLine(number, Box::new(Error::InvalidStep(line.to_string()))));
+            };
+            steps.push(step);
+        }
+
+        Ok(Scenario { model, stacks: Stacks::try_from(stack_lines)?, steps })
+    }
+
+    // Stops at the first action the stacks cannot take, checkpoints keep running after a failed one.
+    pub fn run(&self) -> Result<ScenarioReport, Error> {
+        let mut stacks = self.stacks.clone();
+        let mut checkpoints = Vec::new();
+        for step in &self.steps {
+            match step {
+                ScenarioStep::Action(action) => stacks.apply(action, self.model)?,
+                ScenarioStep::Checkpoint { label, expected, line } => {
+                    let actual = stacks.top();
+                    tracing::debug!("Checkpoint {} (line {}):\n{}", label, line, stacks);
+                    checkpoints.push(CheckpointResult { label: label.clone(), line: *line, expected: expected.clone(), actual });
+                }
+            }
+        }
+        Ok(ScenarioReport { checkpoints, stacks })
+    }
+}
+
+pub fn run_scenario(input: impl IntoInput) -> Result<ScenarioReport, Error> {
+    Scenario::parse(&input.into_input()?)?.run()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day5_example.txt"), answers: [Some("CMZ"), Some("MCD")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    fn variants(&self, part: u8) -> &'static [&'static str] {
+        match part {
+            2 => &["streaming"],
+            _ => &[],
+        }
+    }
+
+    fn variant(&self, part: u8, name: &str, input: &str) -> Result<Answer, crate::Error> {
+        match (part, name) {
+            (2, "streaming") => Ok(run_challenge2_streaming(input)?.into()),
+            _ => Err(crate::Error::UnknownVariant(part, name.to_string())),
+        }
+    }
+}
+
+// Only tracks stack heights going forward, then follows each final top crate back through the
+// actions to its starting position, so no crate is ever copied.
+pub fn top_9001_streaming(stacks: &Stacks, actions: &[CraneAction]) -> Result<String, Error> {
+    let mut heights = stacks.stacks.iter().map(Vec::len).collect::<Vec<_>>();
+
+    for (index, action) in actions.iter().enumerate() {
+        let CraneAction::Move { number_crates, from_stack, to_stack, .. } = *action;
+        let valid = (1..=heights.len()).contains(&from_stack) &&
+            (1..=heights.len()).contains(&to_stack) &&
+            heights[from_stack - 1] >= number_crates;
+
+        if !valid {
+            // Replay up to this action to report exactly the error the simulation would.
+            let mut replayed = stacks.clone();
+            for action in &actions[..=index] {
+                replayed.apply(action, CraneModel::CrateMover9001)?;
+            }
+
+            // Only reached if the heights and the simulation disagree.
+            let crates = replayed.stacks.get(from_stack.wrapping_sub(1)).cloned().unwrap_or_default();
+            return Err(Error::ImpossibleToApplyAction(crates, action.clone()));
+        }
+
+        heights[from_stack - 1] -= number_crates;
+        heights[to_stack - 1] += number_crates;
+    }
+
+    let mut top = String::new();
+    for (stack, height) in heights.iter().enumerate() {
+        if *height == 0 {
+            continue;
+        }
+
+        let mut heights = heights.clone();
+        let (mut stack, mut index) = (stack, height - 1);
+
+        for action in actions.iter().rev() {
+            let CraneAction::Move { number_crates, from_stack, to_stack, .. } = *action;
+            let (from, to) = (from_stack - 1, to_stack - 1);
+
+            heights[to] -= number_crates;
+            heights[from] += number_crates;
+
+            if stack == to && index >= heights[to] {
+                index = heights[from] - number_crates + (index - heights[to]);
+                stack = from;
+            }
+        }
+
+        top.push(stacks.stacks[stack][index]);
+    }
+
+    Ok(top)
+}
+
+pub fn run_challenge2_streaming(input: impl IntoInput) -> Result<String, Error> {
+    let content = input.into_input()?;
+    let (stacks, actions) = read_input(&content)?;
+    top_9001_streaming(&stacks, &actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day05::*;
+    use proptest::strategy::Strategy as _;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day5_example.txt"))?;
+        assert_eq!(result, "CMZ");
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day5_challenge.txt"))?;
+        println!("{}", result);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day5_example.txt"))?;
+        assert_eq!(result, "MCD");
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day5_challenge.txt"))?;
+        println!("{}", result);
+        Ok(())
+    }
+
+    #[test]
+    fn scenario_checkpoints() -> Result<(), Error> {
+        let report = run_scenario(include_str!("data/day5_scenario.txt"))?;
+        assert!(report.passed());
+        assert_eq!(
+            report.checkpoints.iter().map(|checkpoint| (checkpoint.label.as_str(), checkpoint.line)).collect::<Vec<_>>(),
+            vec![("first move", 10), ("first stack emptied", 12), ("end", 15)]
+        );
+
+        let scenario = include_str!("data/day5_scenario.txt").replace("crane 9000", "crane 9001");
+        let report = run_scenario(scenario.as_str())?;
+        assert!(!report.passed());
+        assert_eq!(report.stacks.top(), "MCD");
+        assert_eq!(report.checkpoints[2].to_string(), "FAIL end (line 15): expected CMZ, got MCD");
+        assert_eq!(report.checkpoints[0].to_string(), "ok   first move (line 10): DCP");
+
+        assert!(matches!(run_scenario("[A]\n 1\n\nmove 2 from 1 to 1\n"), Err(Error::ImpossibleToApplyAction(..))));
+        assert!(matches!(run_scenario("crane 9002\n[A]\n 1\n"), Err(Error::InvalidLine(1, e)) if matches!(*e, Error::InvalidCrane(_))));
+        assert!(matches!(run_scenario("[A]\n 1\n\njump\n"), Err(Error::InvalidLine(4, e)) if matches!(*e, Error::InvalidStep(_))));
+        assert!(run_scenario("[A] [B]\n 1   2\ncheckpoint start: AB\n")?.passed());
+        Ok(())
+    }
+
+    #[test]
+    fn plan_example() -> Result<(), Error> {
+        let (stacks, _) = read_input(include_str!("data/day5_example.txt"))?;
+
+        let actions = plan_actions(&stacks, "ZMN", CraneModel::CrateMover9000, 4)?;
+        let mut planned = stacks.clone();
+        for action in actions.iter() {
+            planned.apply(action, CraneModel::CrateMover9000)?;
+        }
+        assert_eq!(planned.top(), "ZMN");
+        assert!(actions.len() <= 4);
+
+        let actions = plan_actions(&stacks, "NDP", CraneModel::CrateMover9001, 3)?;
+        assert!(actions.is_empty());
+
+        assert!(matches!(
+            plan_actions(&stacks, "XYZ", CraneModel::CrateMover9001, 2),
+            Err(Error::NoPlanFound(_, 2))
+        ));
+
+        let example = worked_example(include_str!("data/day5_example.txt"), "ZMN", CraneModel::CrateMover9000, 4)?;
+        assert!(example.starts_with("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3  \n\nmove "));
+        assert_eq!(run_challenge1(example.as_str())?, "ZMN");
+        assert!(matches!("9002".parse::<CraneModel>(), Err(Error::InvalidCrane(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn simulation_options() -> Result<(), Error> {
+        let (stacks, mut actions) = read_input(include_str!("data/day5_example.txt"))?;
+        let options = SimulationOptions::new(CraneModel::CrateMover9000);
+
+        let full = simulate_with_options(&stacks, &actions, options);
+        assert!(full.is_valid() && !full.truncated);
+        assert_eq!((full.steps, full.history.len(), full.stacks.top().as_str()), (4, 4, "CMZ"));
+
+        let limited = simulate_with_options(&stacks, &actions, SimulationOptions { max_steps: Some(2), dry_run: true, ..options });
+        assert!(limited.truncated && limited.history.is_empty());
+        assert_eq!((limited.steps, limited.stacks.top().as_str()), (2, "CZ"));
+
+        actions.insert(1, "move 9 from 1 to 2".parse()?);
+        actions.insert(3, "move 1 from 4 to 1".parse()?);
+        let stopped = simulate_with_options(&stacks, &actions, options);
+        assert_eq!((stopped.steps, stopped.errors.len()), (2, 1));
+
+        let collected = simulate_with_options(&stacks, &actions, SimulationOptions { on_error: ErrorPolicy::CollectErrors, ..options });
+        assert_eq!((collected.steps, collected.errors.len(), collected.stacks.top().as_str()), (6, 2, "CMZ"));
+        assert!(matches!(collected.errors[1], Error::InvalidStackReference(4, _)));
+        Ok(())
+    }
+
+    #[test]
+    fn apply_matches_accept() -> Result<(), Error> {
+        let (stacks, actions) = read_input(include_str!("data/day5_example.txt"))?;
+
+        let mut v1 = stacks.clone();
+        let mut v2 = stacks.clone();
+        let mut accepted_v1 = stacks.clone();
+        let mut accepted_v2 = stacks;
+        for action in actions.iter() {
+            v1.apply(action, CraneModel::CrateMover9000)?;
+            v2.apply(action, CraneModel::CrateMover9001)?;
+            accepted_v1 = accepted_v1.accept(action)?;
+            accepted_v2 = accepted_v2.accept_v2(action)?;
+        }
+
+        assert_eq!(v1, accepted_v1);
+        assert_eq!(v2, accepted_v2);
+        Ok(())
+    }
+
+    #[test]
+    fn errors_cite_input_lines() {
+        let content = include_str!("data/day5_example.txt").replace("move 2 from 2 to 1", "move 5 from 2 to 1");
+        let error = run_challenge1(&content).err().unwrap();
+        assert!(matches!(error, Error::ImpossibleToApplyAction(_, CraneAction::Move { line: Some(8), .. })));
+        assert_eq!(error.to_string(), "Impossible to apply action 'move 5 from 2 to 1 (line 8)' on stack '['M', 'C']'");
+
+        let content = include_str!("data/day5_example.txt").replace("move 1 from 1 to 2", "move one from 1 to 2");
+        assert!(matches!(read_input(&content), Err(Error::InvalidLine(9, _))));
+    }
+
+    #[test]
+    fn streaming_matches_simulation() -> Result<(), Error> {
+        assert_eq!(run_challenge2_streaming(include_str!("data/day5_example.txt"))?, "MCD");
+
+        let challenge = include_str!("data/day5_challenge.txt");
+        assert_eq!(run_challenge2_streaming(challenge)?, run_challenge2(challenge)?);
+
+        let content = include_str!("data/day5_example.txt").replace("move 2 from 2 to 1", "move 5 from 2 to 1");
+        assert!(matches!(run_challenge2_streaming(content.as_str()), Err(Error::ImpossibleToApplyAction(_, _))));
+
+        assert_eq!(Day5.variant(2, "streaming", challenge).unwrap(), Day5.part2(challenge).unwrap());
+        assert!(matches!(Day5.variant(1, "streaming", challenge), Err(crate::Error::UnknownVariant(1, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn stack_stores() -> Result<(), Error> {
+        let (stacks, actions) = read_input(include_str!("data/day5_challenge.txt"))?;
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            let mut vec = stacks.clone();
+            let mut deque = stacks.with_store::<VecDeque<char>>();
+            let mut persistent = stacks.with_store::<PersistentStack>();
+            for action in actions.iter() {
+                vec.apply(action, model)?;
+                deque.apply(action, model)?;
+                persistent.apply(action, model)?;
+            }
+
+            assert_eq!(deque.to_string(), vec.to_string());
+            assert_eq!(persistent.to_string(), vec.to_string());
+            assert_eq!(persistent.with_store::<Vec<char>>(), vec);
+        }
+
+        let (stacks, actions) = read_input(include_str!("data/day5_example.txt"))?;
+        let snapshot = stacks.with_store::<PersistentStack>();
+        let mut moved = snapshot.clone();
+        moved.apply(&actions[0], CraneModel::CrateMover9000)?;
+        assert_eq!((snapshot.top(), moved.top()), ("NDP".to_string(), "DCP".to_string()));
+        assert!(matches!(
+            moved.apply(&CraneAction::Move { number_crates: 9, from_stack: 1, to_stack: 2, line: None }, CraneModel::CrateMover9000),
+            Err(Error::ImpossibleToApplyAction(crates, _)) if crates == vec!['Z', 'N', 'D']
+        ));
+
+        let mut tall = PersistentStack::default();
+        tall.put_on_top(std::iter::repeat_n('A', 1_000_000));
+        let shared = tall.clone();
+        drop(tall);
+        assert_eq!(shared.len(), 1_000_000);
+        drop(shared);
+        Ok(())
+    }
+
+    fn arbitrary_stacks() -> impl proptest::strategy::Strategy<Value = Stacks> {
+        proptest::collection::vec(proptest::collection::vec(proptest::char::range('A', 'Z'), 0..8), 1..6)
+            .prop_map(|stacks| Stacks { stacks })
+    }
+
+    // Stack references go one past both ends, so invalid actions show up too.
+    fn arbitrary_actions() -> impl proptest::strategy::Strategy<Value = Vec<CraneAction>> {
+        proptest::collection::vec(
+            (0..6_usize, 0..7_usize, 0..7_usize).prop_map(|(number_crates, from_stack, to_stack)|
+                CraneAction::Move { number_crates, from_stack, to_stack, line: None }
+            ),
+            0..30,
+        )
+    }
+
+    // Only actions that can be applied, each sized against the heights the ones before it leave.
+    fn arbitrary_scenario() -> impl proptest::strategy::Strategy<Value = (Stacks, Vec<CraneAction>)> {
+        (arbitrary_stacks(), proptest::collection::vec((0..6_usize, 0..6_usize, 1..8_usize), 0..60))
+            .prop_map(|(stacks, moves)| {
+                let mut heights = stacks.stacks.iter().map(Vec::len).collect::<Vec<_>>();
+                let mut actions = Vec::new();
+                for (from, to, number_crates) in moves {
+                    let (from, to) = (from % heights.len(), to % heights.len());
+                    let number_crates = number_crates.min(heights[from]);
+                    if from == to || number_crates == 0 {
+                        continue;
+                    }
+
+                    heights[from] -= number_crates;
+                    heights[to] += number_crates;
+                    actions.push(CraneAction::Move { number_crates, from_stack: from + 1, to_stack: to + 1, line: None });
+                }
+                (stacks, actions)
+            })
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn in_place_matches_functional(stacks in arbitrary_stacks(), actions in arbitrary_actions()) {
+            for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+                let mut in_place = stacks.clone();
+                let mut functional = stacks.clone();
+
+                for action in actions.iter() {
+                    let before = in_place.clone();
+                    let applied = in_place.apply(action, model);
+                    let accepted = match model {
+                        CraneModel::CrateMover9000 => functional.accept(action),
+                        CraneModel::CrateMover9001 => functional.accept_v2(action),
+                    };
+
+                    match (applied, accepted) {
+                        (Ok(()), Ok(accepted)) => {
+                            proptest::prop_assert_eq!(&in_place, &accepted);
+                            proptest::prop_assert_eq!(in_place.crate_counts(), stacks.crate_counts());
+                            functional = accepted;
+                        }
+                        (Err(applied), Err(accepted)) => {
+                            proptest::prop_assert_eq!(applied.to_string(), accepted.to_string());
+                            proptest::prop_assert_eq!(&in_place, &before);
+                            break;
+                        }
+                        (applied, accepted) => proptest::prop_assert!(false, "{:?} vs {:?}", applied, accepted),
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn streaming_matches_simulated((stacks, actions) in arbitrary_scenario()) {
+            let mut simulated = stacks.clone();
+            for action in actions.iter() {
+                simulated.apply(action, CraneModel::CrateMover9001).unwrap();
+            }
+
+            proptest::prop_assert_eq!(top_9001_streaming(&stacks, &actions).unwrap(), simulated.top());
+        }
+    }
+}
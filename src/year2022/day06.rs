@@ -0,0 +1,440 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::RangeInclusive,
+};
+use thiserror::Error;
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+    utils::counter::Counter,
+};
+
+// How marker positions are counted: in bytes like the puzzle, in chars, or in bytes of input that
+// has to be ASCII.
+#[derive(Clone, Copy, Debug)]
+pub enum Indexing {
+    Bytes,
+    Chars,
+    AsciiOnly,
+}
+
+pub fn find_distinct_window<T>(items: &[T], packet_size: usize) -> Option<usize>
+    where T: Copy + Eq + Hash {
+    if packet_size == 0 {
+        return Some(0);
+    }
+
+    let mut window = Counter::new();
+    for (index, item) in items.iter().enumerate() {
+        window.add(*item);
+        if index >= packet_size {
+            window.remove(&items[index - packet_size]);
+        }
+
+        if window.len() == packet_size {
+            return Some(index + 1);
+        }
+    }
+
+    None
+}
+
+// Same result as find_distinct_window on bytes, with per-byte counts kept on the stack instead of a set.
+#[inline]
+pub fn find_marker_in(bytes: &[u8], window: usize) -> Option<usize> {
+    if window == 0 {
+        return Some(0);
+    }
+
+    let mut counts = [0_u32; 256];
+    let mut duplicated = 0_usize;
+
+    for (index, byte) in bytes.iter().enumerate() {
+        counts[*byte as usize] += 1;
+        if counts[*byte as usize] == 2 {
+            duplicated += 1;
+        }
+
+        if index >= window {
+            let leaving = bytes[index - window] as usize;
+            counts[leaving] -= 1;
+            if counts[leaving] == 1 {
+                duplicated -= 1;
+            }
+        }
+
+        if index + 1 >= window && duplicated == 0 {
+            return Some(index + 1);
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Window {
+    pub start: usize,
+    pub length: usize,
+}
+
+// Any marker size up to the returned length can be found in the stream, none longer can.
+pub fn longest_distinct_window<T>(items: &[T]) -> Option<Window>
+    where T: Copy + Eq + Hash {
+    let mut last_seen: HashMap<T, usize> = HashMap::new();
+    let mut best: Option<Window> = None;
+    let mut start = 0;
+
+    for (end, item) in items.iter().enumerate() {
+        if let Some(previous) = last_seen.insert(*item, end) {
+            start = start.max(previous + 1);
+        }
+
+        let length = end + 1 - start;
+        if best.as_ref().is_none_or(|best| length > best.length) {
+            best = Some(Window { start, length });
+        }
+    }
+
+    best
+}
+
+fn find_packet_distinct_chars(s: &str, packet_size: usize) -> Result<usize, Error> {
+    find_packet_distinct_chars_with(s, packet_size, Indexing::Bytes)
+}
+
+fn find_packet_distinct_chars_with(s: &str, packet_size: usize, indexing: Indexing) -> Result<usize, Error> {
+    let index = match indexing {
+        Indexing::Bytes => find_marker_in(s.as_bytes(), packet_size),
+        Indexing::Chars => find_distinct_window(&s.chars().collect::<Vec<_>>(), packet_size),
+        Indexing::AsciiOnly => match s.char_indices().find(|(_, c)| !c.is_ascii()) {
+            Some((index, c)) => Err(Error::NonAsciiInput(c, index))?,
+            None => find_marker_in(s.as_bytes(), packet_size),
+        },
+    };
+
+    index.ok_or_else(|| Error::NoPacketStart(s.to_string()))
+}
+
+// Index is the end of the marker, counted in chars. Context spans up to `context` chars on each side of the window.
+#[derive(Debug, PartialEq)]
+pub struct Marker {
+    pub index: usize,
+    pub window: String,
+    pub context: String,
+}
+
+pub fn find_marker(s: &str, packet_size: usize, context: usize) -> Result<Marker, Error> {
+    let index = find_packet_distinct_chars_with(s, packet_size, Indexing::Chars)?;
+    let chars = s.chars().collect::<Vec<_>>();
+
+    let start = index - packet_size;
+    Ok(Marker {
+        index,
+        window: chars[start..index].iter().collect(),
+        context: chars[start.saturating_sub(context)..(index + context).min(chars.len())].iter().collect(),
+    })
+}
+
+fn find_packet_start(s: &str) -> Result<usize, Error> {
+    find_packet_distinct_chars(s, 4)
+}
+
+fn find_message_start(s: &str) -> Result<usize, Error> {
+    find_packet_distinct_chars(s, 14)
+}
+
+// Marker positions over a batch of streams, one per line. Streams without a marker are only counted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkerStats {
+    indexes: Vec<usize>,
+    missing: usize,
+}
+
+impl MarkerStats {
+    pub fn new(content: &str, packet_size: usize) -> Self {
+        let mut indexes = Vec::new();
+        let mut missing = 0;
+        for line in content.lines() {
+            match find_marker_in(line.as_bytes(), packet_size) {
+                Some(index) => indexes.push(index),
+                None => missing += 1,
+            }
+        }
+        indexes.sort_unstable();
+
+        MarkerStats { indexes, missing }
+    }
+
+    pub fn found(&self) -> usize {
+        self.indexes.len()
+    }
+
+    pub fn missing(&self) -> usize {
+        self.missing
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.indexes.first().copied()
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.indexes.last().copied()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (!self.indexes.is_empty()).then(|| self.indexes.iter().sum::<usize>() as f64 / self.indexes.len() as f64)
+    }
+
+    // Equal-width buckets from min to max, the last one may reach past max.
+    pub fn histogram(&self, buckets: usize) -> Vec<(RangeInclusive<usize>, usize)> {
+        let (Some(min), Some(max)) = (self.min(), self.max()) else {
+            return Vec::new();
+        };
+        let width = (max - min + 1).div_ceil(buckets.max(1));
+
+        (0..buckets.max(1))
+            .map(|bucket| min + bucket * width)
+            .take_while(|start| *start <= max)
+            .map(|start| {
+                let end = start + width - 1;
+                (start..=end, self.indexes.iter().filter(|index| (start..=end).contains(index)).count())
+            })
+            .collect()
+    }
+
+    fn describe(&self) -> Vec<String> {
+        let mut lines = vec![format!("streams: {} ({} without marker)", self.found() + self.missing, self.missing)];
+        if let (Some(min), Some(max), Some(mean)) = (self.min(), self.max(), self.mean()) {
+            lines.push(format!("min: {}, max: {}, mean: {:.2}", min, max, mean));
+        }
+
+        let histogram = self.histogram(10);
+        let largest = histogram.iter().map(|(_, count)| *count).max().unwrap_or_default().max(1);
+        for (range, count) in histogram {
+            let bar = "#".repeat(count * 40 / largest);
+            lines.push(format!("{:>6}..={:<6} {:<40} {}", range.start(), range.end(), bar, count));
+        }
+        lines
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("No packet start found in '{0}'")]
+    NoPacketStart(String),
+    #[error("Non-ASCII character '{0}' at byte {1}")]
+    NonAsciiInput(char, usize),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ Error::NonAsciiInput(..) => crate::Error::parse(e),
+            e @ Error::NoPacketStart(..) => crate::Error::solve(e),
+        }
+    }
+}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<Vec<usize>, Error> {
+    let content = input.into_input()?;
+    let mut indexes: Vec<usize> = Vec::new();
+    for line in content.lines() {
+        indexes.push(find_packet_start(line)?);
+    }
+
+    Ok(indexes)
+}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<Vec<usize>, Error> {
+    let content = input.into_input()?;
+    let mut indexes: Vec<usize> = Vec::new();
+    for line in content.lines() {
+        indexes.push(find_message_start(line)?);
+    }
+
+    Ok(indexes)
+}
+
+// The end of the first marker of every stream, one per line.
+pub fn find_markers(input: impl IntoInput, packet_size: usize, indexing: Indexing) -> Result<Vec<usize>, Error> {
+    let content = input.into_input()?;
+    content.lines().map(|line| find_packet_distinct_chars_with(line, packet_size, indexing)).collect()
+}
+
+fn packet_size(part: u8) -> Result<usize, crate::Error> {
+    match part {
+        1 => Ok(4),
+        2 => Ok(14),
+        _ => Err(crate::Error::UnknownPart(part)),
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day6_example.txt"), answers: [Some("7\n5\n6\n10\n11"), Some("19\n23\n23\n29\n26")] }
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.iter().map(usize::to_string).collect::<Vec<_>>().join("\n").into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.iter().map(usize::to_string).collect::<Vec<_>>().join("\n").into())
+    }
+
+    // Positions counted in chars, or a failure on anything but ASCII, rather than in bytes.
+    fn variants(&self, _part: u8) -> &'static [&'static str] {
+        &["chars", "ascii"]
+    }
+
+    fn variant(&self, part: u8, name: &str, input: &str) -> Result<Answer, crate::Error> {
+        let indexing = match name {
+            "chars" => Indexing::Chars,
+            "ascii" => Indexing::AsciiOnly,
+            _ => return Err(crate::Error::UnknownVariant(part, name.to_string())),
+        };
+        let indexes = find_markers(input, packet_size(part)?, indexing)?;
+        Ok(indexes.iter().map(usize::to_string).collect::<Vec<_>>().join("\n").into())
+    }
+
+    fn details(&self, part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        const MARKER_CONTEXT: usize = 4;
+
+        let packet_size = packet_size(part)?;
+        let mut details = MarkerStats::new(input, packet_size).describe();
+        for (n, line) in input.lines().enumerate() {
+            if let Ok(Marker { index, window, context }) = find_marker(line, packet_size, MARKER_CONTEXT) {
+                details.push(format!("stream {}: marker {} ends at {}, in {}", n + 1, window, index, context));
+            }
+            if let Some(Window { start, length }) = longest_distinct_window(&line.chars().collect::<Vec<_>>()) {
+                details.push(format!("stream {}: at most {} distinct chars in a row, first from {}", n + 1, length, start));
+            }
+        }
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day06::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day6_example.txt"))?;
+        assert_eq!(result, vec![7, 5, 6, 10, 11]);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day6_challenge.txt"))?;
+        println!("{:?}", result);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day6_example.txt"))?;
+        assert_eq!(result, vec![19, 23, 23, 29, 26]);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day6_challenge.txt"))?;
+        println!("{:?}", result);
+        Ok(())
+    }
+
+    #[test]
+    fn char_indexing() -> Result<(), Error> {
+        let stream = "aéébcd";
+        assert_eq!(find_packet_distinct_chars_with(stream, 4, Indexing::Bytes)?, 7);
+        assert_eq!(find_packet_distinct_chars_with(stream, 4, Indexing::Chars)?, 6);
+        assert!(matches!(
+            find_packet_distinct_chars_with(stream, 4, Indexing::AsciiOnly),
+            Err(Error::NonAsciiInput('é', 1))
+        ));
+
+        for line in include_str!("data/day6_example.txt").lines() {
+            assert_eq!(
+                find_packet_distinct_chars_with(line, 14, Indexing::Chars)?,
+                find_packet_distinct_chars_with(line, 14, Indexing::AsciiOnly)?
+            );
+        }
+
+        assert_eq!(Day6.variant(1, "chars", "aéébcd\nmjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap().to_string(), "6\n7");
+        assert!(matches!(Day6.variant(1, "ascii", stream), Err(crate::Error::Parse(_))));
+        assert!(matches!(Day6.variant(3, "chars", stream), Err(crate::Error::UnknownPart(3))));
+        Ok(())
+    }
+
+    #[test]
+    fn marker_context() -> Result<(), Error> {
+        let marker = find_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4, 3)?;
+        assert_eq!(marker, Marker { index: 7, window: "jpqm".to_string(), context: "mjqjpqmgbl".to_string() });
+
+        let marker = find_marker("abcd", 4, 10)?;
+        assert_eq!(marker.context, "abcd");
+        assert_eq!(find_marker("aéébcd", 4, 0)?.window, "ébcd");
+        Ok(())
+    }
+
+    #[test]
+    fn marker_statistics() {
+        let stats = MarkerStats::new(include_str!("data/day6_example.txt"), 4);
+        assert_eq!((stats.found(), stats.missing(), stats.min(), stats.max()), (5, 0, Some(5), Some(11)));
+        assert_eq!(stats.mean(), Some(7.8));
+        assert_eq!(stats.histogram(3), vec![(5..=7, 3), (8..=10, 1), (11..=13, 1)]);
+        assert_eq!(stats.describe()[1], "min: 5, max: 11, mean: 7.80");
+
+        let stats = MarkerStats::new("aaaa\nabcd\n", 4);
+        assert_eq!((stats.found(), stats.missing()), (1, 1));
+        assert_eq!(stats.histogram(10), vec![(4..=4, 1)]);
+        assert_eq!(MarkerStats::new("aaaa\n", 4).mean(), None);
+    }
+
+    #[test]
+    fn longest_window() {
+        assert_eq!(longest_distinct_window::<u8>(&[]), None);
+        assert_eq!(longest_distinct_window(b"abcabcbb"), Some(Window { start: 0, length: 3 }));
+        assert_eq!(longest_distinct_window(b"bbbbb"), Some(Window { start: 0, length: 1 }));
+        assert_eq!(longest_distinct_window(b"pwwkew"), Some(Window { start: 2, length: 3 }));
+
+        let details = Day6.details(1, "abcabcdbb\n\nbbbbb\n").unwrap();
+        assert_eq!(&details[details.len() - 3..], [
+            "stream 1: marker abcd ends at 7, in abcabcdbb",
+            "stream 1: at most 4 distinct chars in a row, first from 3",
+            "stream 3: at most 1 distinct chars in a row, first from 0",
+        ]);
+    }
+
+    fn brute_force_longest(items: &[u8]) -> Option<Window> {
+        (1..=items.len())
+            .rev()
+            .find_map(|length|
+                find_distinct_window(items, length).map(|end| Window { start: end - length, length })
+            )
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn marker_in_matches_distinct_window(stream in "[a-f]{0,40}", window in 0..8_usize) {
+            proptest::prop_assert_eq!(find_marker_in(stream.as_bytes(), window), find_distinct_window(stream.as_bytes(), window));
+        }
+
+        #[test]
+        fn longest_window_matches_brute_force(stream in "[a-f]{0,40}") {
+            proptest::prop_assert_eq!(longest_distinct_window(stream.as_bytes()), brute_force_longest(stream.as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,883 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use nom::{
+    Finish,
+    IResult,
+    branch::alt,
+    character::complete,
+    bytes::complete::{tag, take_while1},
+    combinator::{all_consuming, map},
+    sequence::{preceded, separated_pair},
+};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt,
+    rc::Rc,
+};
+use thiserror::Error;
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+    utils::size::human_size,
+};
+
+#[derive(Debug)]
+struct List;
+
+fn parse_list(i: &str) -> IResult<&str, List> {
+    map(tag("ls"), |_| List)(i)
+}
+
+#[derive(Debug)]
+struct ChangeDirectory(Utf8PathBuf);
+
+fn parse_path(i: &str) -> IResult<&str, Utf8PathBuf> {
+    map(
+        take_while1(|c: char| c.is_alphabetic() || c == '.' || c == '/'),
+        Utf8PathBuf::from,
+    )(i)
+}
+
+fn parse_change_directory(i: &str) -> IResult<&str, ChangeDirectory> {
+    map(preceded(tag("cd "), parse_path), ChangeDirectory)(i)
+}
+
+#[derive(Debug)]
+enum Command {
+    List(List),
+    ChangeDirectory(ChangeDirectory),
+}
+
+fn parse_command(i: &str) -> IResult<&str, Command> {
+    let (i, _) = tag("$ ")(i)?;
+    alt(
+        (
+            map(parse_list, Command::List),
+            map(parse_change_directory, Command::ChangeDirectory)
+        )
+    )(i)
+}
+
+#[derive(Debug)]
+enum Entry {
+    Dir(Utf8PathBuf),
+    File(u64, Utf8PathBuf),
+}
+
+fn parse_entry(i: &str) -> IResult<&str, Entry> {
+    let parse_file = map(
+        separated_pair(complete::u64, tag(" "), parse_path),
+        |(size, path)| Entry::File(size, path),
+    );
+
+    let parse_dir = map(
+        preceded(tag("dir "), parse_path),
+        Entry::Dir,
+    );
+
+    alt((parse_file, parse_dir))(i)
+}
+
+#[derive(Debug)]
+enum Line {
+    Command(Command),
+    Entry(Entry),
+}
+
+fn parse_line(i: &str) -> IResult<&str, Line> {
+    alt(
+        (
+            map(parse_entry, Line::Entry),
+            map(parse_command, Line::Command),
+        )
+    )(i)
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[error("No directory found")]
+    NoDirectoryFound,
+    #[error("Directory depth limit of {0} exceeded at line {1}")]
+    DepthLimitExceeded(usize, usize),
+    #[error("Files take {0} bytes, more than the {1} bytes of the disk")]
+    DiskOverfull(u64, u64),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ Error::Nom(..) => crate::Error::parse(e),
+            e @ (Error::DepthLimitExceeded(..) | Error::DiskOverfull(..)) => crate::Error::validation(e),
+            e @ Error::NoDirectoryFound => crate::Error::solve(e),
+        }
+    }
+}
+
+pub struct Node {
+    parent: Option<NodeHandle>,
+    name: Utf8PathBuf,
+    size: u64,
+    // Ordered by name, so that traversals and printed trees are stable across runs.
+    children: BTreeMap<Utf8PathBuf, NodeHandle>,
+    cached_total_size: Option<u64>,
+}
+
+impl Node {
+    fn new_dir(name: Utf8PathBuf, parent: Option<NodeHandle>) -> Node {
+        Node {
+            parent,
+            name,
+            size: 0_u64,
+            children: BTreeMap::new(),
+            cached_total_size: None,
+        }
+    }
+    fn new_file(name: Utf8PathBuf, size: u64, parent: Option<NodeHandle>) -> Node {
+        Node {
+            parent,
+            name,
+            size,
+            children: BTreeMap::new(),
+            cached_total_size: None,
+        }
+    }
+
+    pub fn name(&self) -> &Utf8Path {
+        &self.name
+    }
+
+    pub fn children(&self) -> impl Iterator<Item=&NodeHandle> {
+        self.children.values()
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn total_size(&self) -> u64 {
+        if let Some(total_size) = self.cached_total_size {
+            return total_size;
+        }
+
+        let mut total_size = self.size;
+        let mut stack = self.children.values().cloned().collect::<Vec<_>>();
+        while let Some(node) = stack.pop() {
+            let node = node.borrow();
+            match node.cached_total_size {
+                Some(cached) => total_size += cached,
+                None => {
+                    total_size += node.size;
+                    stack.extend(node.children.values().cloned());
+                }
+            }
+        }
+
+        total_size
+    }
+}
+
+pub type NodeHandle = Rc<RefCell<Node>>;
+
+// Fills the size cache of every node, children before their parent.
+pub fn cache_total_sizes(node: &NodeHandle) -> u64 {
+    let mut stack = vec![node.clone()];
+    let mut pre_order = Vec::new();
+    while let Some(current) = stack.pop() {
+        stack.extend(current.borrow().children.values().cloned());
+        pre_order.push(current);
+    }
+
+    for current in pre_order.iter().rev() {
+        let total_size = current.borrow().size + current.borrow().children
+            .values()
+            .map(|child| child.borrow().cached_total_size.unwrap_or_default())
+            .sum::<u64>();
+
+        current.borrow_mut().cached_total_size = Some(total_size);
+    }
+
+    node.borrow().total_size()
+}
+
+// Drops the cached size of the node and of all its ancestors.
+pub fn invalidate_total_size(node: &NodeHandle) {
+    let mut current = Some(node.clone());
+    while let Some(node) = current {
+        if node.borrow().cached_total_size.is_none() {
+            break;
+        }
+
+        node.borrow_mut().cached_total_size = None;
+        current = node.borrow().parent.clone();
+    }
+}
+
+pub struct AllDirs {
+    stack: Vec<NodeHandle>,
+}
+
+impl Iterator for AllDirs {
+    type Item = NodeHandle;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(
+            node.borrow().children
+                .values()
+                .rev()
+                .filter(|c| c.borrow().is_dir())
+                .cloned()
+        );
+        Some(node)
+    }
+}
+
+pub fn all_dirs(node: NodeHandle) -> AllDirs {
+    AllDirs { stack: vec![node] }
+}
+
+pub struct PrettyNode<'a>(pub &'a NodeHandle);
+
+impl<'a> fmt::Debug for PrettyNode<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_tree(f, self.0, false)
+    }
+}
+
+// Same tree as PrettyNode, with each entry's share of its parent directory and of the root.
+pub struct SharesNode<'a>(pub &'a NodeHandle);
+
+impl<'a> fmt::Debug for SharesNode<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_tree(f, self.0, true)
+    }
+}
+
+fn share(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        100.0 * part as f64 / whole as f64
+    }
+}
+
+fn write_tree(f: &mut fmt::Formatter<'_>, root: &NodeHandle, shares: bool) -> fmt::Result {
+    let root_size = root.borrow().total_size();
+
+    let mut stack = vec![(root.clone(), 0_usize)];
+    while let Some((node, depth)) = stack.pop() {
+        let this = node.borrow();
+        // The root and its direct children share the same indentation.
+        let indent = "  ".repeat(depth.saturating_sub(1));
+
+        if shares {
+            let size = this.total_size();
+            let parent_size = this.parent.as_ref().map_or(size, |parent| parent.borrow().total_size());
+            let kind = if this.is_dir() { "dir" } else { "file" };
+            writeln!(
+                f,
+                "{indent}{} ({kind}, size={size}, {:.1}% of parent, {:.1}% of /)",
+                this.name,
+                share(size, parent_size),
+                share(size, root_size),
+            )?;
+        } else if this.size == 0 {
+            writeln!(f, "{indent}{} (dir)", this.name)?;
+        } else {
+            writeln!(f, "{indent}{} (file, size={})", this.name, this.size)?;
+        }
+
+        stack.extend(this.children.values().rev().map(|child| (child.clone(), depth + 1)));
+    }
+    Ok(())
+}
+
+
+pub fn read_input(content: &str) -> Result<NodeHandle, Error> {
+    read_input_with_depth_limit(content, None)
+}
+
+fn read_input_with_depth_limit(content: &str, max_depth: Option<usize>) -> Result<NodeHandle, Error> {
+    let root = Rc::new(RefCell::new(Node::new_dir("/".parse().unwrap(), None)));
+    let mut node = root.clone();
+    let mut depth = 0_usize;
+
+    for (index, line) in content.lines().enumerate() {
+        let (_, line) = all_consuming(parse_line)(line)
+            .map_err(|e| e.to_owned())
+            .finish()?;
+
+        tracing::trace!("{:?}", line);
+
+        match line {
+            Line::Command(command) =>
+                match command {
+                    Command::List(_) => (),
+                    Command::ChangeDirectory(ChangeDirectory(name)) => {
+                        match name.as_str() {
+                            "/" => {
+                                node = root.clone();
+                                depth = 0;
+                            }
+                            ".." => {
+                                node = node.clone().borrow().parent.clone().unwrap_or_else(|| root.clone());
+                                depth = depth.saturating_sub(1);
+                            }
+                            _ => {
+                                depth += 1;
+                                if let Some(max_depth) = max_depth.filter(|max_depth| depth > *max_depth) {
+                                    Err(Error::DepthLimitExceeded(max_depth, index + 1))?;
+                                }
+
+                                node = node.clone().borrow_mut().children
+                                    .entry(name.clone())
+                                    .or_insert_with(||
+                                        Rc::new(
+                                            RefCell::new(
+                                                Node::new_dir(name.clone(), Some(node.clone()))
+                                            )
+                                        )
+                                    ).clone();
+                            }
+                        };
+                    }
+                },
+            Line::Entry(entry) =>
+                match entry {
+                    Entry::Dir(name) => {
+                        invalidate_total_size(&node);
+                        node.borrow_mut().children
+                            .entry(name.clone())
+                            .or_insert_with(||
+                                Rc::new(
+                                    RefCell::new(
+                                        Node::new_dir(name.clone(), Some(node.clone()))
+                                    )
+                                )
+                            );
+                    }
+                    Entry::File(size, name) => {
+                        invalidate_total_size(&node);
+                        node.borrow_mut().children
+                            .entry(name.clone())
+                            .or_insert_with(||
+                                Rc::new(
+                                    RefCell::new(
+                                        Node::new_file(name.clone(), size, Some(node.clone()))
+                                    )
+                                )
+                            );
+                    }
+                }
+        }
+    }
+
+    cache_total_sizes(&root);
+
+    Ok(root)
+}
+
+// Size of every entry by absolute path, 0 for directories.
+pub fn flatten(root: &NodeHandle) -> BTreeMap<Utf8PathBuf, u64> {
+    let mut entries = BTreeMap::new();
+    let mut stack = vec![(Utf8PathBuf::from("/"), root.clone())];
+    while let Some((path, node)) = stack.pop() {
+        let node = node.borrow();
+        stack.extend(node.children.values().map(|child| (path.join(&child.borrow().name), child.clone())));
+        entries.insert(path, node.size);
+    }
+    entries
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirSize {
+    pub path: Utf8PathBuf,
+    pub size: u64,
+}
+
+// A parsed tree with its directories indexed by total size, largest first, ties ordered by path.
+pub struct Filesystem {
+    root: NodeHandle,
+    dirs: Vec<DirSize>,
+}
+
+impl Filesystem {
+    pub fn new(root: NodeHandle) -> Self {
+        cache_total_sizes(&root);
+
+        let mut dirs = Vec::new();
+        let mut stack = vec![(Utf8PathBuf::from("/"), root.clone())];
+        while let Some((path, node)) = stack.pop() {
+            let node = node.borrow();
+            if node.is_dir() {
+                stack.extend(node.children.values().map(|child| (path.join(&child.borrow().name), child.clone())));
+                dirs.push(DirSize { path, size: node.total_size() });
+            }
+        }
+        dirs.sort_by(|left, right| right.size.cmp(&left.size).then_with(|| left.path.cmp(&right.path)));
+
+        Filesystem { root, dirs }
+    }
+
+    pub fn read(content: &str) -> Result<Self, Error> {
+        Ok(Filesystem::new(read_input(content)?))
+    }
+
+    pub fn root(&self) -> &NodeHandle {
+        &self.root
+    }
+
+    // Largest first, ties ordered by path.
+    pub fn dirs_by_size(&self) -> impl Iterator<Item=&DirSize> {
+        self.dirs.iter()
+    }
+
+    // 0 is the largest directory, which is always the root.
+    pub fn kth_largest_dir(&self, k: usize) -> Option<&DirSize> {
+        self.dirs_by_size().nth(k)
+    }
+
+    // The first by path among the directories of that smallest size.
+    pub fn smallest_dir_of_at_least(&self, size: u64) -> Option<&DirSize> {
+        let large_enough = &self.dirs[..self.dirs.partition_point(|dir| dir.size >= size)];
+        let smallest = large_enough.last()?.size;
+        large_enough.get(large_enough.partition_point(|dir| dir.size > smallest))
+    }
+}
+
+// Order of the directories sharing a parent in a du summary.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DuOrder {
+    Name,
+    // Largest first, by name on ties.
+    Size,
+}
+
+impl Filesystem {
+    // Directories like coreutils du, one per line with its total size, indented below its parent.
+    // The root is at depth 0, directories deeper than `depth` are left out but still counted.
+    pub fn du(&self, depth: Option<usize>, order: DuOrder) -> String {
+        let mut summary = String::new();
+        let mut stack = vec![(Utf8PathBuf::from("/"), self.root.clone(), 0_usize)];
+        while let Some((path, node, level)) = stack.pop() {
+            let node = node.borrow();
+            summary.push_str(&format!("{:>10}  {}{}\n", human_size(node.total_size()), "  ".repeat(level), path));
+            if depth.is_some_and(|depth| level >= depth) {
+                continue;
+            }
+
+            let mut children = node.children.values().filter(|child| child.borrow().is_dir()).cloned().collect::<Vec<_>>();
+            if order == DuOrder::Size {
+                children.sort_by_key(|child| std::cmp::Reverse(child.borrow().total_size()));
+            }
+            stack.extend(children.into_iter().rev().map(|child| (path.join(&child.borrow().name), child.clone(), level + 1)));
+        }
+        summary
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+// Shares of the total used space, in percent, from which a directory is flagged at each severity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thresholds {
+    pub info: f64,
+    pub warn: f64,
+    pub critical: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds { info: 10.0, warn: 25.0, critical: 50.0 }
+    }
+}
+
+impl Thresholds {
+    fn severity(&self, share: f64) -> Option<Severity> {
+        if share >= self.critical {
+            Some(Severity::Critical)
+        } else if share >= self.warn {
+            Some(Severity::Warn)
+        } else if share >= self.info {
+            Some(Severity::Info)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Flag {
+    pub dir: DirSize,
+    pub share: f64,
+    pub severity: Severity,
+}
+
+impl Filesystem {
+    // Largest first. The root always holds everything, so it is never flagged.
+    pub fn flag_dirs(&self, thresholds: &Thresholds) -> Vec<Flag> {
+        let total = self.root.borrow().total_size();
+        self.dirs_by_size()
+            .filter(|dir| dir.path != "/")
+            .filter_map(|dir| {
+                let share = share(dir.size, total);
+                thresholds.severity(share).map(|severity| Flag { dir: dir.clone(), share, severity })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct FsDiff {
+    pub only_left: Vec<Utf8PathBuf>,
+    pub only_right: Vec<Utf8PathBuf>,
+    pub size_mismatches: Vec<(Utf8PathBuf, u64, u64)>,
+}
+
+impl FsDiff {
+    pub fn between(left: &NodeHandle, right: &NodeHandle) -> Self {
+        let (left, right) = (flatten(left), flatten(right));
+        let mut diff = FsDiff::default();
+
+        for (path, left_size) in left.iter() {
+            match right.get(path) {
+                None => diff.only_left.push(path.clone()),
+                Some(right_size) if right_size != left_size => diff.size_mismatches.push((path.clone(), *left_size, *right_size)),
+                Some(_) => (),
+            }
+        }
+        diff.only_right = right.keys().filter(|path| !left.contains_key(*path)).cloned().collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty() && self.size_mismatches.is_empty()
+    }
+}
+
+// Like a unified diff: - for paths only on the left, + for paths only on the right.
+impl fmt::Display for FsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for path in &self.only_left {
+            writeln!(f, "- {}", path)?;
+        }
+        for path in &self.only_right {
+            writeln!(f, "+ {}", path)?;
+        }
+        for (path, left, right) in &self.size_mismatches {
+            writeln!(f, "~ {}: {} -> {}", path, left, right)?;
+        }
+        Ok(())
+    }
+}
+
+enum SessionStep {
+    Enter(NodeHandle),
+    Leave,
+}
+
+// Lists every non-empty directory exactly once, entering only directories with content.
+pub fn minimize_session(content: &str) -> Result<String, Error> {
+    let root = read_input(content)?;
+    let mut session = String::from("$ cd /\n");
+
+    let mut steps = vec![SessionStep::Enter(root.clone())];
+    while let Some(step) = steps.pop() {
+        let node = match step {
+            SessionStep::Leave => {
+                session.push_str("$ cd ..\n");
+                continue;
+            }
+            SessionStep::Enter(node) => node,
+        };
+
+        let node = node.borrow();
+        if node.parent.is_some() {
+            session.push_str(&format!("$ cd {}\n", node.name));
+        }
+
+        let children = node.children.values().cloned().collect::<Vec<_>>();
+        if children.is_empty() {
+            continue;
+        }
+
+        session.push_str("$ ls\n");
+        for child in children.iter() {
+            let child = child.borrow();
+            if child.is_dir() {
+                session.push_str(&format!("dir {}\n", child.name));
+            } else {
+                session.push_str(&format!("{} {}\n", child.size, child.name));
+            }
+        }
+
+        for child in children.into_iter().rev().filter(|child| child.borrow().is_dir() && !child.borrow().children.is_empty()) {
+            steps.push(SessionStep::Leave);
+            steps.push(SessionStep::Enter(child));
+        }
+    }
+
+    while session.ends_with("$ cd ..\n") {
+        session.truncate(session.len() - "$ cd ..\n".len());
+    }
+
+    Ok(session)
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge1(input: impl IntoInput) -> Result<u64, Error> {
+    let content = input.into_input()?;
+    let nodes = read_input(&content)?;
+    tracing::debug!("{:#?}", PrettyNode(&nodes));
+
+    let sum = all_dirs(nodes)
+        .map(|d| d.borrow().total_size())
+        .filter(|&s| s <= 100_000)
+        .sum::<u64>();
+
+    Ok(sum)
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge2(input: impl IntoInput) -> Result<u64, Error> {
+    let content = input.into_input()?;
+    let filesystem = Filesystem::read(&content)?;
+    tracing::debug!("{:#?}", PrettyNode(filesystem.root()));
+
+    let total_space = 70000000_u64;
+    let used_space = filesystem.root().borrow().total_size();
+    let free_space = total_space.checked_sub(used_space).ok_or(Error::DiskOverfull(used_space, total_space))?;
+    let needed_free_space = 30000000_u64;
+    let minimum_space_to_free = needed_free_space.saturating_sub(free_space);
+
+    filesystem
+        .smallest_dir_of_at_least(minimum_space_to_free)
+        .map(|dir| dir.size)
+        .ok_or(Error::NoDirectoryFound)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day7_example.txt"), answers: [Some("95437"), Some("24933642")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    fn details(&self, _part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        let flags = Filesystem::read(input)?.flag_dirs(&Thresholds::default());
+        Ok(flags
+            .iter()
+            .map(|flag| format!("{:?}: {} ({}, {:.1}%)", flag.severity, flag.dir.path, flag.dir.size, flag.share))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day07::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let sum = run_challenge1(include_str!("data/day7_example.txt"))?;
+        assert_eq!(sum, 95437);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let sum = run_challenge1(include_str!("data/day7_challenge.txt"))?;
+        dbg!(sum);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let size = run_challenge2(include_str!("data/day7_example.txt"))?;
+        assert_eq!(size, 24933642);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let sum = run_challenge2(include_str!("data/day7_challenge.txt"))?;
+        dbg!(sum);
+        Ok(())
+    }
+
+    fn deep_session(depth: usize) -> String {
+        let mut session = String::from("$ cd /\n");
+        for level in 1..=depth {
+            session.push_str(&format!("$ ls\ndir d\n{level} f\n$ cd d\n"));
+        }
+        session
+    }
+
+    #[test]
+    fn cached_total_sizes() -> Result<(), Error> {
+        let depth = 200_u64;
+        let root = read_input(&deep_session(depth as usize))?;
+
+        for (level, dir) in all_dirs(root).enumerate() {
+            let level = level as u64;
+            let expected = (level + 1..=depth).sum::<u64>();
+            assert_eq!(dir.borrow().cached_total_size, Some(expected));
+            assert_eq!(dir.borrow().total_size(), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn size_ranking() -> Result<(), Error> {
+        let filesystem = Filesystem::read(include_str!("data/day7_example.txt"))?;
+        let ranking = filesystem.dirs_by_size().map(|dir| (dir.path.as_str(), dir.size)).collect::<Vec<_>>();
+        assert_eq!(ranking, vec![("/", 48381165), ("/d", 24933642), ("/a", 94853), ("/a/e", 584)]);
+
+        assert_eq!(filesystem.kth_largest_dir(2).map(|dir| dir.size), Some(94853));
+        assert_eq!(filesystem.kth_largest_dir(4), None);
+        assert_eq!(filesystem.smallest_dir_of_at_least(585).map(|dir| dir.path.as_str()), Some("/a"));
+        assert_eq!(filesystem.smallest_dir_of_at_least(48381166), None);
+
+        let filesystem = Filesystem::read("$ cd /\n$ ls\ndir b\ndir a\n$ cd b\n$ ls\n5 f\n$ cd ..\n$ cd a\n$ ls\n5 f\n")?;
+        let ranking = filesystem.dirs_by_size().map(|dir| (dir.path.as_str(), dir.size)).collect::<Vec<_>>();
+        assert_eq!(ranking, vec![("/", 10), ("/a", 5), ("/b", 5)]);
+        assert_eq!(filesystem.smallest_dir_of_at_least(1).map(|dir| dir.path.as_str()), Some("/a"));
+
+        assert!(matches!(run_challenge2("$ cd /\n$ ls\n70000001 f\n"), Err(Error::DiskOverfull(70000001, 70000000))));
+        Ok(())
+    }
+
+    #[test]
+    fn du_summary() -> Result<(), Error> {
+        let filesystem = Filesystem::read(include_str!("data/day7_example.txt"))?;
+        assert_eq!(
+            filesystem.du(None, DuOrder::Name),
+            "  46.1 MiB  /\n  92.6 KiB    /a\n     584 B      /a/e\n  23.8 MiB    /d\n"
+        );
+        assert_eq!(filesystem.du(Some(1), DuOrder::Size), "  46.1 MiB  /\n  23.8 MiB    /d\n  92.6 KiB    /a\n");
+        assert_eq!(filesystem.du(Some(0), DuOrder::Size), "  46.1 MiB  /\n");
+        Ok(())
+    }
+
+    #[test]
+    fn flagged_dirs() -> Result<(), Error> {
+        let filesystem = Filesystem::read(include_str!("data/day7_example.txt"))?;
+        let flagged = |thresholds| filesystem.flag_dirs(&thresholds)
+            .into_iter()
+            .map(|flag| (flag.dir.path.to_string(), flag.severity))
+            .collect::<Vec<_>>();
+
+        assert_eq!(flagged(Thresholds::default()), vec![("/d".to_string(), Severity::Critical)]);
+        assert_eq!(
+            flagged(Thresholds { info: 0.1, warn: 0.15, critical: 60.0 }),
+            vec![("/d".to_string(), Severity::Warn), ("/a".to_string(), Severity::Warn)]
+        );
+        assert_eq!(flagged(Thresholds { info: 0.0, ..Thresholds::default() }).last(), Some(&("/a/e".to_string(), Severity::Info)));
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_tree() -> Result<(), Error> {
+        let root = read_input(include_str!("data/day7_example.txt"))?;
+        assert_eq!(format!("{:?}", PrettyNode(&root)), "\
+/ (dir)
+a (dir)
+  e (dir)
+    i (file, size=584)
+  f (file, size=29116)
+  g (file, size=2557)
+  h.lst (file, size=62596)
+b.txt (file, size=14848514)
+c.dat (file, size=8504156)
+d (dir)
+  d.ext (file, size=5626152)
+  d.log (file, size=8033020)
+  j (file, size=4060174)
+  k (file, size=7214296)
+");
+
+        let dirs = all_dirs(root.clone()).map(|dir| dir.borrow().name.to_string()).collect::<Vec<_>>();
+        assert_eq!(dirs, vec!["/", "a", "e", "d"]);
+        Ok(())
+    }
+
+    #[test]
+    fn shares_tree() -> Result<(), Error> {
+        let root = read_input(include_str!("data/day7_example.txt"))?;
+        let tree = format!("{:?}", SharesNode(&root));
+
+        assert_eq!(tree.lines().count(), format!("{:?}", PrettyNode(&root)).lines().count());
+        assert!(tree.contains("/ (dir, size=48381165, 100.0% of parent, 100.0% of /)\n"));
+        assert!(tree.contains("a (dir, size=94853, 0.2% of parent, 0.2% of /)\n"));
+        assert!(tree.contains("  e (dir, size=584, 0.6% of parent, 0.0% of /)\n"));
+        assert!(tree.contains("  i (file, size=584, 100.0% of parent, 0.0% of /)\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn very_deep_session() -> Result<(), Error> {
+        let depth = 100_000_u64;
+        let session = deep_session(depth as usize);
+
+        let root = read_input(&session)?;
+        assert_eq!(root.borrow().total_size(), depth * (depth + 1) / 2);
+        assert_eq!(all_dirs(root).count() as u64, depth + 1);
+
+        let root = read_input(&deep_session(1_000))?;
+        assert_eq!(format!("{:?}", PrettyNode(&root)).lines().count(), 2 * 1_000 + 1);
+
+        assert!(matches!(
+            read_input_with_depth_limit(&session, Some(1_000)),
+            Err(Error::DepthLimitExceeded(1_000, 4_005))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn session_minimization() -> Result<(), Error> {
+        let example = include_str!("data/day7_example.txt");
+        let noisy = format!("{example}\n$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ cd e\n$ cd ..\n$ cd ..\n$ ls");
+        let minimized = minimize_session(&noisy)?;
+
+        assert!(FsDiff::between(&read_input(&noisy)?, &read_input(&minimized)?).is_empty());
+        assert_eq!(minimized.lines().filter(|line| *line == "$ ls").count(), 4);
+        assert!(minimized.lines().count() < noisy.lines().count());
+        assert_eq!(minimize_session(&minimized)?, minimized);
+
+        let challenge = include_str!("data/day7_challenge.txt");
+        assert!(FsDiff::between(&read_input(challenge)?, &read_input(&minimize_session(challenge)?)?).is_empty());
+
+        let diff = FsDiff::between(&read_input(example)?, &read_input("$ cd /\n$ ls\ndir a\n100 b.txt\n")?);
+        assert_eq!(diff.size_mismatches, vec![(Utf8PathBuf::from("/b.txt"), 14848514, 100)]);
+        assert!(diff.only_right.is_empty());
+        assert_eq!(diff.only_left.len(), 11);
+        assert!(diff.to_string().ends_with("~ /b.txt: 14848514 -> 100\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,673 @@
+#[cfg(feature = "image")]
+use camino::Utf8Path;
+use thiserror::Error;
+use crate::render::style;
+use crate::visualize::{Frame, Rgb, Visualize};
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid number '{0}'")]
+    InvalidNumber(char),
+    #[error("Empty input")]
+    EmptyInput,
+    #[error("All tree lines should be of the same size")]
+    InvalidTreeLines,
+    #[cfg(feature = "image")]
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ (Error::InvalidNumber(..) | Error::EmptyInput | Error::InvalidTreeLines) => crate::Error::parse(e),
+            #[cfg(feature = "image")]
+            e @ Error::Image(..) => crate::Error::output(e),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Trees {
+    trees: Vec<Vec<u32>>,
+    rows: usize,
+    columns: usize,
+}
+
+fn is_visible(size: u32, mut trees: impl Iterator<Item=u32>) -> bool {
+    trees.all(|s| s < size)
+}
+
+fn scenic_score(size: u32, trees: impl Iterator<Item=u32>) -> usize {
+    let mut count = 0;
+    for tree in trees {
+        count += 1;
+        if tree >= size {
+            break;
+        }
+    }
+
+    count
+}
+
+impl Trees {
+    #[inline]
+    fn get_trees(&self, from_x: usize, to_x: usize, from_y: usize, to_y: usize) -> impl DoubleEndedIterator<Item=u32> + '_ {
+        self.trees[from_y..to_y].iter()
+            .flat_map(move |line| line[from_x..to_x].iter().cloned())
+    }
+
+    fn left_trees(&self, x: usize, y: usize) -> impl DoubleEndedIterator<Item=u32> + '_ {
+        self.get_trees(0, x, y, y + 1)
+    }
+
+    fn right_trees(&self, x: usize, y: usize) -> impl DoubleEndedIterator<Item=u32> + '_ {
+        self.get_trees(x + 1, self.columns, y, y + 1)
+    }
+
+    fn up_trees(&self, x: usize, y: usize) -> impl DoubleEndedIterator<Item=u32> + '_ {
+        self.get_trees(x, x + 1, 0, y)
+    }
+
+    fn bottom_trees(&self, x: usize, y: usize) -> impl DoubleEndedIterator<Item=u32> + '_ {
+        self.get_trees(x, x + 1, y + 1, self.rows)
+    }
+
+    fn visible_trees(&self) -> usize {
+        self.trees.iter().cloned().enumerate()
+            .map(|(y, line)|
+                line.iter().cloned().enumerate()
+                    .filter(|(x, size)|
+                        is_visible(*size, self.left_trees(*x, y)) ||
+                            is_visible(*size, self.right_trees(*x, y)) ||
+                            is_visible(*size, self.up_trees(*x, y)) ||
+                            is_visible(*size, self.bottom_trees(*x, y))
+                    )
+                    .count()
+            ).sum()
+    }
+
+    // Visible from the left, right, up and down, in that order.
+    fn visible_directions(&self, x: usize, y: usize) -> [bool; 4] {
+        paranoid_assert!(x < self.columns && y < self.rows, "({}, {}) is outside the grid", x, y);
+        let size = self.trees[y][x];
+        [
+            is_visible(size, self.left_trees(x, y)),
+            is_visible(size, self.right_trees(x, y)),
+            is_visible(size, self.up_trees(x, y)),
+            is_visible(size, self.bottom_trees(x, y)),
+        ]
+    }
+
+    fn is_edge(&self, x: usize, y: usize) -> bool {
+        x == 0 || y == 0 || x == self.columns - 1 || y == self.rows - 1
+    }
+
+    // The breakdown of a single tree, empty when it is hidden.
+    pub fn tree_visibility(&self, x: usize, y: usize) -> VisibilityBreakdown {
+        let [left, right, up, down] = self.visible_directions(x, y);
+        let visible = left || right || up || down;
+        VisibilityBreakdown {
+            edge: (visible && self.is_edge(x, y)) as usize,
+            interior: (visible && !self.is_edge(x, y)) as usize,
+            from_left: left as usize,
+            from_right: right as usize,
+            from_up: up as usize,
+            from_down: down as usize,
+        }
+    }
+
+    pub fn visibility_breakdown(&self) -> VisibilityBreakdown {
+        let mut breakdown = VisibilityBreakdown::default();
+        for y in 0..self.rows {
+            for x in 0..self.columns {
+                breakdown += self.tree_visibility(x, y);
+            }
+        }
+        breakdown
+    }
+
+    fn scenic_score_at(&self, x: usize, y: usize) -> usize {
+        let size = self.trees[y][x];
+        scenic_score(size, self.left_trees(x, y).rev()) *
+            scenic_score(size, self.right_trees(x, y)) *
+            scenic_score(size, self.up_trees(x, y).rev()) *
+            scenic_score(size, self.bottom_trees(x, y))
+    }
+
+    fn max_scenic_score(&self) -> Option<usize> {
+        self.trees.iter().cloned().enumerate()
+            .filter_map(|(y, line)|
+                line.iter().cloned().enumerate()
+                    .map(|(x, _)| self.scenic_score_at(x, y))
+                    .max()
+            ).max()
+    }
+
+    // First spot with the highest score, in reading order.
+    pub fn best_scenic_spot(&self) -> Option<(usize, usize)> {
+        (0..self.rows)
+            .flat_map(|y| (0..self.columns).map(move |x| (x, y)))
+            .rev()
+            .max_by_key(|(x, y)| self.scenic_score_at(*x, *y))
+    }
+
+    pub fn heightmap(&self, overlay: HeightmapOverlay) -> Frame {
+        let mut frame = Frame::new(self.columns, self.rows, [0, 0, 0]);
+
+        for y in 0..self.rows {
+            for x in 0..self.columns {
+                let gray = (self.trees[y][x] * 255 / 9) as u8;
+                let visible = overlay.visibility && self.tree_visibility(x, y).visible() > 0;
+                frame.set(x, y, if visible { HeightmapOverlay::visible(gray) } else { [gray, gray, gray] });
+            }
+        }
+
+        if overlay.best_spot {
+            if let Some((x, y)) = self.best_scenic_spot() {
+                frame.set(x, y, HeightmapOverlay::BEST_SPOT);
+            }
+        }
+
+        frame
+    }
+
+    #[cfg(feature = "image")]
+    pub fn to_png(&self, path: &Utf8Path, overlay: HeightmapOverlay) -> Result<(), Error> {
+        Ok(self.heightmap(overlay).save_png(path)?)
+    }
+}
+
+// Replanting a single tree, and the best scenic score of the park once done.
+#[derive(Debug, PartialEq)]
+pub struct ParkChange {
+    pub x: usize,
+    pub y: usize,
+    pub from: u32,
+    pub to: u32,
+    pub best_score: usize,
+}
+
+impl Trees {
+    // Changing a tree only affects the scores along its row and column, so only those are
+    // recomputed for every candidate height. Ties go to the first tree in reading order, then to
+    // the smallest height.
+    pub fn park_design(&self, max_change: u32) -> Option<ParkChange> {
+        let scores = (0..self.rows)
+            .flat_map(|y| (0..self.columns).map(move |x| (x, y)))
+            .map(|(x, y)| (self.scenic_score_at(x, y), x, y))
+            .collect::<Vec<_>>();
+        let current = scores.iter().map(|(score, _, _)| *score).max()?;
+        let mut ranked = scores;
+        ranked.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+
+        let mut park = self.clone();
+        let mut best: Option<ParkChange> = None;
+        for y in 0..self.rows {
+            for x in 0..self.columns {
+                let from = self.trees[y][x];
+                let untouched = ranked.iter()
+                    .find(|(_, other_x, other_y)| *other_x != x && *other_y != y)
+                    .map_or(0, |(score, _, _)| *score);
+
+                for to in from.saturating_sub(max_change)..=from.saturating_add(max_change).min(9) {
+                    if to == from {
+                        continue;
+                    }
+
+                    park.trees[y][x] = to;
+                    let best_score = (0..self.columns).map(|other_x| park.scenic_score_at(other_x, y))
+                        .chain((0..self.rows).map(|other_y| park.scenic_score_at(x, other_y)))
+                        .fold(untouched, usize::max);
+                    if best_score > best.as_ref().map_or(current, |best| best.best_score) {
+                        best = Some(ParkChange { x, y, from, to, best_score });
+                    }
+                }
+                park.trees[y][x] = from;
+            }
+        }
+
+        best
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HeightmapOverlay {
+    pub visibility: bool,
+    pub best_spot: bool,
+}
+
+impl HeightmapOverlay {
+    pub const BEST_SPOT: Rgb = [255, 0, 0];
+
+    // Keeps the height readable in the green channel.
+    pub fn visible(gray: u8) -> Rgb {
+        [gray / 4, gray / 2 + 128, gray / 4]
+    }
+}
+
+// A tree visible from several directions is counted once in edge/interior and once per direction.
+#[derive(Debug, Default, PartialEq)]
+pub struct VisibilityBreakdown {
+    pub edge: usize,
+    pub interior: usize,
+    pub from_left: usize,
+    pub from_right: usize,
+    pub from_up: usize,
+    pub from_down: usize,
+}
+
+impl VisibilityBreakdown {
+    pub fn visible(&self) -> usize {
+        self.edge + self.interior
+    }
+
+    pub fn directions(&self) -> usize {
+        self.from_left + self.from_right + self.from_up + self.from_down
+    }
+
+    fn describe(&self) -> Vec<String> {
+        vec![
+            format!("visible: {} ({} on the edge, {} inside)", self.visible(), self.edge, self.interior),
+            format!(
+                "from the left: {}, right: {}, up: {}, down: {}",
+                self.from_left, self.from_right, self.from_up, self.from_down,
+            ),
+        ]
+    }
+}
+
+impl std::ops::AddAssign for VisibilityBreakdown {
+    fn add_assign(&mut self, other: Self) {
+        self.edge += other.edge;
+        self.interior += other.interior;
+        self.from_left += other.from_left;
+        self.from_right += other.from_right;
+        self.from_up += other.from_up;
+        self.from_down += other.from_down;
+    }
+}
+
+// Hidden trees are dark, visible ones get brighter with each direction they can be seen from.
+pub struct VisibilityMap<'a>(pub &'a Trees);
+
+impl<'a> VisibilityMap<'a> {
+    const HIDDEN: Rgb = style::HEAT.background;
+}
+
+impl<'a> Visualize for VisibilityMap<'a> {
+    fn frames(&self) -> Vec<Frame> {
+        let trees = self.0;
+        let mut frame = Frame::new(trees.columns, trees.rows, Self::HIDDEN);
+
+        for y in 0..trees.rows {
+            for x in 0..trees.columns {
+                let directions = trees.tree_visibility(x, y).directions();
+                if directions > 0 {
+                    frame.set(x, y, style::HEAT.ramp(directions - 1, 3));
+                }
+            }
+        }
+
+        vec![frame]
+    }
+}
+
+// Heights stored row after row, so visibility can be computed with whole-row max-scans.
+pub struct FlatTrees {
+    heights: Vec<u8>,
+    rows: usize,
+    columns: usize,
+}
+
+impl From<&Trees> for FlatTrees {
+    fn from(trees: &Trees) -> Self {
+        FlatTrees {
+            heights: trees.trees.iter().flatten().map(|h| *h as u8).collect(),
+            rows: trees.rows,
+            columns: trees.columns,
+        }
+    }
+}
+
+impl FlatTrees {
+    #[inline]
+    fn row(&self, y: usize) -> &[u8] {
+        &self.heights[y * self.columns..(y + 1) * self.columns]
+    }
+
+    // Heights are shifted by one in the running maxima, so 0 means "no tree seen yet".
+    fn sweep_columns(&self, rows: impl Iterator<Item=usize>, visible: &mut [bool]) {
+        let mut running = vec![0_u8; self.columns];
+        for y in rows {
+            let row = self.row(y);
+            let visible_row = &mut visible[y * self.columns..(y + 1) * self.columns];
+
+            for ((max, height), visible) in running.iter_mut().zip(row).zip(visible_row.iter_mut()) {
+                *visible |= *height >= *max;
+                *max = (*max).max(*height + 1);
+            }
+        }
+    }
+
+    fn sweep_row(row: &[u8], visible: &mut [bool]) {
+        let mut max = 0_u8;
+        for (height, visible) in row.iter().zip(visible.iter_mut()) {
+            *visible |= *height >= max;
+            max = max.max(*height + 1);
+        }
+
+        let mut max = 0_u8;
+        for (height, visible) in row.iter().zip(visible.iter_mut()).rev() {
+            *visible |= *height >= max;
+            max = max.max(*height + 1);
+        }
+    }
+
+    pub fn visible_trees(&self) -> usize {
+        let mut visible = vec![false; self.heights.len()];
+
+        self.sweep_columns(0..self.rows, &mut visible);
+        self.sweep_columns((0..self.rows).rev(), &mut visible);
+
+        for (row, visible_row) in self.heights.chunks_exact(self.columns).zip(visible.chunks_exact_mut(self.columns)) {
+            Self::sweep_row(row, visible_row);
+        }
+
+        visible.iter().filter(|v| **v).count()
+    }
+}
+
+pub fn read_input(content: &str) -> Result<Trees, Error> {
+    let mut trees: Vec<Vec<u32>> = Vec::new();
+    for line in content.lines() {
+        let mut current_line: Vec<u32> = Vec::new();
+
+        for char in line.chars() {
+            current_line.push(
+                char
+                    .to_digit(10)
+                    .ok_or(Error::InvalidNumber(char))?
+            );
+        }
+
+        trees.push(current_line);
+    }
+
+    match trees.first() {
+        None => Err(Error::EmptyInput),
+        Some(first) if first.is_empty() => Err(Error::EmptyInput),
+        Some(first) => {
+            let rows = trees.len();
+            let expected_colums = first.len();
+
+            if trees.iter().all(|line| line.len() == expected_colums) {
+                Ok(
+                    Trees {
+                        trees,
+                        rows,
+                        columns: expected_colums,
+                    }
+                )
+            } else {
+                Err(
+                    Error::InvalidTreeLines
+                )
+            }
+        }
+    }
+}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<usize, Error> {
+    let content = input.into_input()?;
+    let trees = read_input(&content)?;
+    Ok(trees.visible_trees())
+}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<usize, Error> {
+    let content = input.into_input()?;
+    let trees = read_input(&content)?;
+    trees.max_scenic_score().ok_or(Error::EmptyInput)
+}
+
+pub struct Day8;
+
+// Every candidate height rescores a whole row and column, so larger changes get slow on the challenge input.
+const PARK_DESIGN_CHANGE: u32 = 1;
+
+impl Solution for Day8 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day8_example.txt"), answers: [Some("21"), Some("8")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    fn variants(&self, part: u8) -> &'static [&'static str] {
+        match part {
+            1 => &["flat"],
+            _ => &[],
+        }
+    }
+
+    fn variant(&self, part: u8, name: &str, input: &str) -> Result<Answer, crate::Error> {
+        match (part, name) {
+            (1, "flat") => Ok(run_challenge1_flat(input)?.into()),
+            _ => Err(crate::Error::UnknownVariant(part, name.to_string())),
+        }
+    }
+
+    fn visualize(&self, part: u8, input: &str) -> Result<Vec<Frame>, crate::Error> {
+        let trees = read_input(input)?;
+        match part {
+            1 => Ok(VisibilityMap(&trees).frames()),
+            2 => Ok(vec![trees.heightmap(HeightmapOverlay { visibility: true, best_spot: true })]),
+            _ => Err(crate::Error::UnknownPart(part)),
+        }
+    }
+
+    fn details(&self, part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        let trees = read_input(input)?;
+        match part {
+            1 => Ok(trees.visibility_breakdown().describe()),
+            2 => Ok(vec![match trees.park_design(PARK_DESIGN_CHANGE) {
+                Some(ParkChange { x, y, from, to, best_score }) => {
+                    format!("replanting the tree at {},{} from {} to {} raises the best score to {}", x, y, from, to, best_score)
+                }
+                None => format!("no tree changed by at most {} raises the best score", PARK_DESIGN_CHANGE),
+            }]),
+            _ => Err(crate::Error::UnknownPart(part)),
+        }
+    }
+}
+
+// The first part on flat rows of heights, for grids large enough that the sweeps matter.
+pub fn run_challenge1_flat(input: impl IntoInput) -> Result<usize, Error> {
+    let content = input.into_input()?;
+    let trees = read_input(&content)?;
+    Ok(FlatTrees::from(&trees).visible_trees())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day08::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let sum = run_challenge1(include_str!("data/day8_example.txt"))?;
+        assert_eq!(sum, 21);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let sum = run_challenge1(include_str!("data/day8_challenge.txt"))?;
+        dbg!(sum);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let size = run_challenge2(include_str!("data/day8_example.txt"))?;
+        assert_eq!(size, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let sum = run_challenge2(include_str!("data/day8_challenge.txt"))?;
+        dbg!(sum);
+        Ok(())
+    }
+
+    #[test]
+    fn flat_visible_trees() -> Result<(), Error> {
+        assert_eq!(run_challenge1_flat(include_str!("data/day8_example.txt"))?, 21);
+
+        let challenge = include_str!("data/day8_challenge.txt");
+        assert_eq!(run_challenge1_flat(challenge)?, run_challenge1(challenge)?);
+        assert_eq!(Day8.variant(1, "flat", challenge).unwrap(), Day8.part1(challenge).unwrap());
+        assert!(matches!(Day8.variant(2, "flat", challenge), Err(crate::Error::UnknownVariant(2, _))));
+
+        // A line break alone is one row without any tree.
+        assert!(matches!(run_challenge1_flat("\n"), Err(Error::EmptyInput)));
+        Ok(())
+    }
+
+    // Rectangular forests of any tree heights, one row per line.
+    fn arbitrary_forest() -> impl proptest::strategy::Strategy<Value = String> {
+        use proptest::strategy::Strategy as _;
+
+        (1..40_usize, 1..40_usize)
+            .prop_flat_map(|(rows, columns)| {
+                proptest::collection::vec(proptest::collection::vec(0..10_u8, columns), rows)
+            })
+            .prop_map(|rows| {
+                rows.iter()
+                    .map(|row| row.iter().map(|height| char::from(b'0' + height)).collect::<String>() + "\n")
+                    .collect()
+            })
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn flat_matches_nested(forest in arbitrary_forest()) {
+            proptest::prop_assert_eq!(run_challenge1_flat(&forest).unwrap(), run_challenge1(&forest).unwrap());
+        }
+    }
+
+    #[test]
+    fn visibility_breakdown() -> Result<(), Error> {
+        let trees = read_input(include_str!("data/day8_example.txt"))?;
+        let breakdown = trees.visibility_breakdown();
+
+        assert_eq!(breakdown.edge, 16);
+        assert_eq!(breakdown.interior, 5);
+        assert_eq!(breakdown.visible(), trees.visible_trees());
+        assert_eq!(
+            (breakdown.from_left, breakdown.from_right, breakdown.from_up, breakdown.from_down),
+            (11, 11, 10, 8)
+        );
+
+        let frames = VisibilityMap(&trees).frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].get(3, 3), VisibilityMap::HIDDEN);
+        assert_eq!(frames[0].get(0, 0), style::HEAT.ramp(1, 3));
+        assert_eq!(trees.tree_visibility(0, 0).directions(), 2);
+
+        assert_eq!(Day8.details(1, include_str!("data/day8_example.txt")).unwrap(), [
+            "visible: 21 (16 on the edge, 5 inside)",
+            "from the left: 11, right: 11, up: 10, down: 8",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn park_design() -> Result<(), Error> {
+        let trees = read_input(include_str!("data/day8_example.txt"))?;
+        assert_eq!(trees.park_design(0), None);
+        assert_eq!(
+            Day8.details(2, include_str!("data/day8_example.txt")).unwrap(),
+            ["replanting the tree at 1,1 from 5 to 4 raises the best score to 12"],
+        );
+
+        for max_change in 1..=3 {
+            let change = trees.park_design(max_change).unwrap();
+            let mut park = trees.clone();
+            park.trees[change.y][change.x] = change.to;
+            assert_eq!(park.max_scenic_score(), Some(change.best_score));
+            assert!(change.best_score > trees.max_scenic_score().unwrap());
+            assert!(change.from.abs_diff(change.to) <= max_change);
+
+            // Brute force over every single-tree change.
+            let brute_force = (0..trees.rows)
+                .flat_map(|y| (0..trees.columns).map(move |x| (x, y)))
+                .flat_map(|(x, y)| {
+                    let from = trees.trees[y][x];
+                    (from.saturating_sub(max_change)..=from.saturating_add(max_change).min(9)).map(move |to| (x, y, to))
+                })
+                .map(|(x, y, to)| {
+                    let mut park = trees.clone();
+                    park.trees[y][x] = to;
+                    park.max_scenic_score().unwrap()
+                })
+                .max();
+            assert_eq!(brute_force, Some(change.best_score));
+        }
+
+        // Any change of at least 9 can reach every height.
+        assert_eq!(trees.park_design(u32::MAX), trees.park_design(9));
+        Ok(())
+    }
+
+    #[test]
+    fn heightmap() -> Result<(), Error> {
+        let trees = read_input(include_str!("data/day8_example.txt"))?;
+        assert_eq!(trees.best_scenic_spot(), Some((2, 3)));
+
+        let frame = trees.heightmap(HeightmapOverlay::default());
+        assert_eq!(frame.get(3, 3), [113, 113, 113]);
+        assert_eq!(frame.get(2, 3), [141, 141, 141]);
+
+        let frame = trees.heightmap(HeightmapOverlay { visibility: true, best_spot: true });
+        assert_eq!(frame.get(3, 3), [113, 113, 113]);
+        assert_eq!(frame.get(0, 0), HeightmapOverlay::visible(85));
+        assert_eq!(frame.get(2, 3), HeightmapOverlay::BEST_SPOT);
+        assert_eq!(Day8.visualize(2, include_str!("data/day8_example.txt")).unwrap(), [frame]);
+        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn png_export() -> Result<(), Error> {
+        let trees = read_input(include_str!("data/day8_example.txt"))?;
+        let path = camino::Utf8PathBuf::from_path_buf(std::env::temp_dir().join("aoc22_day8_heightmap.png")).unwrap();
+        trees.to_png(&path, HeightmapOverlay { visibility: true, best_spot: true })?;
+
+        let image = image::open(&path)?.to_rgb8();
+        assert_eq!(image.dimensions(), (5, 5));
+        assert_eq!(image.get_pixel(2, 3).0, HeightmapOverlay::BEST_SPOT);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
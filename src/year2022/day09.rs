@@ -0,0 +1,721 @@
+use nom::{
+    Finish,
+    IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete,
+    combinator::{all_consuming, map, value},
+    sequence::separated_pair,
+};
+use camino::Utf8Path;
+use rayon::prelude::*;
+use std::{
+    collections::HashSet,
+    fmt,
+    str::FromStr,
+};
+use thiserror::Error;
+use crate::{
+    input::{Input, IntoInput},
+    solution::{Answer, Example, Solution},
+};
+pub use crate::utils::geometry::Vec2 as Pos;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            value(Self::Up, tag("U")),
+            value(Self::Down, tag("D")),
+            value(Self::Left, tag("L")),
+            value(Self::Right, tag("R")),
+        ))(i)
+    }
+
+    fn as_pos(&self) -> Pos {
+        match self {
+            Direction::Up => Pos { x: 0, y: 1 },
+            Direction::Down => Pos { x: 0, y: -1 },
+            Direction::Left => Pos { x: -1, y: 0 },
+            Direction::Right => Pos { x: 1, y: 0 },
+        }
+    }
+
+    fn left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    fn right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Command {
+    direction: Direction,
+    delta: u32,
+}
+
+impl Command {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        map(
+            separated_pair(Direction::parse, complete::space1, complete::u32),
+            |(direction, delta)| Self { direction, delta },
+        )(i)
+    }
+
+    fn iterator(&self) -> impl Iterator<Item=Direction> {
+        std::iter::repeat_n(self.direction, self.delta as usize)
+    }
+}
+
+// As a line of the puzzle input.
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.direction, self.delta)
+    }
+}
+
+pub fn read_input(content: &str) -> Result<Vec<Command>, Error> {
+    let mut commands = Vec::new();
+    for line in content.lines() {
+        let (_, command) = all_consuming(Command::parse)(line)
+            .map_err(|e| e.to_owned())
+            .finish()?;
+
+        commands.push(command);
+    }
+
+    Ok(commands)
+}
+
+// How a knot reacts to the knot ahead of it, given the offset from itself to that knot.
+pub trait FollowRule {
+    fn follow(&self, diff: Pos) -> Pos;
+
+    // Largest distance left between two consecutive knots once the whole rope has moved.
+    fn slack(&self) -> i32;
+}
+
+// The puzzle's rule: catch up one step, diagonally if needed, as soon as the knots stop touching.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Chebyshev;
+
+impl FollowRule for Chebyshev {
+    fn follow(&self, diff: Pos) -> Pos {
+        if diff.chebyshev() > 1 { diff.signum() } else { Pos::ZERO }
+    }
+
+    fn slack(&self) -> i32 {
+        1
+    }
+}
+
+// Lets the rope stretch over one empty cell before pulling the knot along.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Lazy;
+
+impl FollowRule for Lazy {
+    fn follow(&self, diff: Pos) -> Pos {
+        if diff.chebyshev() > 2 { diff.signum() } else { Pos::ZERO }
+    }
+
+    fn slack(&self) -> i32 {
+        2
+    }
+}
+
+struct Grid<R = Chebyshev> {
+    knots: Vec<Pos>,
+    rule: R,
+}
+
+impl Grid {
+    fn new(knots: usize) -> Self {
+        Grid::with_rule(knots, Chebyshev)
+    }
+}
+
+impl<R: FollowRule> Grid<R> {
+    fn with_rule(knots: usize, rule: R) -> Self {
+        Grid { knots: std::iter::repeat_n(Pos::ZERO, knots).collect(), rule }
+    }
+
+    fn move_head(&mut self, direction: Direction) {
+        self.knots[0] += direction.as_pos();
+
+        for i in 1..self.knots.len() {
+            let diff = self.knots[i - 1] - self.knots[i];
+            self.knots[i] += self.rule.follow(diff);
+        }
+
+        paranoid_assert!(
+            self.knots.windows(2).all(|pair| (pair[0] - pair[1]).chebyshev() <= self.rule.slack()),
+            "knots are too far apart: {:?}", self.knots
+        );
+    }
+}
+
+// Inclusive on both corners.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.min.x <= pos.x && pos.x <= self.max.x && self.min.y <= pos.y && pos.y <= self.max.y
+    }
+}
+
+// `min_x,min_y,max_x,max_y`, the rope starting at 0,0.
+impl FromStr for Bounds {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidBounds(s.to_string());
+        let values = s.split(',').map(|value| value.trim().parse::<i32>()).collect::<Result<Vec<_>, _>>().map_err(|_| invalid())?;
+        let [min_x, min_y, max_x, max_y] = values[..] else {
+            return Err(invalid());
+        };
+
+        let bounds = Bounds { min: Pos { x: min_x, y: min_y }, max: Pos { x: max_x, y: max_y } };
+        if bounds.contains(Pos::ZERO) { Ok(bounds) } else { Err(invalid()) }
+    }
+}
+
+// The head hit a wall while running a command, the rest of which was dropped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clamp {
+    pub command: usize,
+    pub head: Pos,
+    pub dropped: u32,
+}
+
+// Every knot before the first move, then after each single-cell move.
+#[derive(Debug)]
+pub struct RopeTrace {
+    pub steps: Vec<Vec<Pos>>,
+    pub clamps: Vec<Clamp>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RopeStats {
+    pub collisions: usize,
+    pub max_spread: i32,
+    pub spread_timeline: Vec<i32>,
+}
+
+pub fn trace(commands: &[Command], knots: usize) -> RopeTrace {
+    trace_bounded(commands, knots, None)
+}
+
+// Without bounds, no clamp is ever recorded.
+pub fn trace_bounded(commands: &[Command], knots: usize, bounds: Option<Bounds>) -> RopeTrace {
+    let mut grid = Grid::new(knots);
+    let mut steps = vec![grid.knots.clone()];
+    let mut clamps = Vec::new();
+
+    for (index, command) in commands.iter().enumerate() {
+        for moved in 0..command.delta {
+            let head = grid.knots[0] + command.direction.as_pos();
+            if bounds.is_some_and(|bounds| !bounds.contains(head)) {
+                clamps.push(Clamp { command: index, head: grid.knots[0], dropped: command.delta - moved });
+                break;
+            }
+
+            grid.move_head(command.direction);
+            steps.push(grid.knots.clone());
+        }
+    }
+
+    RopeTrace { steps, clamps }
+}
+
+impl RopeTrace {
+    pub fn tail_visited(&self) -> HashSet<Pos> {
+        self.steps.iter().flat_map(|knots| knots.last()).cloned().collect()
+    }
+
+    // Spread is the largest side of the box bounding all the knots.
+    fn spread(knots: &[Pos]) -> i32 {
+        let min_x = knots.iter().map(|p| p.x).min().unwrap_or_default();
+        let max_x = knots.iter().map(|p| p.x).max().unwrap_or_default();
+        let min_y = knots.iter().map(|p| p.y).min().unwrap_or_default();
+        let max_y = knots.iter().map(|p| p.y).max().unwrap_or_default();
+
+        (max_x - min_x).max(max_y - min_y)
+    }
+
+    // A collision is a knot sharing its cell with an earlier knot of the rope, counted on every step.
+    fn collisions(knots: &[Pos]) -> usize {
+        knots.len() - knots.iter().collect::<HashSet<_>>().len()
+    }
+
+    // FNV-1a over the sorted tail-visited cells then the final knots, stable across runs and builds.
+    pub fn digest(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        // Column by column, the order digests have always been computed in.
+        let mut visited = self.tail_visited().into_iter().collect::<Vec<_>>();
+        visited.sort_by_key(|pos| (pos.x, pos.y));
+        let final_knots = self.steps.last().cloned().unwrap_or_default();
+
+        visited
+            .iter()
+            .chain(final_knots.iter())
+            .flat_map(|pos| pos.x.to_le_bytes().into_iter().chain(pos.y.to_le_bytes()))
+            .fold(OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+    }
+
+    pub fn stats(&self) -> RopeStats {
+        let spread_timeline = self.steps.iter().map(|knots| Self::spread(knots)).collect::<Vec<_>>();
+
+        RopeStats {
+            collisions: self.steps.iter().map(|knots| Self::collisions(knots)).sum(),
+            max_spread: spread_timeline.iter().cloned().max().unwrap_or_default(),
+            spread_timeline,
+        }
+    }
+}
+
+pub fn tail_visited_with<R: FollowRule>(commands: &[Command], knots: usize, rule: R) -> HashSet<Pos> {
+    let mut grid = Grid::with_rule(knots, rule);
+    let mut tail_pos = HashSet::new();
+
+    for direction in commands.iter().flat_map(Command::iterator) {
+        grid.move_head(direction);
+        tail_pos.insert(grid.knots[knots - 1]);
+    }
+
+    tail_pos
+}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<HashSet<Pos>, Error> {
+    let content = input.into_input()?;
+    let commands = read_input(&content)?;
+    Ok(tail_visited_with(&commands, 2, Chebyshev))
+}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<HashSet<Pos>, Error> {
+    let content = input.into_input()?;
+    let commands = read_input(&content)?;
+    Ok(tail_visited_with(&commands, 10, Chebyshev))
+}
+
+// Lindenmayer systems drawn by a turtle: F moves the head one cell forward, + and - turn it left and
+// right, any other symbol only takes part in the rewriting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    // Doubles the number of moves with each rewrite.
+    Dragon,
+    // Fills a square of 2^depth cells a side, quadrupling the moves with each rewrite.
+    Hilbert,
+}
+
+impl Pattern {
+    // Enough for millions of moves.
+    pub fn max_depth(self) -> u32 {
+        match self {
+            Pattern::Dragon => 24,
+            Pattern::Hilbert => 12,
+        }
+    }
+
+    fn axiom(self) -> &'static str {
+        match self {
+            Pattern::Dragon => "FX",
+            Pattern::Hilbert => "A",
+        }
+    }
+
+    fn rule(self, symbol: char) -> Option<&'static str> {
+        match (self, symbol) {
+            (Pattern::Dragon, 'X') => Some("X+YF+"),
+            (Pattern::Dragon, 'Y') => Some("-FX-Y"),
+            (Pattern::Hilbert, 'A') => Some("+BF-AFA-FB+"),
+            (Pattern::Hilbert, 'B') => Some("-AF+BFB+FA-"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Pattern::Dragon => "dragon",
+            Pattern::Hilbert => "hilbert",
+        })
+    }
+}
+
+// Starts heading right, and merges straight runs into a single command.
+pub fn generate(pattern: Pattern, depth: u32) -> Result<Vec<Command>, Error> {
+    if depth > pattern.max_depth() {
+        return Err(Error::TooDeep(pattern, pattern.max_depth()));
+    }
+
+    let mut symbols = pattern.axiom().to_string();
+    for _ in 0..depth {
+        let mut rewritten = String::with_capacity(symbols.len() * 3);
+        for symbol in symbols.chars() {
+            match pattern.rule(symbol) {
+                Some(replacement) => rewritten.push_str(replacement),
+                None => rewritten.push(symbol),
+            }
+        }
+        symbols = rewritten;
+    }
+
+    let mut direction = Direction::Right;
+    let mut commands = Vec::<Command>::new();
+    for symbol in symbols.chars() {
+        match symbol {
+            'F' => match commands.last_mut() {
+                Some(last) if last.direction == direction => last.delta += 1,
+                _ => commands.push(Command { direction, delta: 1 }),
+            },
+            '+' => direction = direction.left(),
+            '-' => direction = direction.right(),
+            _ => {}
+        }
+    }
+
+    Ok(commands)
+}
+
+// Tail-visited counts of one command file, for 2 and 10 knots.
+#[derive(Debug)]
+pub struct FileReport {
+    pub input: Input,
+    pub visited: Result<(usize, usize), Error>,
+}
+
+#[derive(Debug)]
+pub struct BatchReport {
+    pub files: Vec<FileReport>,
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.files.iter().map(|file| file.input.to_string().len()).max().unwrap_or_default();
+        for file in &self.files {
+            match &file.visited {
+                Ok((short, long)) => writeln!(f, "{:<width$}  {:>8}  {:>8}", file.input, short, long)?,
+                Err(e) => writeln!(f, "{:<width$}  error: {}", file.input, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Files are independent, so they are spread over the rayon pool. Reports keep the order of the inputs.
+pub fn simulate_batch(inputs: Vec<Input>) -> BatchReport {
+    let files = inputs
+        .into_par_iter()
+        .map(|input| {
+            let visited = input.clone().into_input().map_err(Error::from).and_then(|content| {
+                let commands = read_input(&content)?;
+                Ok((tail_visited_with(&commands, 2, Chebyshev).len(), tail_visited_with(&commands, 10, Chebyshev).len()))
+            });
+            FileReport { input, visited }
+        })
+        .collect();
+
+    BatchReport { files }
+}
+
+// Every .txt file of the directory, by path.
+pub fn simulate_dir(dir: &Utf8Path) -> Result<BatchReport, Error> {
+    let mut paths = Vec::new();
+    for entry in dir.read_dir_utf8()? {
+        let path = entry?.path().to_path_buf();
+        if path.extension() == Some("txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(simulate_batch(paths.into_iter().map(Input::Path).collect()))
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day9_example.txt"), answers: [Some("13"), Some("1")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.len().into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.len().into())
+    }
+
+    fn details(&self, part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        let knots = match part {
+            1 => 2,
+            2 => 10,
+            _ => return Err(crate::Error::UnknownPart(part)),
+        };
+        let stats = trace(&read_input(input)?, knots).stats();
+        Ok(vec![
+            format!("knots sharing a cell: {} times", stats.collisions),
+            format!("largest spread: {}", stats.max_spread),
+        ])
+    }
+
+    fn variants(&self, part: u8) -> &'static [&'static str] {
+        match part {
+            1 | 2 => &["lazy"],
+            _ => &[],
+        }
+    }
+
+    fn variant(&self, part: u8, name: &str, input: &str) -> Result<Answer, crate::Error> {
+        let knots = match (part, name) {
+            (1, "lazy") => 2,
+            (2, "lazy") => 10,
+            _ => return Err(crate::Error::UnknownVariant(part, name.to_string())),
+        };
+        Ok(tail_visited_with(&read_input(input)?, knots, Lazy).len().into())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[error("The {0} pattern goes up to depth {1}")]
+    TooDeep(Pattern, u32),
+    #[error("Invalid bounds '{0}', expected min_x,min_y,max_x,max_y around 0,0")]
+    InvalidBounds(String),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ Error::Nom(..) => crate::Error::parse(e),
+            e @ (Error::TooDeep(..) | Error::InvalidBounds(..)) => crate::Error::validation(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day09::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day9_example.txt"))?;
+        assert_eq!(result.len(), 13);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day9_challenge.txt"))?;
+        dbg!(result.len());
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day9_example.txt"))?;
+        assert_eq!(result.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example2() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day9_example2.txt"))?;
+        assert_eq!(result.len(), 36);
+        Ok(())
+    }
+
+    #[test]
+    fn follow_rules() -> Result<(), Error> {
+        let commands = read_input(include_str!("data/day9_example2.txt"))?;
+        assert_eq!(tail_visited_with(&commands, 10, Chebyshev).len(), 36);
+
+        assert_eq!(tail_visited_with(&commands, 10, Lazy).len(), 6);
+        assert_eq!(tail_visited_with(&commands, 2, Chebyshev).len(), 88);
+        assert_eq!(tail_visited_with(&commands, 2, Lazy).len(), 79);
+
+        assert_eq!(Lazy.follow(Pos { x: 2, y: -2 }), Pos::ZERO);
+        assert_eq!(Lazy.follow(Pos { x: 3, y: -1 }), Pos { x: 1, y: -1 });
+
+        let example = include_str!("data/day9_example2.txt");
+        assert_eq!(Day9.variant(1, "lazy", example).unwrap(), "79");
+        assert_eq!(Day9.variant(2, "lazy", example).unwrap(), "6");
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day9_challenge.txt"))?;
+        dbg!(result.len());
+        Ok(())
+    }
+
+    #[test]
+    fn rope_stats() -> Result<(), Error> {
+        let commands = read_input(include_str!("data/day9_example2.txt"))?;
+        let rope = trace(&commands, 10);
+        assert_eq!(rope.tail_visited(), run_challenge2(include_str!("data/day9_example2.txt"))?);
+
+        let stats = rope.stats();
+        assert_eq!(stats.spread_timeline.len(), rope.steps.len());
+        assert_eq!(stats.spread_timeline[0], 0);
+        assert_eq!(stats.max_spread, 9);
+
+        let commands = read_input("R 1\nL 1")?;
+        let stats = trace(&commands, 2).stats();
+        assert_eq!(stats, RopeStats { collisions: 2, max_spread: 1, spread_timeline: vec![0, 1, 0] });
+
+        assert_eq!(Day9.details(2, include_str!("data/day9_example2.txt")).unwrap()[1], "largest spread: 9");
+        assert_eq!(Day9.details(1, "R 1\nL 1").unwrap(), ["knots sharing a cell: 2 times", "largest spread: 1"]);
+        Ok(())
+    }
+
+    #[test]
+    fn trace_digest() -> Result<(), Error> {
+        let commands = read_input(include_str!("data/day9_example.txt"))?;
+
+        let digest = trace(&commands, 2).digest();
+        assert_eq!(digest, trace(&commands, 2).digest());
+        assert_eq!(digest, 0xa22a4334f1acf611);
+        assert_ne!(digest, trace(&commands, 10).digest());
+        assert_ne!(digest, trace(&commands[1..], 2).digest());
+        Ok(())
+    }
+
+    #[test]
+    fn batch_simulation() -> Result<(), Error> {
+        let dir = crate::utils::unique_temp_path("aoc22_day9_batch");
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("a.txt"), include_str!("data/day9_example.txt"))?;
+        std::fs::write(dir.join("b.txt"), include_str!("data/day9_example2.txt"))?;
+        std::fs::write(dir.join("c.txt"), "X 3\n")?;
+        std::fs::write(dir.join("notes.md"), "")?;
+
+        let report = simulate_dir(&dir)?;
+        std::fs::remove_dir_all(&dir)?;
+        let names = report.files.iter().map(|file| file.input.to_string()).collect::<Vec<_>>();
+        assert_eq!(names, ["a.txt", "b.txt", "c.txt"].map(|name| dir.join(name).to_string()));
+        assert_eq!(report.files[0].visited.as_ref().ok(), Some(&(13, 1)));
+        assert_eq!(report.files[1].visited.as_ref().ok().map(|visited| visited.1), Some(36));
+        assert!(matches!(report.files[2].visited, Err(Error::Nom(_))));
+        assert!(report.to_string().lines().nth(2).unwrap().contains("error:"));
+
+        let report = simulate_batch(vec![Input::Text("R 4\n".to_string())]);
+        assert_eq!(report.files[0].visited.as_ref().ok(), Some(&(4, 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn bounded_trace() -> Result<(), Error> {
+        let commands = read_input(include_str!("data/day9_example.txt"))?;
+        let bounds = Bounds { min: Pos::ZERO, max: Pos { x: 3, y: 3 } };
+
+        let rope = trace_bounded(&commands, 2, Some(bounds));
+        assert!(rope.steps.iter().flatten().all(|pos| bounds.contains(*pos)));
+        assert_eq!(rope.clamps[0], Clamp { command: 0, head: Pos { x: 3, y: 0 }, dropped: 1 });
+        assert_eq!(rope.clamps[1], Clamp { command: 1, head: Pos { x: 3, y: 3 }, dropped: 1 });
+
+        let unbounded = trace(&commands, 2);
+        assert!(unbounded.clamps.is_empty());
+        assert_eq!(trace_bounded(&commands, 2, Some(Bounds { min: Pos { x: -10, y: -10 }, max: Pos { x: 10, y: 10 } })).steps, unbounded.steps);
+
+        assert_eq!("0, 0,3,3".parse::<Bounds>()?, bounds);
+        assert!(matches!("0,0,3".parse::<Bounds>(), Err(Error::InvalidBounds(_))));
+        assert!(matches!("1,1,3,3".parse::<Bounds>(), Err(Error::InvalidBounds(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn l_system_patterns() -> Result<(), Error> {
+        let text = |commands: Vec<Command>| commands.iter().map(Command::to_string).collect::<Vec<_>>().join("\n");
+        assert_eq!(text(generate(Pattern::Dragon, 0)?), "R 1");
+        assert_eq!(text(generate(Pattern::Dragon, 2)?), "R 1\nU 1\nL 1\nU 1");
+        assert_eq!(text(generate(Pattern::Hilbert, 1)?), "U 1\nR 1\nD 1");
+        assert_eq!(text(generate(Pattern::Hilbert, 2)?), "R 1\nU 1\nL 1\nU 2\nR 1\nD 1\nR 1\nU 1\nR 1\nD 2\nL 1\nD 1\nR 1");
+
+        let dragon = generate(Pattern::Dragon, 12)?;
+        assert_eq!(dragon.iter().map(|command| command.delta).sum::<u32>(), 4096);
+        assert_eq!(read_input(&text(generate(Pattern::Dragon, 12)?))?.len(), dragon.len());
+
+        // The head goes through every cell of the square once.
+        let heads = trace(&generate(Pattern::Hilbert, 5)?, 1).steps.into_iter().map(|knots| knots[0]).collect::<Vec<_>>();
+        assert_eq!(heads.len(), 1024);
+        assert_eq!(heads.iter().collect::<HashSet<_>>().len(), 1024);
+        assert!(heads.iter().all(|head| (0..32).contains(&head.x) && (0..32).contains(&head.y)));
+
+        assert!(matches!(generate(Pattern::Hilbert, 13), Err(Error::TooDeep(Pattern::Hilbert, 12))));
+        Ok(())
+    }
+
+    #[test]
+    fn generated_moves() -> Result<(), Error> {
+        for (pattern, depth, moves) in [(Pattern::Dragon, 16, 1 << 16), (Pattern::Hilbert, 8, (1 << 16) - 1)] {
+            let commands = generate(pattern, depth)?;
+            assert_eq!(commands.iter().map(|command| command.delta).sum::<u32>(), moves, "{}", pattern);
+            // Runs in the same direction are merged, and the curve only ever turns by a quarter.
+            assert!(commands.windows(2).all(|pair| {
+                [pair[0].direction.left(), pair[0].direction.right()].contains(&pair[1].direction)
+            }));
+
+            let heads = trace(&commands, 1).steps.into_iter().map(|knots| knots[0]).collect::<Vec<_>>();
+            let edges = heads.windows(2).map(|pair| {
+                let (a, b) = ((pair[0].x, pair[0].y), (pair[1].x, pair[1].y));
+                (a.min(b), a.max(b))
+            });
+            // Neither curve goes back over its own path.
+            assert_eq!(edges.collect::<HashSet<_>>().len(), moves as usize, "{}", pattern);
+        }
+        Ok(())
+    }
+}
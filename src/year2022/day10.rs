@@ -0,0 +1,896 @@
+use nom::{
+    Finish,
+    IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete,
+    combinator::{all_consuming, map, value},
+    sequence::preceded,
+};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Formatter},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+use thiserror::Error;
+use crate::{
+    answers,
+    input::IntoInput,
+    render::style,
+    solution::{Answer, Example, Solution},
+    visualize::{self, Frame, Visualize},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Command {
+    NoOp,
+    Addx(i64),
+}
+
+impl Command {
+    fn parse(i: &str) -> IResult<&str, Command> {
+        let parse_noop = value(Command::NoOp, tag("noop"));
+        let parse_addx = map(preceded(tag("addx "), complete::i64), Command::Addx);
+
+        alt((
+            parse_noop,
+            parse_addx
+        ))(i)
+    }
+
+    fn cycles(&self) -> usize {
+        match self {
+            Command::NoOp => 1,
+            Command::Addx(_) => 2,
+        }
+    }
+}
+
+// Bytecode: one opcode byte per command, addx followed by its operand as a zigzag LEB128 varint.
+const OPCODE_NOOP: u8 = 0x00;
+const OPCODE_ADDX: u8 = 0x01;
+
+pub fn assemble(commands: &[Command]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(commands.len() * 2);
+    for command in commands {
+        match command {
+            Command::NoOp => bytes.push(OPCODE_NOOP),
+            Command::Addx(delta) => {
+                bytes.push(OPCODE_ADDX);
+                let mut operand = ((delta << 1) ^ (delta >> 63)) as u64;
+                loop {
+                    let byte = (operand & 0x7f) as u8;
+                    operand >>= 7;
+                    if operand == 0 {
+                        bytes.push(byte);
+                        break;
+                    }
+                    bytes.push(byte | 0x80);
+                }
+            }
+        }
+    }
+    bytes
+}
+
+pub fn disassemble(bytes: &[u8]) -> Result<VecDeque<Command>, Error> {
+    let mut commands = VecDeque::new();
+    let mut offset = 0;
+
+    while let Some(opcode) = bytes.get(offset) {
+        let start = offset;
+        offset += 1;
+
+        match *opcode {
+            OPCODE_NOOP => commands.push_back(Command::NoOp),
+            OPCODE_ADDX => {
+                let mut operand = 0_u64;
+                for shift in (0..64).step_by(7) {
+                    let byte = *bytes.get(offset).ok_or(Error::InvalidOperand(start))?;
+                    offset += 1;
+                    // The tenth byte only has room for the 64th bit, and cannot go on.
+                    if shift == 63 && byte > 1 {
+                        return Err(Error::InvalidOperand(start));
+                    }
+                    operand |= ((byte & 0x7f) as u64) << shift;
+                    if byte & 0x80 == 0 {
+                        break;
+                    }
+                }
+                commands.push_back(Command::Addx((operand >> 1) as i64 ^ -((operand & 1) as i64)));
+            }
+            opcode => return Err(Error::UnknownOpcode(opcode, start)),
+        }
+    }
+
+    Ok(commands)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Validation {
+    Fits,
+    Truncated {
+        cycles: usize,
+        truncated_commands: usize,
+    },
+}
+
+impl fmt::Display for Validation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Validation::Fits => write!(f, "fits the {} cycles of the screen", Machine::CRT_CYCLES),
+            Validation::Truncated { cycles, truncated_commands } => write!(
+                f,
+                "warning: {} cycles, the last {} commands run past the {} of the screen",
+                cycles, truncated_commands, Machine::CRT_CYCLES
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Program {
+    commands: VecDeque<Command>,
+}
+
+impl Program {
+    pub fn read(content: &str) -> Result<Self, Error> {
+        Ok(Self { commands: read_input(content)? })
+    }
+
+    pub fn cycle_count(&self) -> usize {
+        self.commands.iter().map(Command::cycles).sum()
+    }
+
+    // Assumes straight-line code, which is all the instruction set allows.
+    pub fn max_register_excursion(&self) -> RangeInclusive<i64> {
+        let mut register = Machine::INITIAL_REGISTER;
+        let (mut min, mut max) = (register, register);
+
+        for command in self.commands.iter() {
+            if let Command::Addx(delta) = command {
+                register += delta;
+                min = min.min(register);
+                max = max.max(register);
+            }
+        }
+
+        min..=max
+    }
+
+    pub fn validate(&self) -> Validation {
+        let cycles = self.cycle_count();
+        if cycles <= Machine::CRT_CYCLES {
+            Validation::Fits
+        } else {
+            let mut elapsed = 0;
+            let fitting_commands = self.commands
+                .iter()
+                .take_while(|command| {
+                    elapsed += command.cycles();
+                    elapsed <= Machine::CRT_CYCLES
+                })
+                .count();
+
+            Validation::Truncated {
+                cycles,
+                truncated_commands: self.commands.len() - fitting_commands,
+            }
+        }
+    }
+}
+
+pub fn read_input(content: &str) -> Result<VecDeque<Command>, Error> {
+    let mut commands = VecDeque::new();
+    for line in content.lines() {
+        let (_, command) = all_consuming(Command::parse)(line)
+            .map_err(|e| e.to_owned())
+            .finish()?;
+
+        commands.push_back(command);
+    }
+
+    Ok(commands)
+}
+
+// The screen, and the register file: the X each program ended with, in the order they were given.
+#[derive(Debug)]
+pub struct Machine {
+    registers: Vec<i64>,
+    crt: [[bool; 40]; 6],
+}
+
+impl Machine {
+    const INITIAL_REGISTER: i64 = 1;
+    const CRT_CYCLES: usize = 40 * 6;
+
+    fn new() -> Self {
+        Self { registers: Vec::new(), crt: [[false; 40]; 6] }
+    }
+
+    pub fn execute_bytes(bytes: &[u8]) -> Result<(i64, Machine), Error> {
+        run_loop(disassemble(bytes)?)
+    }
+
+    pub fn registers(&self) -> &[i64] {
+        &self.registers
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Theme {
+    #[default]
+    Ascii,
+    Blocks,
+    // Packs 2x4 pixels per character, halving the width.
+    Braille,
+}
+
+impl Theme {
+    // Dot bits of a braille character, indexed by row then column.
+    const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+    fn pixel(self, lit: bool) -> char {
+        match (self, lit) {
+            (Theme::Blocks, true) => '█',
+            (Theme::Blocks, false) => ' ',
+            (_, true) => '#',
+            (_, false) => '.',
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Ascii => "ascii",
+            Theme::Blocks => "blocks",
+            Theme::Braille => "braille",
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Theme::Ascii, Theme::Blocks, Theme::Braille]
+            .into_iter()
+            .find(|theme| theme.name() == s)
+            .ok_or_else(|| Error::UnknownTheme(s.to_string()))
+    }
+}
+
+pub struct Themed<'a>(&'a Machine, Theme);
+
+impl<'a> fmt::Display for Themed<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Themed(machine, theme) = self;
+        match theme {
+            Theme::Ascii | Theme::Blocks =>
+                for line in machine.crt {
+                    for pixel in line {
+                        write!(f, "{}", theme.pixel(pixel))?;
+                    }
+                    f.write_str("\n")?;
+                },
+            Theme::Braille =>
+                for rows in machine.crt.chunks(4) {
+                    for x in (0..40).step_by(2) {
+                        let mut bits = 0;
+                        for (dy, row) in rows.iter().enumerate() {
+                            for dx in 0..2 {
+                                if row[x + dx] {
+                                    bits |= Theme::BRAILLE_DOTS[dy][dx];
+                                }
+                            }
+                        }
+                        write!(f, "{}", char::from_u32(0x2800 + bits).unwrap())?;
+                    }
+                    f.write_str("\n")?;
+                },
+        }
+
+        Ok(())
+    }
+}
+
+impl Machine {
+    pub fn themed(&self, theme: Theme) -> Themed<'_> {
+        Themed(self, theme)
+    }
+}
+
+// Pictures drawn like the characters: dim dots for the unlit pixels of ASCII, nothing for blocks,
+// and braille cells as bright as the number of their dots that are lit.
+impl<'a> Visualize for Themed<'a> {
+    fn frames(&self) -> Vec<Frame> {
+        let Themed(machine, theme) = self;
+        match theme {
+            Theme::Blocks => machine.frames(),
+            Theme::Ascii => {
+                let mut frame = machine.frames().remove(0);
+                let dot = visualize::gradient(style::CRT.background, style::CRT.low, 1, 3);
+                for (y, line) in machine.crt.iter().enumerate() {
+                    for (x, _) in line.iter().enumerate().filter(|(_, lit)| !**lit) {
+                        frame.set(x, y, dot);
+                    }
+                }
+                vec![frame]
+            }
+            Theme::Braille => {
+                let mut frame = Frame::new(20, 2, style::CRT.background);
+                for (cell_y, rows) in machine.crt.chunks(4).enumerate() {
+                    for cell_x in 0..20 {
+                        let dots = rows.iter().flat_map(|row| &row[cell_x * 2..cell_x * 2 + 2]).filter(|lit| **lit).count();
+                        frame.set(cell_x, cell_y, visualize::gradient(style::CRT.background, style::CRT.high, dots, 8));
+                    }
+                }
+                vec![frame]
+            }
+        }
+    }
+}
+
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.themed(Theme::default()))
+    }
+}
+
+impl Visualize for Machine {
+    fn frames(&self) -> Vec<Frame> {
+        let mut frame = Frame::new(40, 6, style::CRT.background);
+        for (y, line) in self.crt.iter().enumerate() {
+            for (x, _) in line.iter().enumerate().filter(|(_, lit)| **lit) {
+                frame.set(x, y, style::CRT.high);
+            }
+        }
+        vec![frame]
+    }
+}
+
+// Whether the screen shows the expected letters, whatever characters draw either.
+pub fn crt_matches(machine: &Machine, expected: &str) -> bool {
+    answers::normalize_crt(&machine.to_string()) == answers::normalize_crt(expected)
+}
+
+pub fn assert_crt_matches(machine: &Machine, expected: &str) {
+    assert!(
+        crt_matches(machine, expected),
+        "CRT mismatch\n--- rendered ---\n{}--- expected ---\n{}",
+        machine,
+        expected
+    );
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "event", rename_all = "snake_case"))]
+pub enum Event {
+    Fetch { cycle: usize, command: Command },
+    Execute { cycle: usize, command: Command, register: i64 },
+    PixelDrawn { cycle: usize, x: usize, y: usize },
+    Sample { cycle: usize, register: i64, strength: i64 },
+}
+
+impl Event {
+    pub fn cycle(&self) -> usize {
+        match self {
+            Event::Fetch { cycle, .. }
+            | Event::Execute { cycle, .. }
+            | Event::PixelDrawn { cycle, .. }
+            | Event::Sample { cycle, .. } => *cycle,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub fn events_to_json(events: &[Event]) -> Result<String, Error> {
+    Ok(serde_json::to_string_pretty(events)?)
+}
+
+// Everything the machine did while running the program, in cycle order.
+pub fn log_events(input: impl IntoInput) -> Result<Vec<Event>, Error> {
+    let content = input.into_input()?;
+    let mut events = Vec::new();
+    run_loop_logged(read_input(&content)?, Some(&mut events))?;
+    Ok(events)
+}
+
+enum Action {
+    AwaitCommand,
+    Defer(Command, usize),
+}
+
+// One program being executed, with its own X register.
+struct Cpu {
+    commands: VecDeque<Command>,
+    register: i64,
+    action: Action,
+}
+
+impl Cpu {
+    fn new(commands: VecDeque<Command>) -> Self {
+        Cpu { commands, register: Machine::INITIAL_REGISTER, action: Action::AwaitCommand }
+    }
+
+    fn is_lighten_pixel(&self, x: i64) -> bool {
+        self.register - 1 <= x && x <= self.register + 1
+    }
+
+    // Runs the end of a cycle, once the CRT has been drawn with X as it was during that cycle.
+    // Returns false when the program is over, there being no command left to start.
+    fn step(&mut self, cycle: usize, log: &mut impl FnMut(Event)) -> bool {
+        match std::mem::replace(&mut self.action, Action::AwaitCommand) {
+            Action::AwaitCommand => match self.commands.pop_front() {
+                None => return false,
+                Some(command) => {
+                    log(Event::Fetch { cycle, command: command.clone() });
+                    let cycles = command.cycles();
+                    if cycles > 1 {
+                        self.action = Action::Defer(command, cycles - 1);
+                    } else {
+                        log(Event::Execute { cycle, command, register: self.register });
+                    }
+                }
+            }
+            Action::Defer(command, cycles) =>
+                if cycles == 1 {
+                    match command {
+                        Command::NoOp => (),
+                        Command::Addx(delta) => self.register += delta,
+                    }
+                    log(Event::Execute { cycle, command, register: self.register });
+                } else {
+                    self.action = Action::Defer(command, cycles - 1);
+                }
+        }
+
+        true
+    }
+}
+
+fn run_loop(commands: VecDeque<Command>) -> Result<(i64, Machine), Error> {
+    run_loop_logged(commands, None)
+}
+
+fn run_loop_logged(commands: VecDeque<Command>, events: Option<&mut Vec<Event>>) -> Result<(i64, Machine), Error> {
+    run_scheduled(vec![commands], events)
+}
+
+// Round robin: each cycle goes to the next program that is not over, which draws that cycle's pixel
+// with its own X then steps. A single program gets every cycle, as in the puzzle. Signal strength is
+// sampled from whichever program owns the cycle.
+fn run_scheduled(programs: Vec<VecDeque<Command>>, mut events: Option<&mut Vec<Event>>) -> Result<(i64, Machine), Error> {
+    let mut log = |event: Event| if let Some(events) = events.as_mut() {
+        events.push(event);
+    };
+
+    #[cfg(feature = "paranoid")]
+    let program_cycles = programs.iter().flatten().map(Command::cycles).sum::<usize>() + programs.len();
+
+    let mut machine = Machine::new();
+    let mut cpus = programs.into_iter().map(Cpu::new).collect::<Vec<_>>();
+    let mut running = vec![true; cpus.len()];
+    let mut turn = 0_usize;
+    let mut cycle = 1_usize;
+
+    let mut strength = 0_i64;
+
+    while let Some(current) = (0..cpus.len()).map(|offset| (turn + offset) % cpus.len()).find(|index| running[*index]) {
+        paranoid_assert!(cycle <= program_cycles, "cycle {} runs past the programs", cycle);
+        let cpu = &mut cpus[current];
+
+        let x = (cycle - 1) % 40;
+        if cycle <= Machine::CRT_CYCLES && cpu.is_lighten_pixel(x as i64) {
+            machine.crt[(cycle - 1) / 40][x] = true;
+            log(Event::PixelDrawn { cycle, x, y: (cycle - 1) / 40 });
+        }
+
+        if cycle == 20 || cycle > 20 && (cycle - 20).is_multiple_of(40) {
+            let cycle_strength = cycle as i64 * cpu.register;
+            strength += cycle_strength;
+            log(Event::Sample { cycle, register: cpu.register, strength: cycle_strength });
+            tracing::debug!("Cycle {} | X={} | Cycle Strength={} | Total Strength={}", cycle, cpu.register, cycle_strength, strength);
+        }
+
+        running[current] = cpu.step(cycle, &mut log);
+        turn = current + 1;
+        cycle += 1;
+    }
+
+    machine.registers = cpus.iter().map(|cpu| cpu.register).collect();
+    Ok((strength, machine))
+}
+
+// Experimental: both programs share the CRT, taking turns cycle by cycle, so each draws every other
+// pixel until one of them is over.
+pub fn run_interleaved(first: impl IntoInput, second: impl IntoInput) -> Result<Machine, Error> {
+    let programs = vec![read_input(&first.into_input()?)?, read_input(&second.into_input()?)?];
+    Ok(run_scheduled(programs, None)?.1)
+}
+
+// Value of X during each cycle, as the CRT sees it.
+fn register_timeline(commands: &VecDeque<Command>) -> Vec<i64> {
+    let mut register = Machine::INITIAL_REGISTER;
+    let mut timeline = Vec::new();
+    for command in commands {
+        timeline.extend(std::iter::repeat_n(register, command.cycles()));
+        if let Command::Addx(delta) = command {
+            register += delta;
+        }
+    }
+    timeline
+}
+
+// First cycles at which two programs disagree. A register is None once its program is over.
+#[derive(Debug)]
+pub struct ProgramDiff {
+    pub register: Option<(usize, Option<i64>, Option<i64>)>,
+    pub pixel: Option<(usize, usize, usize)>,
+    left: Machine,
+    right: Machine,
+}
+
+pub fn diff_programs(left: impl IntoInput, right: impl IntoInput) -> Result<ProgramDiff, Error> {
+    let (left, right) = (read_input(&left.into_input()?)?, read_input(&right.into_input()?)?);
+    let (left_timeline, right_timeline) = (register_timeline(&left), register_timeline(&right));
+
+    let register = (0..left_timeline.len().max(right_timeline.len()))
+        .map(|index| (index + 1, left_timeline.get(index).copied(), right_timeline.get(index).copied()))
+        .find(|(_, left, right)| left != right);
+
+    let (left, right) = (run_loop(left)?.1, run_loop(right)?.1);
+    let pixel = (0..Machine::CRT_CYCLES)
+        .map(|index| (index + 1, index % 40, index / 40))
+        .find(|(_, x, y)| left.crt[*y][*x] != right.crt[*y][*x]);
+
+    Ok(ProgramDiff { register, pixel, left, right })
+}
+
+// Both screens side by side, then a column marking the pixels that differ.
+impl fmt::Display for ProgramDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (left, right) in self.left.crt.iter().zip(self.right.crt.iter()) {
+            let row = |pixels: &[bool; 40]| pixels.iter().map(|lit| Theme::Ascii.pixel(*lit)).collect::<String>();
+            let marks = left.iter().zip(right.iter()).map(|(l, r)| if l == r { ' ' } else { '^' }).collect::<String>();
+            writeln!(f, "{} | {} | {}", row(left), row(right), marks.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge1(input: impl IntoInput) -> Result<i64, Error> {
+    let content = input.into_input()?;
+    let commands = read_input(&content)?;
+    Ok(run_loop(commands)?.0)
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge2(input: impl IntoInput) -> Result<Machine, Error> {
+    let content = input.into_input()?;
+    let commands = read_input(&content)?;
+    Ok(run_loop(commands)?.1)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn example(&self) -> Example {
+        Example {
+            input: include_str!("data/day10_example.txt"),
+            answers: [
+                Some("13140"),
+                Some(concat!(
+                "##..##..##..##..##..##..##..##..##..##..\n",
+                "###...###...###...###...###...###...###.\n",
+                "####....####....####....####....####....\n",
+                "#####.....#####.....#####.....#####.....\n",
+                "######......######......######......####\n",
+                "#######.......#######.......#######.....",
+            )),
+            ],
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.to_string().trim_end().into())
+    }
+
+    // The screen drawn with another theme than ASCII.
+    fn variants(&self, part: u8) -> &'static [&'static str] {
+        match part {
+            2 => &["blocks", "braille"],
+            _ => &[],
+        }
+    }
+
+    fn variant(&self, part: u8, name: &str, input: &str) -> Result<Answer, crate::Error> {
+        match (part, name.parse::<Theme>()) {
+            (2, Ok(theme)) if theme != Theme::Ascii => {
+                Ok(run_challenge2(input)?.themed(theme).to_string().trim_end().into())
+            }
+            _ => Err(crate::Error::UnknownVariant(part, name.to_string())),
+        }
+    }
+
+    fn visualize(&self, part: u8, input: &str) -> Result<Vec<Frame>, crate::Error> {
+        match part {
+            2 => Ok(run_challenge2(input)?.frames()),
+            _ => Err(crate::Error::UnknownPart(part)),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Unknown opcode {0:#04x} at byte {1}")]
+    UnknownOpcode(u8, usize),
+    #[error("Invalid operand for the command at byte {0}")]
+    InvalidOperand(usize),
+    #[error("Unknown theme '{0}', expected ascii, blocks or braille")]
+    UnknownTheme(String),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ (Error::Nom(..) | Error::UnknownOpcode(..) | Error::InvalidOperand(..)) => crate::Error::parse(e),
+            e @ Error::UnknownTheme(..) => crate::Error::validation(e),
+            #[cfg(feature = "serde")]
+            e @ Error::Json(..) => crate::Error::output(e),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day10::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day10_example.txt"))?;
+        assert_eq!(result, 13140);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day10_challenge.txt"))?;
+        dbg!(result);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day10_example.txt"))?;
+        println!("{}", result);
+        assert_crt_matches(&result, "
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+");
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day10_challenge.txt"))?;
+        println!("{}", result);
+        Ok(())
+    }
+
+    #[test]
+    fn bytecode_round_trip() -> Result<(), Error> {
+        let mut commands = read_input(include_str!("data/day10_example.txt"))?;
+        let bytes = assemble(commands.make_contiguous());
+        assert_eq!(disassemble(&bytes)?, commands);
+
+        let (strength, machine) = Machine::execute_bytes(&bytes)?;
+        assert_eq!(strength, 13140);
+        assert_eq!(machine.to_string(), run_challenge2(include_str!("data/day10_example.txt"))?.to_string());
+
+        let extremes = vec![Command::Addx(i64::MIN), Command::NoOp, Command::Addx(-1), Command::Addx(i64::MAX)];
+        assert_eq!(assemble(&extremes[2..]), vec![0x01, 0x01, 0x01, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        assert_eq!(Vec::from(disassemble(&assemble(&extremes))?), extremes);
+
+        assert!(matches!(disassemble(&[0x00, 0x07]), Err(Error::UnknownOpcode(0x07, 1))));
+        assert!(matches!(disassemble(&[0x01, 0x80]), Err(Error::InvalidOperand(0))));
+
+        // Ten operand bytes, the last one carrying bits past the 64th.
+        let mut oversized = vec![0x00, 0x01];
+        oversized.extend([0xff; 9]);
+        assert!(matches!(disassemble(&[oversized.as_slice(), &[0x7f]].concat()), Err(Error::InvalidOperand(1))));
+        assert!(matches!(disassemble(&[oversized.as_slice(), &[0x02]].concat()), Err(Error::InvalidOperand(1))));
+        assert!(matches!(disassemble(&[oversized.as_slice(), &[0x81, 0x00]].concat()), Err(Error::InvalidOperand(1))));
+        assert_eq!(disassemble(&[oversized.as_slice(), &[0x01]].concat())?, VecDeque::from([Command::NoOp, Command::Addx(i64::MIN)]));
+        Ok(())
+    }
+
+    #[test]
+    fn program_analysis() -> Result<(), Error> {
+        let program = Program::read(include_str!("data/day10_example.txt"))?;
+        assert_eq!(program.cycle_count(), 240);
+        assert_eq!(program.validate(), Validation::Fits);
+        assert_eq!(program.validate().to_string(), "fits the 240 cycles of the screen");
+        assert_eq!(program.max_register_excursion(), -1..=38);
+
+        let program = Program { commands: read_input("addx 3\naddx -5\nnoop")? };
+        assert_eq!(program.cycle_count(), 5);
+        assert_eq!(program.max_register_excursion(), -1..=4);
+        Ok(())
+    }
+
+    #[test]
+    fn program_truncation() -> Result<(), Error> {
+        let content = "noop\n".repeat(241);
+        let program = Program::read(&content)?;
+        assert_eq!(program.validate(), Validation::Truncated { cycles: 241, truncated_commands: 1 });
+        assert_eq!(program.validate().to_string(), "warning: 241 cycles, the last 1 commands run past the 240 of the screen");
+
+        let (_, machine) = run_loop(program.commands)?;
+        assert!(machine.crt[5][0]);
+        Ok(())
+    }
+
+    #[test]
+    fn event_log() -> Result<(), Error> {
+        let mut events = Vec::new();
+        run_loop_logged(read_input("noop\naddx 3\naddx -5")?, Some(&mut events))?;
+
+        let executions = events.iter()
+            .filter_map(|event| match event {
+                Event::Execute { cycle, register, .. } => Some((*cycle, *register)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(executions, vec![(1, 1), (3, 4), (5, -1)]);
+        assert_eq!(events.first(), Some(&Event::PixelDrawn { cycle: 1, x: 0, y: 0 }));
+        assert!(events.windows(2).all(|pair| pair[0].cycle() <= pair[1].cycle()));
+
+        let events = log_events(include_str!("data/day10_example.txt"))?;
+        let strengths = events.iter()
+            .filter_map(|event| match event {
+                Event::Sample { strength, .. } => Some(*strength),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(strengths, vec![420, 1140, 1800, 2940, 2880, 3960]);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn event_log_json() -> Result<(), Error> {
+        let events = vec![Event::Fetch { cycle: 1, command: Command::Addx(3) }];
+        let json = events_to_json(&events)?;
+        assert!(json.contains("\"event\": \"fetch\""));
+        assert!(json.contains("\"Addx\": 3"));
+        Ok(())
+    }
+
+    #[test]
+    fn program_diff() -> Result<(), Error> {
+        let program = include_str!("data/day10_example.txt");
+        let same = diff_programs(program, program)?;
+        assert_eq!((same.register, same.pixel), (None, None));
+
+        // An extra noop in front delays the first addx 15, so X differs from cycle 3 on.
+        let shifted = format!("noop\n{}", program);
+        let diff = diff_programs(program, shifted.as_str())?;
+        assert_eq!(diff.register.map(|(cycle, _, _)| cycle), Some(3));
+        assert!(diff.pixel.is_some_and(|(cycle, _, _)| cycle >= 3));
+
+        let rendered = diff.to_string();
+        assert_eq!(rendered.lines().count(), 6);
+        assert!(rendered.lines().next().unwrap().starts_with("##..##..##..##..##..##..##..##..##..##.. | "));
+        assert!(rendered.contains('^'));
+
+        let ended = diff_programs("noop\n", "noop\nnoop\n")?;
+        assert_eq!(ended.register, Some((2, None, Some(1))));
+        Ok(())
+    }
+
+    #[test]
+    fn render_themes() -> Result<(), Error> {
+        let machine = run_challenge2(include_str!("data/day10_example.txt"))?;
+        assert_eq!(machine.themed(Theme::Ascii).to_string(), machine.to_string());
+        assert_crt_matches(&machine, &machine.themed(Theme::Blocks).to_string());
+
+        let braille = machine.themed(Theme::Braille).to_string();
+        assert_eq!(braille.lines().map(|line| line.chars().count()).collect::<Vec<_>>(), vec![20, 20]);
+        assert!(braille.starts_with("\u{28ff}\u{28e6}"));
+
+        let plain = machine.frames()[0].to_terminal(crate::render::style::ColorSupport::None);
+        assert_eq!(plain, machine.to_string().replace('#', "##").replace('.', "  "));
+
+        assert_eq!("braille".parse::<Theme>()?, Theme::Braille);
+        assert!(matches!("dark".parse::<Theme>(), Err(Error::UnknownTheme(_))));
+        assert_eq!(machine.themed(Theme::Blocks).frames(), machine.frames());
+        let ascii = &machine.themed(Theme::Ascii).frames()[0];
+        assert_eq!(ascii.get(0, 0), style::CRT.high);
+        assert_ne!(ascii.get(2, 0), style::CRT.background);
+        let braille = &machine.themed(Theme::Braille).frames()[0];
+        assert_eq!((braille.width, braille.height), (20, 2));
+        assert_eq!(braille.get(0, 0), style::CRT.high);
+
+        let input = include_str!("data/day10_example.txt");
+        assert_eq!(Day10.variant(2, "braille", input).unwrap(), machine.themed(Theme::Braille).to_string().trim_end());
+        assert!(matches!(Day10.variant(2, "ascii", input), Err(crate::Error::UnknownVariant(2, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn crt_comparison_normalizes_pixels() -> Result<(), Error> {
+        let machine = run_challenge2(include_str!("data/day10_example.txt"))?;
+        assert_crt_matches(&machine, &machine.to_string().replace('#', "█").replace('.', " "));
+        assert!(!crt_matches(&machine, &machine.to_string().replacen('#', ".", 1)));
+        assert!(!crt_matches(&machine, ""));
+        Ok(())
+    }
+
+    #[test]
+    fn single_program_schedule() -> Result<(), Error> {
+        let commands = read_input(include_str!("data/day10_example.txt"))?;
+        let (strength, machine) = run_scheduled(vec![commands.clone()], None)?;
+        assert_eq!(strength, 13140);
+        assert_eq!(machine.to_string(), run_challenge2(include_str!("data/day10_example.txt"))?.to_string());
+        assert_eq!(machine.registers(), [17]);
+
+        let (mut logged, mut scheduled) = (Vec::new(), Vec::new());
+        run_loop_logged(commands.clone(), Some(&mut logged))?;
+        run_scheduled(vec![commands], Some(&mut scheduled))?;
+        assert_eq!(logged, scheduled);
+
+        let (strength, machine) = run_scheduled(Vec::new(), None)?;
+        assert_eq!((strength, machine.registers()), (0, [].as_slice()));
+        Ok(())
+    }
+
+    #[test]
+    fn round_robin_schedule() -> Result<(), Error> {
+        let first = include_str!("data/day10_example.txt");
+        let second = format!("noop\n{}", first);
+        let machine = run_interleaved(first, second.as_str())?;
+        assert_eq!(machine.registers(), [17, 17]);
+
+        // Both programs run for longer than the screen at half speed, each owning every other pixel.
+        let timelines = [register_timeline(&read_input(first)?), register_timeline(&read_input(&second)?)];
+        for index in 0..Machine::CRT_CYCLES {
+            let register = timelines[index % 2][index / 2];
+            let x = (index % 40) as i64;
+            assert_eq!(machine.crt[index / 40][index % 40], (register - x).abs() <= 1, "pixel of cycle {}", index + 1);
+        }
+
+        // Once a program is over, the other one gets every cycle.
+        let machine = run_interleaved("noop\n", "addx 10\naddx 20\nnoop\nnoop\nnoop")?;
+        assert_eq!(machine.registers(), [1, 31]);
+        assert_eq!(machine.crt[0][..8], [true, true, true, false, false, false, false, false]);
+        Ok(())
+    }
+}
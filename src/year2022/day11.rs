@@ -0,0 +1,988 @@
+use nom::{
+    Finish,
+    IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete,
+    combinator::{all_consuming, map, opt, value},
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+};
+use std::{
+    fmt::{self, Write},
+    io::{self, BufRead},
+};
+use thiserror::Error;
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Var {
+    Old,
+    Num(u64),
+}
+
+impl Var {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let old_parser = value(Var::Old, tag("old"));
+        let num_parser = map(complete::u64, Var::Num);
+
+        alt((old_parser, num_parser))(i)
+    }
+
+    fn apply(self, old: u64) -> u64 {
+        match self {
+            Var::Old => old,
+            Var::Num(num) => num
+        }
+    }
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Var::Old => f.write_str("old"),
+            Var::Num(num) => write!(f, "{}", num),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Operator {
+    Add,
+    Mul,
+}
+
+impl Operator {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            value(Operator::Add, complete::char('+')),
+            value(Operator::Mul, complete::char('*')),
+        ))(i)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operation {
+    Add(Var, Var),
+    Mul(Var, Var),
+    // Never parsed, only produced by simplification.
+    Value(Var),
+}
+
+impl Operation {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        preceded(tag("new ="), Self::parse_expression)(i)
+    }
+
+    // The right-hand side alone.
+    fn parse_expression(i: &str) -> IResult<&str, Self> {
+        map(
+            tuple((
+                complete::space0,
+                Var::parse,
+                complete::space0,
+                Operator::parse,
+                complete::space0,
+                Var::parse,
+            )), |(_, left, _, operator, _, right)| match operator {
+                Operator::Add => Operation::Add(left, right),
+                Operator::Mul => Operation::Mul(left, right),
+            },
+        )(i)
+    }
+
+    fn apply(self, old: u64) -> u64 {
+        match self {
+            Operation::Add(left, right) => left.apply(old) + right.apply(old),
+            Operation::Mul(left, right) => left.apply(old) * right.apply(old),
+            Operation::Value(var) => var.apply(old),
+        }
+    }
+
+    fn checked_apply(self, old: u64) -> Option<u64> {
+        match self {
+            Operation::Add(left, right) => left.apply(old).checked_add(right.apply(old)),
+            Operation::Mul(left, right) => left.apply(old).checked_mul(right.apply(old)),
+            Operation::Value(var) => Some(var.apply(old)),
+        }
+    }
+
+    // Folds constants and drops neutral operands, keeping the same result for every input.
+    pub fn simplified(self) -> Self {
+        match self {
+            Operation::Add(Var::Num(left), Var::Num(right)) => Operation::Value(Var::Num(left + right)),
+            Operation::Mul(Var::Num(left), Var::Num(right)) => Operation::Value(Var::Num(left * right)),
+            Operation::Add(var, Var::Num(0)) | Operation::Add(Var::Num(0), var) => Operation::Value(var),
+            Operation::Mul(var, Var::Num(1)) | Operation::Mul(Var::Num(1), var) => Operation::Value(var),
+            Operation::Mul(_, Var::Num(0)) | Operation::Mul(Var::Num(0), _) => Operation::Value(Var::Num(0)),
+            operation => operation,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Add(left, right) => write!(f, "new = {} + {}", left, right),
+            Operation::Mul(left, right) => write!(f, "new = {} * {}", left, right),
+            Operation::Value(var) => write!(f, "new = {}", var),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Test {
+    divisible_by: u64,
+    if_true_send_to: usize,
+    if_false_send_to: usize,
+}
+
+impl Test {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        map(
+            tuple((
+                tag("divisible by"),
+                complete::space1,
+                complete::u64,
+                complete::line_ending,
+                complete::space1,
+                tag("If true: throw to monkey"),
+                complete::space1,
+                complete::u32,
+                complete::line_ending,
+                complete::space1,
+                tag("If false: throw to monkey"),
+                complete::space1,
+                complete::u32,
+            )),
+            |(_, _, divisible_by, _, _, _, _, if_true_send_to, _, _, _, _, if_false_send_to)| Test {
+                divisible_by,
+                if_true_send_to: if_true_send_to as usize,
+                if_false_send_to: if_false_send_to as usize,
+            },
+        )(i)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Monkey {
+    inspected: u64,
+    index: u32,
+    items: Vec<u64>,
+    operation: Operation,
+    test: Test,
+}
+
+impl Monkey {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let monkey_index_parser = map(
+            tuple((
+                tag("Monkey"),
+                complete::space1,
+                complete::u32,
+                complete::char(':'),
+            )),
+            |(_, _, index, _)| index,
+        );
+
+        let starting_items_parser =
+            separated_list1(
+                tuple((complete::char(','), complete::space0)),
+                complete::u64,
+            );
+
+        map(
+            tuple((
+                monkey_index_parser,
+                complete::line_ending,
+                complete::space1,
+                tag("Starting items:"),
+                complete::space1,
+                starting_items_parser,
+                complete::line_ending,
+                complete::space1,
+                tag("Operation:"),
+                complete::space1,
+                Operation::parse,
+                complete::line_ending,
+                complete::space1,
+                tag("Test:"),
+                complete::space1,
+                Test::parse,
+                complete::line_ending,
+            )),
+            |(index, _, _, _, _, items, _, _, _, _, operation, _, _, _, _, test, _)| Monkey { inspected: 0, index, items, operation, test },
+        )(i)
+    }
+}
+
+pub fn read_input(content: &str) -> Result<Vec<Monkey>, Error> {
+    let (_, mut monkeys) = all_consuming(separated_list1(complete::line_ending, Monkey::parse))(content)
+        .map_err(|e| e.to_owned())
+        .finish()?;
+
+    monkeys.sort_by_key(|x| x.index);
+
+    Ok(monkeys)
+}
+
+// Number of items thrown from a monkey (row) to another (column).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemFlow {
+    thrown: Vec<Vec<u64>>,
+}
+
+impl ItemFlow {
+    fn new(monkeys: usize) -> Self {
+        ItemFlow { thrown: vec![vec![0; monkeys]; monkeys] }
+    }
+
+    fn max(&self) -> u64 {
+        self.thrown.iter().flatten().cloned().max().unwrap_or_default()
+    }
+}
+
+fn run_loop(iterations: usize, worry_level_divider: u64, monkeys: Vec<Monkey>) -> Vec<Monkey> {
+    simulate(iterations, worry_level_divider, monkeys).0
+}
+
+pub fn simulate(iterations: usize, worry_level_divider: u64, monkeys: Vec<Monkey>) -> (Vec<Monkey>, ItemFlow) {
+    let mut keep_away = KeepAway::new(worry_level_divider, monkeys);
+    keep_away.run(iterations);
+
+    tracing::debug!("{:?}", keep_away.monkeys);
+
+    (keep_away.monkeys, keep_away.flow)
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Snapshot {
+    round: usize,
+    worry_level_divider: u64,
+    monkeys: Vec<Monkey>,
+    flow: ItemFlow,
+}
+
+#[derive(Debug)]
+pub struct KeepAway {
+    round: usize,
+    worry_level_divider: u64,
+    divisor_product: u64,
+    monkeys: Vec<Monkey>,
+    flow: ItemFlow,
+}
+
+impl KeepAway {
+    fn new(worry_level_divider: u64, monkeys: Vec<Monkey>) -> Self {
+        let flow = ItemFlow::new(monkeys.len());
+        Self::restore(Snapshot { round: 0, worry_level_divider, monkeys, flow })
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            round: self.round,
+            worry_level_divider: self.worry_level_divider,
+            monkeys: self.monkeys.clone(),
+            flow: self.flow.clone(),
+        }
+    }
+
+    fn restore(snapshot: Snapshot) -> Self {
+        let Snapshot { round, worry_level_divider, monkeys, flow } = snapshot;
+        let divisor_product = monkeys.iter().map(|m| m.test.divisible_by).product::<u64>();
+
+        KeepAway { round, worry_level_divider, divisor_product, monkeys, flow }
+    }
+
+    fn run(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.play_round();
+        }
+    }
+
+    fn play_round(&mut self) {
+        let monkeys = &mut self.monkeys;
+
+        for m in 0..monkeys.len() {
+            let Monkey { operation, test, items, .. } = monkeys[m].clone();
+            let operation = operation.simplified();
+
+            monkeys[m].inspected += monkeys[m].items.len() as u64;
+            monkeys[m].items.clear();
+
+            for mut item in items.iter().cloned() {
+                item %= self.divisor_product;
+                item = operation.apply(item);
+                item /= self.worry_level_divider;
+
+                let target = if item % test.divisible_by == 0 {
+                    test.if_true_send_to
+                } else {
+                    test.if_false_send_to
+                };
+
+                paranoid_assert!(target < monkeys.len() && target != m, "monkey {} throws to monkey {}", m, target);
+                monkeys[target].items.push(item);
+                self.flow.thrown[m][target] += 1;
+            }
+        }
+
+        self.round += 1;
+    }
+}
+
+// Inspection counts of every monkey after a given round.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InspectionSample {
+    pub round: usize,
+    pub inspected: Vec<u64>,
+}
+
+impl InspectionSample {
+    // The two busiest monkeys, busiest first, ties going to the lowest index. None with fewer than
+    // two monkeys.
+    fn top2(&self) -> Option<(usize, usize)> {
+        let mut order = (0..self.inspected.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&m| std::cmp::Reverse(self.inspected[m]));
+        match order[..] {
+            [first, second, ..] => Some((first, second)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Convergence {
+    pub samples: Vec<InspectionSample>,
+}
+
+impl Convergence {
+    // Plays the given rounds, sampling every `every` rounds and after the last one.
+    pub fn sample(worry_level_divider: u64, monkeys: Vec<Monkey>, rounds: usize, every: usize) -> Self {
+        let mut keep_away = KeepAway::new(worry_level_divider, monkeys);
+        let mut samples = Vec::new();
+
+        while keep_away.round < rounds {
+            keep_away.run(every.max(1).min(rounds - keep_away.round));
+            samples.push(InspectionSample {
+                round: keep_away.round,
+                inspected: keep_away.monkeys.iter().map(|m| m.inspected).collect(),
+            });
+        }
+
+        Convergence { samples }
+    }
+
+    // First sampled round from which the top-2 monkeys never swap or change again. None without
+    // samples, or with fewer than two monkeys to rank.
+    pub fn stable_from(&self) -> Option<usize> {
+        let last = self.samples.last()?.top2()?;
+        let unstable = self.samples.iter().rposition(|sample| sample.top2() != Some(last));
+        Some(self.samples[unstable.map_or(0, |i| i + 1)].round)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let monkeys = self.samples.first().map_or(0, |sample| sample.inspected.len());
+        let mut csv = String::from("round");
+        for m in 0..monkeys {
+            write!(csv, ",monkey{}", m).unwrap();
+        }
+        csv.push('\n');
+
+        for sample in &self.samples {
+            write!(csv, "{}", sample.round).unwrap();
+            for inspected in &sample.inspected {
+                write!(csv, ",{}", inspected).unwrap();
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+// The two highest inspection counts multiplied.
+fn monkey_business(monkeys: &[Monkey]) -> u64 {
+    let mut inspected = monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>();
+    inspected.sort();
+
+    inspected.iter().rev().take(2).product()
+}
+
+// A change to try out in the explorer.
+#[derive(Clone, Copy, Debug)]
+pub enum Edit {
+    Operation(u32, Operation),
+    Divisor(u32, u64),
+    Rounds(usize),
+    Relief(u64),
+    Reset,
+}
+
+impl Edit {
+    fn parse_edit(i: &str) -> IResult<&str, Self> {
+        let monkey = |i| preceded(complete::space1, complete::u32)(i);
+        let operation = preceded(tuple((complete::space1, opt(tag("new =")))), Operation::parse_expression);
+
+        alt((
+            map(preceded(tag("op"), tuple((monkey, operation))), |(monkey, operation)| Edit::Operation(monkey, operation)),
+            map(preceded(tag("div"), tuple((monkey, preceded(complete::space1, complete::u64)))), |(monkey, divisor)| Edit::Divisor(monkey, divisor)),
+            map(preceded(tuple((tag("rounds"), complete::space1)), complete::u64), |rounds| Edit::Rounds(rounds as usize)),
+            map(preceded(tuple((tag("relief"), complete::space1)), complete::u64), Edit::Relief),
+            value(Edit::Reset, tag("reset")),
+        ))(i)
+    }
+
+    pub fn parse(line: &str) -> Result<Self, Error> {
+        all_consuming(Self::parse_edit)(line.trim())
+            .finish()
+            .map(|(_, edit)| edit)
+            .map_err(|_| Error::InvalidEdit(line.trim().to_string()))
+    }
+}
+
+// Monkey business of an edited game next to the one of the game as given, after the same rounds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub rounds: usize,
+    pub inspected: Vec<u64>,
+    pub business: u64,
+    pub baseline: u64,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let change = self.business as f64 / self.baseline.max(1) as f64;
+        writeln!(f, "after {} rounds: monkey business {} (as given {}, x{:.3})", self.rounds, self.business, self.baseline, change)?;
+        for (m, inspected) in self.inspected.iter().enumerate() {
+            writeln!(f, "  monkey {}: {} inspections", m, inspected)?;
+        }
+        Ok(())
+    }
+}
+
+// What-if games: edits go to a branch of the starting snapshot, which is played from scratch after
+// each of them and compared with the game as given.
+pub struct Explorer {
+    given: Snapshot,
+    branch: Snapshot,
+    rounds: usize,
+}
+
+impl Explorer {
+    const HELP: &str = "\
+op <monkey> <expression>  change an operation, like `op 2 old * old`
+div <monkey> <divisor>    change a test divisor
+rounds <count>            change how many rounds are played
+relief <divider>          change how much worry drops after inspections, 1 for none
+reset                     undo every change
+show                      list the monkeys
+quit";
+
+    pub fn new(worry_level_divider: u64, monkeys: Vec<Monkey>, rounds: usize) -> Self {
+        let given = KeepAway::new(worry_level_divider, monkeys).snapshot();
+        Explorer { branch: given.clone(), given, rounds }
+    }
+
+    fn monkey(&mut self, index: u32) -> Result<&mut Monkey, Error> {
+        self.branch.monkeys.iter_mut().find(|monkey| monkey.index == index).ok_or(Error::UnknownMonkey(index))
+    }
+
+    // Edits that would make worry levels overflow, or divide by zero, are refused.
+    pub fn apply(&mut self, edit: Edit) -> Result<(), Error> {
+        let previous = self.branch.clone();
+        match edit {
+            Edit::Operation(index, operation) => self.monkey(index)?.operation = operation,
+            Edit::Divisor(_, 0) | Edit::Relief(0) => return Err(Error::InvalidEdit("0 cannot divide".to_string())),
+            Edit::Divisor(index, divisor) => self.monkey(index)?.test.divisible_by = divisor,
+            Edit::Rounds(rounds) => self.rounds = rounds,
+            Edit::Relief(divider) => self.branch.worry_level_divider = divider,
+            Edit::Reset => self.branch = self.given.clone(),
+        }
+
+        // Worry levels are kept below the product of the divisors before each operation.
+        let largest = self.branch.monkeys.iter().try_fold(1_u64, |product, monkey| product.checked_mul(monkey.test.divisible_by));
+        let overflowing = self.branch.monkeys.iter().find(|monkey| largest.is_none_or(|largest| monkey.operation.checked_apply(largest - 1).is_none()));
+        if let Some(monkey) = overflowing {
+            let index = monkey.index;
+            self.branch = previous;
+            return Err(Error::Overflow(index));
+        }
+        Ok(())
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        let play = |snapshot: &Snapshot| {
+            let mut keep_away = KeepAway::restore(snapshot.clone());
+            keep_away.run(self.rounds);
+            keep_away.monkeys
+        };
+
+        let monkeys = play(&self.branch);
+        Outcome {
+            rounds: self.rounds,
+            inspected: monkeys.iter().map(|m| m.inspected).collect(),
+            business: monkey_business(&monkeys),
+            baseline: monkey_business(&play(&self.given)),
+        }
+    }
+
+    // Changed parameters are starred.
+    pub fn show(&self) -> String {
+        let mut text = String::new();
+        let star = |changed| if changed { "*" } else { "" };
+        for (monkey, given) in self.branch.monkeys.iter().zip(&self.given.monkeys) {
+            writeln!(
+                text,
+                "monkey {}: {}{}, divisible by {}{}",
+                monkey.index,
+                monkey.operation,
+                star(monkey.operation != given.operation),
+                monkey.test.divisible_by,
+                star(monkey.test.divisible_by != given.test.divisible_by)
+            ).unwrap();
+        }
+        let divider = self.branch.worry_level_divider;
+        writeln!(text, "relief: divided by {}{}", divider, star(divider != self.given.worry_level_divider)).unwrap();
+        text
+    }
+
+    // Reads commands until quit or the end of the input, printing the outcome after every edit.
+    pub fn interact(&mut self, input: impl BufRead, mut output: impl io::Write, prompt: bool) -> io::Result<()> {
+        write!(output, "{}", self.outcome())?;
+        let mut lines = input.lines();
+        loop {
+            if prompt {
+                write!(output, "> ")?;
+                output.flush()?;
+            }
+            let Some(line) = lines.next().transpose()? else {
+                break;
+            };
+
+            match line.trim() {
+                "" => continue,
+                "quit" => break,
+                "help" => writeln!(output, "{}", Self::HELP)?,
+                "show" => write!(output, "{}", self.show())?,
+                line => match Edit::parse(line).and_then(|edit| self.apply(edit)) {
+                    Ok(()) => write!(output, "{}", self.outcome())?,
+                    Err(e) => writeln!(output, "error: {}", e)?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+// Throws are weighted by the items that went through them when the flow is given.
+pub fn to_dot(monkeys: &[Monkey], flow: Option<&ItemFlow>) -> String {
+    let mut dot = String::from("digraph monkeys {\n");
+    let max_flow = flow.map(ItemFlow::max).unwrap_or_default().max(1);
+
+    for (m, monkey) in monkeys.iter().enumerate() {
+        writeln!(
+            dot,
+            "    {} [label=\"Monkey {}\\n{}\\ndivisible by {}\"];",
+            monkey.index, monkey.index, monkey.operation, monkey.test.divisible_by
+        ).unwrap();
+
+        for (outcome, target) in [("true", monkey.test.if_true_send_to), ("false", monkey.test.if_false_send_to)] {
+            match flow {
+                None => writeln!(dot, "    {} -> {} [label=\"{}\"];", monkey.index, target, outcome).unwrap(),
+                Some(flow) => {
+                    let thrown = flow.thrown[m][target];
+                    let pen_width = 1.0 + 4.0 * thrown as f64 / max_flow as f64;
+                    writeln!(
+                        dot,
+                        "    {} -> {} [label=\"{} ({})\", penwidth={:.2}];",
+                        monkey.index, target, outcome, thrown, pen_width
+                    ).unwrap();
+                }
+            }
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge1(input: impl IntoInput) -> Result<u64, Error> {
+    let content = input.into_input()?;
+    let monkeys = read_input(&content)?;
+    Ok(monkey_business(&run_loop(20, 3, monkeys)))
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run_challenge2(input: impl IntoInput) -> Result<u64, Error> {
+    let content = input.into_input()?;
+    let monkeys = read_input(&content)?;
+    Ok(monkey_business(&run_loop(10_000, 1, monkeys)))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day11_example.txt"), answers: [Some("10605"), Some("2713310158")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        read_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok(run_challenge2(input)?.into())
+    }
+
+    // Operations as the simulation runs them, with what the notes said when that differs.
+    fn details(&self, _part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        Ok(read_input(input)?
+            .iter()
+            .map(|monkey| match monkey.operation.simplified() {
+                simplified if simplified == monkey.operation => format!("Monkey {}: {}", monkey.index, simplified),
+                simplified => format!("Monkey {}: {} (from {})", monkey.index, simplified, monkey.operation),
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[error("Cannot understand `{0}`, try help")]
+    InvalidEdit(String),
+    #[error("No monkey {0}")]
+    UnknownMonkey(u32),
+    #[error("Worry levels of monkey {0} would overflow")]
+    Overflow(u32),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ Error::Nom(..) => crate::Error::parse(e),
+            e @ (Error::InvalidEdit(..) | Error::UnknownMonkey(..) | Error::Overflow(..)) => crate::Error::validation(e),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::year2022::day11::*;
+    use proptest::strategy::Strategy as _;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day11_example.txt"))?;
+        assert_eq!(result, 10605);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day11_challenge.txt"))?;
+        dbg!(result);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day11_example.txt"))?;
+        assert_eq!(result, 2713310158);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day11_challenge.txt"))?;
+        println!("{}", result);
+        Ok(())
+    }
+
+    fn arbitrary_operation() -> impl proptest::strategy::Strategy<Value = Operation> {
+        proptest::prop_oneof![
+            (1..10_u64).prop_map(|num| Operation::Add(Var::Old, Var::Num(num))),
+            (2..20_u64).prop_map(|num| Operation::Mul(Var::Old, Var::Num(num))),
+            proptest::strategy::Just(Operation::Mul(Var::Old, Var::Old)),
+        ]
+    }
+
+    // Distinct small prime divisors keep the product, and so every reduced worry level, far from
+    // overflowing even when squared. No monkey throws to itself.
+    fn arbitrary_monkeys() -> impl proptest::strategy::Strategy<Value = Vec<Monkey>> {
+        (2..9_usize)
+            .prop_flat_map(|count| {
+                let monkey = (0..count - 1, 0..count - 1, arbitrary_operation(), proptest::collection::vec(1..100_u64, 0..6));
+                (proptest::strategy::Just(vec![2, 3, 5, 7, 11, 13, 17, 19, 23]).prop_shuffle(), proptest::collection::vec(monkey, count))
+            })
+            .prop_map(|(primes, monkeys)| {
+                let count = monkeys.len();
+                monkeys
+                    .into_iter()
+                    .enumerate()
+                    .map(|(m, (if_true, if_false, operation, items))| {
+                        let other = |offset| (m + 1 + offset) % count;
+                        let test = Test { divisible_by: primes[m], if_true_send_to: other(if_true), if_false_send_to: other(if_false) };
+                        Monkey { inspected: 0, index: m as u32, items, operation, test }
+                    })
+                    .collect()
+            })
+    }
+
+    // Worry levels as the puzzle states them, never reduced, or None once one leaves u128.
+    fn naive_round(monkeys: &[Monkey], items: &mut [Vec<u128>], inspected: &mut [u64]) -> Option<()> {
+        for (m, monkey) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[m]) {
+                let value = |var| match var {
+                    Var::Old => old,
+                    Var::Num(num) => num as u128,
+                };
+                let new = match monkey.operation {
+                    Operation::Add(left, right) => value(left).checked_add(value(right))?,
+                    Operation::Mul(left, right) => value(left).checked_mul(value(right))?,
+                    Operation::Value(var) => value(var),
+                };
+
+                inspected[m] += 1;
+                let test = monkey.test;
+                let target = if new % test.divisible_by as u128 == 0 { test.if_true_send_to } else { test.if_false_send_to };
+                items[target].push(new);
+            }
+        }
+        Some(())
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn random_monkeys_keep_their_items(monkeys in arbitrary_monkeys(), worry_level_divider in 1..4_u64) {
+            let items = monkeys.iter().map(|m| m.items.len()).sum::<usize>();
+
+            let mut keep_away = KeepAway::new(worry_level_divider, monkeys.clone());
+            let mut inspected = vec![0; monkeys.len()];
+            for _ in 0..50 {
+                keep_away.play_round();
+                proptest::prop_assert_eq!(keep_away.monkeys.iter().map(|m| m.items.len()).sum::<usize>(), items);
+
+                let now = keep_away.monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>();
+                proptest::prop_assert!(now.iter().zip(&inspected).all(|(now, before)| now >= before));
+                inspected = now;
+            }
+        }
+
+        // Without relief, reducing worry levels modulo the divisors' product changes no throw.
+        #[test]
+        fn reduced_rounds_match_naive_ones(monkeys in arbitrary_monkeys()) {
+            let mut keep_away = KeepAway::new(1, monkeys.clone());
+            let mut items = monkeys.iter().map(|m| m.items.iter().map(|item| *item as u128).collect::<Vec<_>>()).collect::<Vec<_>>();
+            let mut inspected = vec![0; monkeys.len()];
+
+            for _ in 0..20 {
+                let (mut next_items, mut next_inspected) = (items.clone(), inspected.clone());
+                if naive_round(&monkeys, &mut next_items, &mut next_inspected).is_none() {
+                    break;
+                }
+                (items, inspected) = (next_items, next_inspected);
+                keep_away.play_round();
+
+                let product = keep_away.divisor_product as u128;
+                proptest::prop_assert_eq!(keep_away.monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>(), inspected.clone());
+                for (monkey, naive) in keep_away.monkeys.iter().zip(&items) {
+                    let reduced = monkey.items.iter().map(|item| *item as u128 % product).collect::<Vec<_>>();
+                    proptest::prop_assert_eq!(reduced, naive.iter().map(|item| item % product).collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn snapshot_restore() -> Result<(), Error> {
+        let mut keep_away = KeepAway::new(1, read_input(include_str!("data/day11_example.txt"))?);
+        keep_away.run(5000);
+        let snapshot = keep_away.snapshot();
+
+        keep_away.run(5000);
+        let mut resumed = KeepAway::restore(snapshot.clone());
+        resumed.run(5000);
+        assert_eq!(resumed.round, 10_000);
+        assert_eq!(resumed.flow, keep_away.flow);
+        assert_eq!(
+            resumed.monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>(),
+            vec![52166, 47830, 1938, 52013]
+        );
+
+        let mut what_if = snapshot;
+        what_if.monkeys[0].operation = Operation::Add(Var::Old, Var::Num(19));
+        let mut branched = KeepAway::restore(what_if);
+        branched.run(5000);
+        assert_ne!(branched.flow, keep_away.flow);
+        Ok(())
+    }
+
+    #[test]
+    fn inspection_convergence() -> Result<(), Error> {
+        let monkeys = read_input(include_str!("data/day11_example.txt"))?;
+        let convergence = Convergence::sample(1, monkeys, 10_000, 1000);
+        assert_eq!(convergence.samples.len(), 10);
+        assert_eq!(convergence.samples.last().unwrap().inspected, vec![52166, 47830, 1938, 52013]);
+        assert_eq!(convergence.stable_from(), Some(1000));
+
+        let convergence = Convergence::sample(3, read_input(include_str!("data/day11_example.txt"))?, 20, 7);
+        assert_eq!(
+            convergence.to_csv(),
+            "round,monkey0,monkey1,monkey2,monkey3\n7,32,34,5,36\n14,70,66,6,74\n20,101,95,7,105\n"
+        );
+        // Monkey 1 outpaces monkey 0 early on, monkey 0 only settles second from round 14.
+        let every_round = Convergence::sample(3, read_input(include_str!("data/day11_example.txt"))?, 20, 1);
+        assert_eq!(every_round.stable_from(), Some(14));
+
+        // Nothing to rank without at least two monkeys.
+        assert_eq!(Convergence::sample(3, Vec::new(), 20, 7).stable_from(), None);
+        let lonely = Convergence { samples: vec![InspectionSample { round: 1, inspected: vec![4] }] };
+        assert_eq!(lonely.stable_from(), None);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn snapshot_serialization() -> Result<(), Error> {
+        let mut keep_away = KeepAway::new(3, read_input(include_str!("data/day11_example.txt"))?);
+        keep_away.run(10);
+
+        let json = serde_json::to_string(&keep_away.snapshot()).unwrap();
+        let mut resumed = KeepAway::restore(serde_json::from_str(&json).unwrap());
+        resumed.run(10);
+        keep_away.run(10);
+        assert_eq!(resumed.flow, keep_away.flow);
+        Ok(())
+    }
+
+    #[cfg(feature = "paranoid")]
+    #[test]
+    #[should_panic(expected = "throws to monkey")]
+    fn paranoid_self_throw() {
+        let mut monkeys = read_input(include_str!("data/day11_example.txt")).unwrap();
+        monkeys[0].test.if_true_send_to = 0;
+        monkeys[0].test.if_false_send_to = 0;
+        run_loop(1, 3, monkeys);
+    }
+
+    #[test]
+    fn operation_simplification() -> Result<(), Error> {
+        let cases = [
+            ("new = old + 0", "new = old"),
+            ("new = 1 * old", "new = old"),
+            ("new = old * 0", "new = 0"),
+            ("new = 2 + 3", "new = 5"),
+            ("new = old * old", "new = old * old"),
+            ("new = old + 7", "new = old + 7"),
+        ];
+        for (operation, simplified) in cases {
+            let (_, operation) = Operation::parse(operation).map_err(|e| e.to_owned()).finish()?;
+            assert_eq!(operation.simplified().to_string(), simplified);
+            assert!((0..50).all(|old| operation.apply(old) == operation.simplified().apply(old)));
+        }
+
+        let content = include_str!("data/day11_example.txt")
+            .replace("new = old * 19", "new = old * 1")
+            .replace("new = old + 3", "new = old + 0");
+        let monkeys = read_input(&content)?;
+        let simplified = monkeys.iter().cloned()
+            .map(|monkey| Monkey { operation: monkey.operation.simplified(), ..monkey })
+            .collect::<Vec<_>>();
+
+        let (monkeys, flow) = simulate(1000, 1, monkeys);
+        let (simplified, simplified_flow) = simulate(1000, 1, simplified);
+        assert_eq!(flow, simplified_flow);
+        assert_eq!(
+            monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>(),
+            simplified.iter().map(|m| m.inspected).collect::<Vec<_>>()
+        );
+
+        assert_eq!(Day11.details(1, &content).unwrap()[..2], [
+            "Monkey 0: new = old (from new = old * 1)",
+            "Monkey 1: new = old + 6",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn dot_export() -> Result<(), Error> {
+        let monkeys = read_input(include_str!("data/day11_example.txt"))?;
+
+        let dot = to_dot(&monkeys, None);
+        assert!(dot.starts_with("digraph monkeys {\n"));
+        assert!(dot.contains("    0 [label=\"Monkey 0\\nnew = old * 19\\ndivisible by 23\"];\n"));
+        assert!(dot.contains("    0 -> 2 [label=\"true\"];\n"));
+        assert!(dot.contains("    2 -> 1 [label=\"true\"];\n"));
+        assert_eq!(dot.matches("->").count(), 8);
+
+        let (monkeys, flow) = simulate(20, 3, monkeys);
+        let thrown = flow.thrown.iter().map(|row| row.iter().sum::<u64>()).collect::<Vec<_>>();
+        assert_eq!(thrown, monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>());
+
+        let dot = to_dot(&monkeys, Some(&flow));
+        assert!(dot.contains(&format!("    0 -> 3 [label=\"false ({})\"", flow.thrown[0][3])));
+        Ok(())
+    }
+
+    #[test]
+    fn what_if_explorer() -> Result<(), Error> {
+        let mut explorer = Explorer::new(3, read_input(include_str!("data/day11_example.txt"))?, 20);
+        assert_eq!(explorer.outcome(), Outcome { rounds: 20, inspected: vec![101, 95, 7, 105], business: 10605, baseline: 10605 });
+
+        explorer.apply(Edit::parse("op 0 old * 19")?)?;
+        assert_eq!(explorer.outcome().business, 10605);
+        explorer.apply(Edit::parse("op 2 new = old + 3")?)?;
+        explorer.apply(Edit::parse("div 3 5")?)?;
+        let outcome = explorer.outcome();
+        assert_ne!(outcome.business, outcome.baseline);
+        assert_eq!(outcome.baseline, 10605);
+        assert!(explorer.show().contains("monkey 2: new = old + 3*, divisible by 13\nmonkey 3: new = old + 3, divisible by 5*\n"));
+
+        assert!(matches!(Edit::parse("op 9 old + 1").and_then(|edit| explorer.apply(edit)), Err(Error::UnknownMonkey(9))));
+        assert!(matches!(Edit::parse("div 1 two"), Err(Error::InvalidEdit(_))));
+        assert!(matches!(explorer.apply(Edit::Divisor(1, 0)), Err(Error::InvalidEdit(_))));
+        assert!(matches!(explorer.apply(Edit::Divisor(1, u64::MAX / 2)), Err(Error::Overflow(_))));
+        assert!(explorer.show().contains("monkey 1: new = old + 6, divisible by 19\n"));
+
+        explorer.apply(Edit::parse("reset")?)?;
+        explorer.apply(Edit::parse("rounds 10000")?)?;
+        explorer.apply(Edit::parse("relief 1")?)?;
+        let outcome = explorer.outcome();
+        assert_eq!(outcome.business, 2713310158);
+        assert!(explorer.show().ends_with("relief: divided by 1*\n"));
+
+        let mut output = Vec::new();
+        let mut explorer = Explorer::new(3, read_input(include_str!("data/day11_example.txt"))?, 20);
+        explorer.interact("help\nop 1 old * 2\nnonsense\nquit\nrounds 1\n".as_bytes(), &mut output, false)?;
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("after 20 rounds: monkey business 10605 (as given 10605, x1.000)\n  monkey 0: 101 inspections\n"));
+        assert!(output.contains("div <monkey> <divisor>"));
+        assert!(output.contains("error: Cannot understand `nonsense`, try help\n"));
+        assert_eq!(output.matches("after 20 rounds").count(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,898 @@
+use std::{
+    collections::BTreeSet,
+    fmt,
+};
+use nom::{
+    Finish,
+    IResult,
+    branch::alt,
+    character::complete,
+    combinator::{all_consuming, map, value},
+    multi::{many1, separated_list1},
+};
+use thiserror::Error;
+#[cfg(feature = "image")]
+use camino::Utf8Path;
+use crate::render::style;
+use crate::utils::pathfind::{self, Search};
+// Ordered row by row, so that BFS frontiers are visited, and ties broken, in reading order.
+pub use crate::utils::geometry::Vec2 as Pos;
+use crate::visualize::{Frame, Rgb, Visualize};
+use crate::{
+    input::IntoInput,
+    solution::{Answer, Example, Solution},
+};
+
+#[derive(Clone, Copy, Debug)]
+// Start and end keep their own height, which is always a and z in puzzle inputs.
+pub enum Cell {
+    Start(u8),
+    End(u8),
+    Height(u8),
+}
+
+impl Cell {
+    const MIN_HEIGHT: u8 = 0;
+    const MAX_HEIGHT: u8 = b'z' - b'a';
+
+    fn is_start(&self) -> bool {
+        matches!(self, Cell::Start(_))
+    }
+
+    fn is_end(&self) -> bool {
+        matches!(self, Cell::End(_))
+    }
+
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let start_parser = value(Cell::Start(Self::MIN_HEIGHT), complete::char('S'));
+        let end_parser = value(Cell::End(Self::MAX_HEIGHT), complete::char('E'));
+        let height_parser = map(complete::satisfy(|c: char| c.is_ascii_lowercase()), |c| Cell::Height(c as u8 - b'a'));
+
+        alt((
+            start_parser,
+            end_parser,
+            height_parser,
+        ))(i)
+    }
+
+    fn height(self) -> u8 {
+        match self {
+            Cell::Start(height) | Cell::End(height) | Cell::Height(height) => height,
+        }
+    }
+}
+
+// Rows of cells, or of anything known about each cell, looked up by position.
+trait Grid<T> {
+    fn cell(&self, pos: Pos) -> &T;
+
+    fn cell_mut(&mut self, pos: Pos) -> &mut T;
+}
+
+impl<T> Grid<T> for [Vec<T>] {
+    fn cell(&self, pos: Pos) -> &T {
+        &self[pos.y as usize][pos.x as usize]
+    }
+
+    fn cell_mut(&mut self, pos: Pos) -> &mut T {
+        &mut self[pos.y as usize][pos.x as usize]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Endpoints {
+    // Start on the darkest pixel and end on the brightest, first in reading order on ties.
+    Extremes,
+    Explicit { start: Pos, end: Pos },
+}
+
+#[derive(Clone, Debug)]
+pub struct Topology {
+    cells: Vec<Vec<Cell>>,
+    rows: usize,
+    columns: usize,
+}
+
+impl Topology {
+    const NEIGHBOURS_DELTAS: [Pos; 4] = [Pos::new(0, 1), Pos::new(0, -1), Pos::new(1, 0), Pos::new(-1, 0)];
+
+    pub fn parse(i: &str) -> Result<Self, Error> {
+        let (_, cells) = all_consuming(
+            separated_list1(
+                complete::line_ending,
+                many1(Cell::parse),
+            )
+        )(i)
+            .map_err(|e| e.to_owned())
+            .finish()?;
+
+        match cells.first() {
+            None => Err(Error::EmptyInput),
+            Some(first) => {
+                let rows = cells.len();
+                let expected_colums = first.len();
+
+                if cells.iter().all(|line| line.len() == expected_colums) {
+                    Ok(
+                        Topology {
+                            cells,
+                            rows,
+                            columns: expected_colums,
+                        }
+                    )
+                } else {
+                    Err(
+                        Error::InvalidLineSize
+                    )
+                }
+            }
+        }
+    }
+
+    // Gray levels are scaled down to the a-z heights.
+    pub fn from_gray(columns: usize, rows: usize, levels: &[u8], endpoints: Endpoints) -> Result<Self, Error> {
+        if columns == 0 || rows == 0 {
+            return Err(Error::EmptyInput);
+        }
+        if levels.len() != columns * rows {
+            return Err(Error::InvalidLineSize);
+        }
+
+        let pos_of = |index: usize| Pos::from_grid(index % columns, index / columns);
+        let (start, end) = match endpoints {
+            Endpoints::Extremes => {
+                let darkest = levels.iter().enumerate().min_by_key(|(index, level)| (**level, *index)).unwrap().0;
+                let brightest = levels.iter().enumerate().max_by_key(|(index, level)| (**level, usize::MAX - index)).unwrap().0;
+                (pos_of(darkest), pos_of(brightest))
+            }
+            Endpoints::Explicit { start, end } => {
+                for pos in [start, end] {
+                    if pos.to_grid().is_none_or(|(x, y)| x >= columns || y >= rows) {
+                        return Err(Error::OutOfBounds(pos));
+                    }
+                }
+                (start, end)
+            }
+        };
+
+        let mut cells = levels
+            .chunks(columns)
+            .map(|row| row.iter().map(|level| Cell::Height((*level as u16 * Cell::MAX_HEIGHT as u16 / 255) as u8)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let (start_height, end_height) = (cells.cell(start).height(), cells.cell(end).height());
+        *cells.cell_mut(start) = Cell::Start(start_height);
+        *cells.cell_mut(end) = Cell::End(end_height);
+
+        Ok(Topology { cells, rows, columns })
+    }
+
+    #[cfg(feature = "image")]
+    pub fn from_image(path: &Utf8Path, endpoints: Endpoints) -> Result<Self, Error> {
+        let image = image::open(path)?.to_luma8();
+        Self::from_gray(image.width() as usize, image.height() as usize, image.as_raw(), endpoints)
+    }
+
+    fn at(&self, pos: &Pos) -> Cell {
+        paranoid_assert!(self.contains(*pos), "{:?} is outside the grid", pos);
+        *self.cells.cell(*pos)
+    }
+
+    fn contains(&self, pos: Pos) -> bool {
+        pos.to_grid().is_some_and(|(x, y)| x < self.columns && y < self.rows)
+    }
+
+    // In reading order.
+    fn positions(&self) -> impl Iterator<Item=Pos> + use<> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |y| (0..columns).map(move |x| Pos::from_grid(x, y)))
+    }
+
+    fn find(&self, predicate: fn(&Cell) -> bool) -> Option<Pos> {
+        self.cells
+            .iter()
+            .enumerate()
+            .find_map(|(y, cells)|
+                cells.iter()
+                    .enumerate()
+                    .find_map(|(x, cell)|
+                        if predicate(cell) {
+                            Some(Pos::from_grid(x, y))
+                        } else {
+                            None
+                        }
+                    )
+            )
+    }
+
+    fn find_all(&self, predicate: fn(&Cell) -> bool) -> Vec<Pos> {
+        self.positions().filter(|pos| predicate(&self.at(pos))).collect()
+    }
+
+    fn neighbours(&self, pos: Pos) -> impl Iterator<Item=(Pos, Cell)> + '_ {
+        Self::NEIGHBOURS_DELTAS
+            .into_iter()
+            .map(move |delta| pos + delta)
+            .filter(|pos| self.contains(*pos))
+            .map(|pos| (pos, self.at(&pos)))
+    }
+}
+
+pub fn walk(
+    topology: Topology,
+    start_filter: fn(&Cell) -> bool,
+    neighbour_filter: fn(&Cell, &Cell) -> bool,
+    termination: fn(&Cell) -> bool,
+) -> Result<Vec<Pos>, Error> {
+    walk_allowed(topology, start_filter, neighbour_filter, termination, None)
+}
+
+// Same path as walk, but plateaus that cannot be on any path from the start to an end are ruled out
+// first, so on huge maps the BFS only floods the part of the map that matters.
+pub fn walk_contracted(
+    topology: Topology,
+    start_filter: fn(&Cell) -> bool,
+    neighbour_filter: fn(&Cell, &Cell) -> bool,
+    termination: fn(&Cell) -> bool,
+) -> Result<Vec<Pos>, Error> {
+    let start = topology.find(start_filter).ok_or(Error::NoStartFound)?;
+    let plateaus = Plateaus::contract(&topology, neighbour_filter);
+    let useful = plateaus.useful(&topology, start, termination);
+
+    if !useful[plateaus.id(start)] {
+        return Err(Error::NoPathFound);
+    }
+    let allowed = plateaus.ids.iter().map(|row| row.iter().map(|id| useful[*id]).collect()).collect::<Vec<Vec<bool>>>();
+    walk_allowed(topology, start_filter, neighbour_filter, termination, Some(&allowed))
+}
+
+// Breadth first from the start, so paths and frontiers come in reading order. Cells outside
+// `allowed` are never entered.
+fn search(
+    topology: &Topology,
+    start_filter: fn(&Cell) -> bool,
+    neighbour_filter: fn(&Cell, &Cell) -> bool,
+    termination: fn(&Cell) -> bool,
+    allowed: Option<&[Vec<bool>]>,
+) -> Result<Search<Pos, usize>, Error> {
+    let start = topology.find(start_filter).ok_or(Error::NoStartFound)?;
+    let successors = move |pos: &Pos| {
+        let cell = topology.at(pos);
+        topology
+            .neighbours(*pos)
+            .filter(move |(next, next_cell)| allowed.is_none_or(|allowed| *allowed.cell(*next)) && neighbour_filter(&cell, next_cell))
+            .map(|(next, _)| next)
+    };
+
+    Ok(pathfind::bfs([start], successors, |pos| *pos != start && termination(&topology.at(pos))))
+}
+
+fn walk_allowed(
+    topology: Topology,
+    start_filter: fn(&Cell) -> bool,
+    neighbour_filter: fn(&Cell, &Cell) -> bool,
+    termination: fn(&Cell) -> bool,
+    allowed: Option<&[Vec<bool>]>,
+) -> Result<Vec<Pos>, Error> {
+    search(&topology, start_filter, neighbour_filter, termination, allowed)?.path().ok_or(Error::NoPathFound)
+}
+
+// Areas of equal height, each contracted into a single node, with the moves allowed between them.
+// Walking across a plateau still costs steps, so the contracted graph only answers reachability.
+#[derive(Clone, Debug)]
+pub struct Plateaus {
+    ids: Vec<Vec<usize>>,
+    edges: Vec<BTreeSet<usize>>,
+}
+
+impl Plateaus {
+    // Equal heights must be walkable both ways under `neighbour_filter`, as they are for every
+    // filter the puzzle uses.
+    pub fn contract(topology: &Topology, neighbour_filter: fn(&Cell, &Cell) -> bool) -> Self {
+        let mut ids = vec![vec![usize::MAX; topology.columns]; topology.rows];
+        let mut plateaus = 0;
+
+        for pos in topology.positions() {
+            if *ids.cell(pos) != usize::MAX {
+                continue;
+            }
+
+            let height = topology.at(&pos).height();
+            paranoid_assert!(neighbour_filter(&Cell::Height(height), &Cell::Height(height)), "plateaus of height {} cannot be crossed", height);
+            *ids.cell_mut(pos) = plateaus;
+            let mut pending = vec![pos];
+            while let Some(curr) = pending.pop() {
+                for (next, cell) in topology.neighbours(curr) {
+                    if *ids.cell(next) == usize::MAX && cell.height() == height {
+                        *ids.cell_mut(next) = plateaus;
+                        pending.push(next);
+                    }
+                }
+            }
+            plateaus += 1;
+        }
+
+        let mut edges = vec![BTreeSet::new(); plateaus];
+        for pos in topology.positions() {
+            let (id, cell) = (*ids.cell(pos), topology.at(&pos));
+            for (next, next_cell) in topology.neighbours(pos) {
+                let next_id = *ids.cell(next);
+                if next_id != id && neighbour_filter(&cell, &next_cell) {
+                    edges[id].insert(next_id);
+                }
+            }
+        }
+
+        Plateaus { ids, edges }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    fn id(&self, pos: Pos) -> usize {
+        *self.ids.cell(pos)
+    }
+
+    fn reachable(edges: &[BTreeSet<usize>], from: impl IntoIterator<Item=usize>) -> Vec<bool> {
+        let mut reached = vec![false; edges.len()];
+        let mut pending = from.into_iter().collect::<Vec<_>>();
+        while let Some(id) = pending.pop() {
+            if !std::mem::replace(&mut reached[id], true) {
+                pending.extend(edges[id].iter().filter(|next| !reached[**next]));
+            }
+        }
+        reached
+    }
+
+    // Plateaus both reachable from the start and leading to a cell where the walk terminates.
+    fn useful(&self, topology: &Topology, start: Pos, termination: fn(&Cell) -> bool) -> Vec<bool> {
+        let mut reversed = vec![BTreeSet::new(); self.len()];
+        for (id, nexts) in self.edges.iter().enumerate() {
+            for next in nexts {
+                reversed[*next].insert(id);
+            }
+        }
+
+        let ends = topology.find_all(termination).into_iter().map(|pos| self.id(pos));
+        let from_start = Self::reachable(&self.edges, [self.id(start)]);
+        let to_end = Self::reachable(&reversed, ends);
+        from_start.iter().zip(to_end).map(|(from_start, to_end)| *from_start && to_end).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn between(from: &Pos, to: &Pos) -> Option<Self> {
+        match (*to - *from).into() {
+            (0, -1) => Some(Direction::Up),
+            (0, 1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            (1, 0) => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub direction: Direction,
+    pub length: usize,
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}", self.direction, self.length)
+    }
+}
+
+pub fn simplify_path(path: &[Pos]) -> Result<Vec<Segment>, Error> {
+    let mut segments: Vec<Segment> = Vec::new();
+
+    for step in path.windows(2) {
+        let direction = Direction::between(&step[0], &step[1]).ok_or(Error::DisjointPath(step[0], step[1]))?;
+
+        match segments.last_mut() {
+            Some(segment) if segment.direction == direction => segment.length += 1,
+            _ => segments.push(Segment { direction, length: 1 }),
+        }
+    }
+
+    Ok(segments)
+}
+
+pub fn count_turns(segments: &[Segment]) -> usize {
+    segments.len().saturating_sub(1)
+}
+
+struct SearchAnimation {
+    topology: Topology,
+    frontiers: Vec<Vec<Pos>>,
+}
+
+impl SearchAnimation {
+    fn record(
+        topology: Topology,
+        start_filter: fn(&Cell) -> bool,
+        neighbour_filter: fn(&Cell, &Cell) -> bool,
+        termination: fn(&Cell) -> bool,
+    ) -> Result<Self, Error> {
+        let search = search(&topology, start_filter, neighbour_filter, termination, None)?;
+        search.goal().ok_or(Error::NoPathFound)?;
+
+        // Cells reached at the same distance make one BFS frontier, the start being the first.
+        let mut frontiers = vec![Vec::new(); search.distances().values().max().map_or(0, |last| last + 1)];
+        for (pos, distance) in search.distances() {
+            frontiers[*distance].push(*pos);
+        }
+        frontiers.iter_mut().for_each(|frontier| frontier.sort());
+        Ok(SearchAnimation { topology, frontiers })
+    }
+
+    fn elevation(cell: Cell) -> Rgb {
+        style::ELEVATION.ramp(cell.height() as usize, Cell::MAX_HEIGHT as usize)
+    }
+}
+
+// Unvisited cells are shaded by elevation, visited ones by the step at which they were reached.
+impl Visualize for SearchAnimation {
+    fn frames(&self) -> Vec<Frame> {
+        let mut frame = Frame::new(self.topology.columns, self.topology.rows, style::ELEVATION.background);
+        for (y, cells) in self.topology.cells.iter().enumerate() {
+            for (x, cell) in cells.iter().enumerate() {
+                frame.set(x, y, Self::elevation(*cell));
+            }
+        }
+
+        let steps = self.frontiers.len().saturating_sub(1);
+        let mut frames = Vec::with_capacity(self.frontiers.len());
+        for (step, frontier) in self.frontiers.iter().enumerate() {
+            let color = style::SEARCH.ramp(step, steps);
+            for (x, y) in frontier.iter().filter_map(|pos| pos.to_grid()) {
+                frame.set(x, y, color);
+            }
+            frames.push(frame.clone());
+        }
+
+        frames
+    }
+}
+
+pub fn climbable(curr: &Cell, neighbour: &Cell) -> bool {
+    neighbour.height() <= curr.height() + 1
+}
+
+// Checks the path can be walked backwards, i.e. that it never drops by more than one step.
+pub fn reverse_path(topology: &Topology, path: &[Pos]) -> Result<Vec<Pos>, Error> {
+    let mut reversed = path.to_vec();
+    reversed.reverse();
+
+    for step in reversed.windows(2) {
+        if Direction::between(&step[0], &step[1]).is_none() {
+            return Err(Error::DisjointPath(step[0], step[1]));
+        }
+        if !climbable(&topology.at(&step[0]), &topology.at(&step[1])) {
+            return Err(Error::UnclimbableStep(step[0], step[1]));
+        }
+    }
+
+    Ok(reversed)
+}
+
+#[derive(Clone, Debug)]
+pub struct RoundTrip {
+    pub there: Vec<Pos>,
+    pub back: Vec<Pos>,
+}
+
+impl RoundTrip {
+    pub fn steps(&self) -> usize {
+        self.there.len() + self.back.len() - 2
+    }
+}
+
+// Both legs are independent, so the shortest way back need not be the way there.
+pub fn round_trip(topology: &Topology) -> Result<RoundTrip, Error> {
+    let there = walk(topology.clone(), Cell::is_start, climbable, Cell::is_end)?;
+    let back = walk(topology.clone(), Cell::is_end, climbable, Cell::is_start)?;
+
+    Ok(RoundTrip { there, back })
+}
+
+#[derive(Clone, Debug)]
+pub struct Arrival {
+    pub path: Vec<Pos>,
+    // Which of the map's ends was reached, counted in reading order.
+    pub endpoint: usize,
+}
+
+impl Arrival {
+    pub fn end(&self) -> Pos {
+        *self.path.last().unwrap()
+    }
+}
+
+// Maps may have several ends, the walk stops on whichever is closest, first in reading order on ties.
+pub fn nearest_end(topology: &Topology) -> Result<Arrival, Error> {
+    let ends = topology.find_all(Cell::is_end);
+    let path = walk(topology.clone(), Cell::is_start, climbable, Cell::is_end)?;
+    let endpoint = ends.iter().position(|end| Some(end) == path.last()).unwrap();
+
+    Ok(Arrival { path, endpoint })
+}
+
+pub fn run_challenge1(input: impl IntoInput) -> Result<Vec<Pos>, Error> {
+    let content = input.into_input()?;
+    let topology = Topology::parse(&content)?;
+    walk(
+        topology,
+        Cell::is_start,
+        climbable,
+        Cell::is_end,
+    )
+}
+
+// Climbing in reverse, from the end down to any lowest square.
+fn descendable(curr: &Cell, neighbour: &Cell) -> bool {
+    curr.height() == 0 || neighbour.height() >= curr.height() - 1
+}
+
+fn is_lowest(cell: &Cell) -> bool {
+    cell.height() == Cell::MIN_HEIGHT
+}
+
+pub fn run_challenge2(input: impl IntoInput) -> Result<Vec<Pos>, Error> {
+    let content = input.into_input()?;
+    let topology = Topology::parse(&content)?;
+    walk(
+        topology,
+        Cell::is_end,
+        descendable,
+        is_lowest,
+    )
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn example(&self) -> Example {
+        Example { input: include_str!("data/day12_example.txt"), answers: [Some("31"), Some("29")] }
+    }
+
+    fn parse(&self, input: &str) -> Result<(), crate::Error> {
+        Topology::parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok((run_challenge1(input)?.len() - 1).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, crate::Error> {
+        Ok((run_challenge2(input)?.len() - 1).into())
+    }
+
+    // The search spreading from the start, or from the end for the second part.
+    fn visualize(&self, part: u8, input: &str) -> Result<Vec<Frame>, crate::Error> {
+        let topology = Topology::parse(input)?;
+        let animation = match part {
+            1 => SearchAnimation::record(topology, Cell::is_start, climbable, Cell::is_end)?,
+            2 => SearchAnimation::record(topology, Cell::is_end, descendable, is_lowest)?,
+            _ => return Err(crate::Error::UnknownPart(part)),
+        };
+        Ok(animation.frames())
+    }
+
+    // The route as straight segments, the second part's walked from its lowest square up. The
+    // first part also tells how to get back to the start.
+    fn details(&self, part: u8, input: &str) -> Result<Vec<String>, crate::Error> {
+        let path = match part {
+            1 => run_challenge1(input)?,
+            2 => run_challenge2(input)?.into_iter().rev().collect(),
+            _ => return Err(crate::Error::UnknownPart(part)),
+        };
+        let segments = simplify_path(&path)?;
+
+        let mut details = segments.iter().map(Segment::to_string).collect::<Vec<_>>();
+        details.push(format!("{} turns", count_turns(&segments)));
+        if part == 1 {
+            let topology = Topology::parse(input)?;
+            details.push(match round_trip(&topology) {
+                Ok(trip) => format!(
+                    "round trip: {} steps there, {} back, {} in all",
+                    trip.there.len() - 1,
+                    trip.back.len() - 1,
+                    trip.steps(),
+                ),
+                Err(Error::NoPathFound) => "no way back to the start".to_string(),
+                Err(e) => return Err(e.into()),
+            });
+            details.push(match reverse_path(&topology, &path) {
+                Ok(_) => "the way there can be walked back".to_string(),
+                Err(e) => format!("the way there cannot be walked back: {}", e),
+            });
+        }
+        Ok(details)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Input(#[from] crate::input::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nom(#[from] nom::error::Error<String>),
+    #[error("Empty input")]
+    EmptyInput,
+    #[error("All lines should be of the same size")]
+    InvalidLineSize,
+    #[error("No start found")]
+    NoStartFound,
+    #[error("No path found")]
+    NoPathFound,
+    #[error("Path jumps from {0:?} to {1:?}")]
+    DisjointPath(Pos, Pos),
+    #[error("Path climbs too steeply from {0:?} to {1:?}")]
+    UnclimbableStep(Pos, Pos),
+    #[error("{0:?} is outside the map")]
+    OutOfBounds(Pos),
+    #[cfg(feature = "image")]
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+}
+
+impl From<Error> for crate::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Input(e) => crate::Error::Input(e),
+            Error::Io(e) => crate::Error::Io(e),
+            e @ (Error::Nom(..) | Error::EmptyInput | Error::InvalidLineSize) => crate::Error::parse(e),
+            e @ (Error::NoStartFound | Error::DisjointPath(..) | Error::UnclimbableStep(..) | Error::OutOfBounds(..)) => crate::Error::validation(e),
+            e @ Error::NoPathFound => crate::Error::solve(e),
+            #[cfg(feature = "image")]
+            e @ Error::Image(..) => crate::Error::output(e),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::Strategy as _;
+
+    use crate::year2022::day12::*;
+
+    #[test]
+    fn challenge1_example() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day12_example.txt"))?;
+        assert_eq!(result.len() - 1, 31);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge1() -> Result<(), Error> {
+        let result = run_challenge1(include_str!("data/day12_challenge.txt"))?;
+        dbg!(result.len() - 1);
+        assert_eq!(result.len() - 1, 352);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2_example() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day12_example.txt"))?;
+        assert_eq!(result.len() - 1, 29);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge2() -> Result<(), Error> {
+        let result = run_challenge2(include_str!("data/day12_challenge.txt"))?;
+        assert_eq!(result.len() - 1, 345);
+        Ok(())
+    }
+
+    #[test]
+    fn simplified_path() -> Result<(), Error> {
+        let path = run_challenge1(include_str!("data/day12_example.txt"))?;
+        let segments = simplify_path(&path)?;
+
+        assert_eq!(segments.iter().map(|s| s.length).sum::<usize>(), 31);
+        assert_eq!(count_turns(&segments), segments.len() - 1);
+        assert!(segments.windows(2).all(|w| w[0].direction != w[1].direction));
+
+        let path = [Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }, Pos { x: 2, y: 0 }, Pos { x: 2, y: 1 }];
+        assert_eq!(
+            simplify_path(&path)?,
+            vec![
+                Segment { direction: Direction::Right, length: 2 },
+                Segment { direction: Direction::Down, length: 1 },
+            ]
+        );
+
+        assert!(matches!(
+            simplify_path(&[Pos { x: 0, y: 0 }, Pos { x: 1, y: 1 }]),
+            Err(Error::DisjointPath(_, _))
+        ));
+
+        assert_eq!(
+            Day12.details(1, include_str!("data/day12_example.txt")).unwrap(),
+            [
+                "Right 2", "Down 4", "Right 5", "Up 4", "Left 4", "Down 3", "Right 3", "Up 2", "Left 2", "Down 1", "Right 1",
+                "10 turns",
+                "round trip: 31 steps there, 7 back, 38 in all",
+                "the way there can be walked back",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_paths() -> Result<(), Error> {
+        let content = include_str!("data/day12_challenge.txt");
+        let path = run_challenge1(content)?;
+        for _ in 0..5 {
+            assert_eq!(run_challenge1(content)?, path);
+        }
+
+        let animation = SearchAnimation::record(Topology::parse(content)?, Cell::is_start, climbable, Cell::is_end)?;
+        assert!(animation.frontiers.iter().all(|frontier| frontier.windows(2).all(|pair| pair[0] < pair[1])));
+        Ok(())
+    }
+
+    #[test]
+    fn way_back() -> Result<(), Error> {
+        let topology = Topology::parse(include_str!("data/day12_example.txt"))?;
+        let path = run_challenge1(include_str!("data/day12_example.txt"))?;
+
+        let trip = round_trip(&topology)?;
+        assert_eq!(trip.there.len() - 1, 31);
+        assert_eq!(trip.back.first(), path.last());
+        assert_eq!(trip.back.last(), path.first());
+        assert_eq!(trip.back.len() - 1, 7);
+        assert_eq!(trip.steps(), 38);
+
+        let reversed = reverse_path(&topology, &path)?;
+        assert_eq!(reversed.first(), path.last());
+        assert_eq!(reversed.len() - 1, 31);
+
+        let cliff = [Pos { x: 5, y: 2 }, Pos { x: 5, y: 1 }];
+        assert!(matches!(reverse_path(&topology, &cliff), Err(Error::UnclimbableStep(_, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn several_ends() -> Result<(), Error> {
+        let arrival = nearest_end(&Topology::parse(include_str!("data/day12_example.txt"))?)?;
+        assert_eq!((arrival.endpoint, arrival.end(), arrival.path.len() - 1), (0, Pos { x: 5, y: 2 }, 31));
+
+        // 26 steps to the left end, 25 to the right one.
+        let row = format!("E{}S{}E", "zyxwvutsrqponmlkjihgfedcb", "bcdefghijklmnopqrstuvwxy");
+        let arrival = nearest_end(&Topology::parse(&row)?)?;
+        assert_eq!((arrival.endpoint, arrival.end(), arrival.path.len() - 1), (1, Pos { x: 51, y: 0 }, 25));
+
+        let tie = nearest_end(&Topology::parse(&format!("E{}S{}E", "yxwvutsrqponmlkjihgfedcb", "bcdefghijklmnopqrstuvwxy"))?)?;
+        assert_eq!((tie.endpoint, tie.end()), (0, Pos { x: 0, y: 0 }));
+        Ok(())
+    }
+
+    // Smooth random terrain, so that plateaus are large, with cliffs that are not always climbable.
+    fn arbitrary_topology() -> impl proptest::strategy::Strategy<Value = Topology> {
+        (1..24_usize, 1..18_usize)
+            .prop_flat_map(|(columns, rows)| {
+                let size = (proptest::strategy::Just(columns), proptest::strategy::Just(rows));
+                let changes = proptest::collection::vec((0..10_u8, 0..40_u8), columns * rows - 1);
+                (size, proptest::num::u8::ANY, changes, (0..columns, 0..rows), (0..columns, 0..rows))
+            })
+            .prop_map(|((columns, rows), first, changes, start, end)| {
+                let mut levels = vec![first; columns * rows];
+                for (index, (kind, change)) in (1..levels.len()).zip(changes) {
+                    let base = if index % columns == 0 { levels[index - columns] } else { levels[index - 1] };
+                    levels[index] = match kind {
+                        0 => base.saturating_add(change),
+                        1 => base.saturating_sub(change),
+                        _ => base,
+                    };
+                }
+                let endpoints = Endpoints::Explicit { start: Pos::from_grid(start.0, start.1), end: Pos::from_grid(end.0, end.1) };
+                Topology::from_gray(columns, rows, &levels, endpoints).unwrap()
+            })
+    }
+
+    #[test]
+    fn contracted_walk() -> Result<(), Error> {
+        let topology = Topology::parse(include_str!("data/day12_challenge.txt"))?;
+        let plateaus = Plateaus::contract(&topology, climbable);
+        assert!(plateaus.len() < topology.rows * topology.columns);
+        assert_eq!(walk_contracted(topology.clone(), Cell::is_start, climbable, Cell::is_end)?.len() - 1, 352);
+        Ok(())
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn contracted_walk_matches_plain_one(topology in arbitrary_topology()) {
+            let steps = |result: Result<Vec<Pos>, Error>| result.map(|path| path.len()).map_err(|e| e.to_string());
+            let plain = steps(walk(topology.clone(), Cell::is_start, climbable, Cell::is_end));
+            let contracted = steps(walk_contracted(topology, Cell::is_start, climbable, Cell::is_end));
+            proptest::prop_assert_eq!(plain, contracted);
+        }
+    }
+
+    #[test]
+    fn search_animation() -> Result<(), Error> {
+        let topology = Topology::parse(include_str!("data/day12_example.txt"))?;
+        let animation = SearchAnimation::record(
+            topology.clone(),
+            Cell::is_start,
+            |curr, neighbour| neighbour.height() <= curr.height() + 1,
+            Cell::is_end,
+        )?;
+
+        let frames = animation.frames();
+        assert_eq!(frames.len(), 32);
+        assert_eq!(animation.frontiers[0], vec![Pos { x: 0, y: 0 }]);
+        assert_eq!(frames[0].get(0, 0), style::SEARCH.low);
+        assert_eq!(frames[0].get(1, 0), SearchAnimation::elevation(topology.at(&Pos { x: 1, y: 0 })));
+
+        let (x, y) = topology.find(Cell::is_end).and_then(Pos::to_grid).unwrap();
+        assert_eq!(frames[30].get(x, y), SearchAnimation::elevation(Cell::End(Cell::MAX_HEIGHT)));
+        assert_eq!(frames[31].get(x, y), style::SEARCH.high);
+
+        let content = include_str!("data/day12_example.txt");
+        assert_eq!(Day12.visualize(1, content).unwrap(), frames);
+        assert_eq!(Day12.visualize(2, content).unwrap().len(), 30);
+        Ok(())
+    }
+
+    #[test]
+    fn gray_import() -> Result<(), Error> {
+        // The bright pixel is an unclimbable peak, which becomes the end by default.
+        let levels = [
+            0, 10, 20, 30,
+            10, 20, 255, 40,
+            20, 30, 40, 50,
+        ];
+        let topology = Topology::from_gray(4, 3, &levels, Endpoints::Extremes)?;
+        assert_eq!(topology.find(Cell::is_start), Some(Pos { x: 0, y: 0 }));
+        assert_eq!(topology.find(Cell::is_end), Some(Pos { x: 2, y: 1 }));
+        assert_eq!(topology.at(&Pos { x: 3, y: 2 }).height(), 4);
+
+        let endpoints = Endpoints::Explicit { start: Pos { x: 0, y: 0 }, end: Pos { x: 3, y: 2 } };
+        let topology = Topology::from_gray(4, 3, &levels, endpoints)?;
+        let path = walk(topology, Cell::is_start, climbable, Cell::is_end)?;
+        assert_eq!(path.len() - 1, 5);
+
+        assert!(matches!(
+            Topology::from_gray(4, 3, &levels, Endpoints::Explicit { start: Pos { x: 4, y: 0 }, end: Pos { x: 0, y: 0 } }),
+            Err(Error::OutOfBounds(_))
+        ));
+        assert!(matches!(Topology::from_gray(4, 2, &levels, Endpoints::Extremes), Err(Error::InvalidLineSize)));
+        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn image_import() -> Result<(), Error> {
+        let path = camino::Utf8PathBuf::from_path_buf(std::env::temp_dir().join("aoc22_day12_terrain.png")).unwrap();
+        let image = image::GrayImage::from_fn(8, 4, |x, y| image::Luma([(x * 10 + y * 5) as u8]));
+        image.save(&path)?;
+
+        let topology = Topology::from_image(&path, Endpoints::Extremes)?;
+        assert_eq!((topology.columns, topology.rows), (8, 4));
+        assert_eq!(topology.find(Cell::is_end), Some(Pos { x: 7, y: 3 }));
+        assert_eq!(walk(topology, Cell::is_start, climbable, Cell::is_end)?.len() - 1, 10);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}